@@ -4,6 +4,7 @@ use crate::{
     animation::ui_standard::AnimateTerminateUnit,
     game::{
         battle::event::{AttackEvent, BattleEvent},
+        character::component::HeroClass,
         overlay::battle_scene::BattleHeroSprite,
     },
     preload::sprites::{HeroAction, HeroActionHandles, HeroActionTextureAtLasHandles},
@@ -32,22 +33,26 @@ pub struct BattleHeroActionBundle {
 }
 
 impl BattleHeroActionBundle {
-    fn idle(image: Handle<Image>, texture_atlas: Handle<TextureAtlasLayout>) -> Self {
+    fn idle(image: Handle<Image>, texture_atlas: Handle<TextureAtlasLayout>, tint: Color) -> Self {
         BattleHeroActionBundle {
             timer: AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
             index: AnimationIndices::new(9, true),
-            image: UiImage::new(image),
+            image: UiImage::new(image).with_color(tint),
             texture_atlas: TextureAtlas {
                 layout: texture_atlas,
                 index: 0,
             },
         }
     }
-    fn attack(image: Handle<Image>, texture_atlas: Handle<TextureAtlasLayout>) -> Self {
+    fn attack(
+        image: Handle<Image>,
+        texture_atlas: Handle<TextureAtlasLayout>,
+        tint: Color,
+    ) -> Self {
         BattleHeroActionBundle {
             timer: AnimationTimer(Timer::from_seconds(0.3, TimerMode::Repeating)),
             index: AnimationIndices::new(3, false),
-            image: UiImage::new(image),
+            image: UiImage::new(image).with_color(tint),
             texture_atlas: TextureAtlas {
                 layout: texture_atlas,
                 index: 0,
@@ -55,11 +60,11 @@ impl BattleHeroActionBundle {
         }
     }
 
-    fn death(image: Handle<Image>, texture_atlas: Handle<TextureAtlasLayout>) -> Self {
+    fn death(image: Handle<Image>, texture_atlas: Handle<TextureAtlasLayout>, tint: Color) -> Self {
         BattleHeroActionBundle {
             timer: AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
             index: AnimationIndices::new(9, false),
-            image: UiImage::new(image),
+            image: UiImage::new(image).with_color(tint),
             texture_atlas: TextureAtlas {
                 layout: texture_atlas,
                 index: 0,
@@ -90,17 +95,18 @@ impl Plugin for BattleHeroAnimationPlugin {
 
 pub fn spawn_battle_hero_anime(
     mut commands: Commands,
-    hero_query: Query<Entity, Added<BattleHeroSprite>>,
+    hero_query: Query<(Entity, &HeroClass), Added<BattleHeroSprite>>,
     hero_sprite: Res<HeroActionHandles>,
     hero_texture_atlas: Res<HeroActionTextureAtLasHandles>,
 ) {
-    for hero in &hero_query {
+    for (hero, class) in &hero_query {
         info!("Spawning Idle Timer");
         commands.entity(hero).insert((
             BattleHeroAction::Idle,
             BattleHeroActionBundle::idle(
                 hero_sprite[&HeroAction::Idle].clone(),
                 hero_texture_atlas[&HeroAction::Idle].clone(),
+                class.tint(),
             ),
         ));
     }
@@ -125,7 +131,12 @@ pub fn trigger_battle_attack_anime(
                 hero.set_if_neq(BattleHeroAction::Attack(0));
             } else {
                 info!("Hero were attacked");
-                let sliding_anime = UiSliding::new(UiRect::left(Val::Px(-30.)), true, 3.);
+                let knockback = if ev.record.damage_out.is_critical() {
+                    -60.
+                } else {
+                    -30.
+                };
+                let sliding_anime = UiSliding::new(UiRect::left(Val::Px(knockback)), true, 3.);
                 if let Some(mut sliding) = sliding {
                     *sliding = sliding_anime;
                 } else {
@@ -157,15 +168,11 @@ pub fn handle_action(
 ) {
     for (mut action, index, texture_atlas, sliding) in &mut sprite_query {
         match *action {
-            BattleHeroAction::Attack(0) => {
-                if sliding.is_finished {
-                    *action = BattleHeroAction::Attack(1);
-                }
+            BattleHeroAction::Attack(0) if sliding.is_finished => {
+                *action = BattleHeroAction::Attack(1);
             }
-            BattleHeroAction::Attack(1..) => {
-                if index.last == texture_atlas.index {
-                    *action = BattleHeroAction::Idle;
-                }
+            BattleHeroAction::Attack(1..) if index.last == texture_atlas.index => {
+                *action = BattleHeroAction::Idle;
             }
             _ => {}
         }
@@ -183,13 +190,14 @@ pub fn handle_battle_hero_anime_update(
             &mut UiImage,
             &mut TextureAtlas,
             Option<&mut UiSliding>,
+            &HeroClass,
         ),
         Changed<BattleHeroAction>,
     >,
     hero_sprite: Res<HeroActionHandles>,
     hero_texture_atlas: Res<HeroActionTextureAtLasHandles>, // time: Res<Time>,
 ) {
-    for (entity, action, mut timer, mut indices, mut image, mut texture_atlas, sliding) in
+    for (entity, action, mut timer, mut indices, mut image, mut texture_atlas, sliding, class) in
         &mut sprite_query
     {
         match *action {
@@ -198,6 +206,7 @@ pub fn handle_battle_hero_anime_update(
                 let bundle = BattleHeroActionBundle::idle(
                     hero_sprite[&HeroAction::Idle].clone(),
                     hero_texture_atlas[&HeroAction::Idle].clone(),
+                    class.tint(),
                 );
 
                 *timer = bundle.timer;
@@ -211,6 +220,7 @@ pub fn handle_battle_hero_anime_update(
                 let bundle = BattleHeroActionBundle::attack(
                     hero_sprite[&HeroAction::Attack].clone(),
                     hero_texture_atlas[&HeroAction::Attack].clone(),
+                    class.tint(),
                 );
 
                 let sliding_anime = UiSliding::new(
@@ -239,6 +249,7 @@ pub fn handle_battle_hero_anime_update(
                 let bundle = BattleHeroActionBundle::death(
                     hero_sprite[&HeroAction::Death].clone(),
                     hero_texture_atlas[&HeroAction::Death].clone(),
+                    class.tint(),
                 );
 
                 *timer = bundle.timer;
@@ -3,8 +3,12 @@ use bevy::prelude::*;
 use crate::{
     animation::ui_standard::AnimateTerminateUnit,
     game::{
-        battle::event::{AttackEvent, BattleEvent},
+        battle::{
+            component::{Combat, TurnFlag},
+            event::{AttackEvent, BattleEvent},
+        },
         character::component::BattleMonsterSprite,
+        overlay::battle_scene::BattleMonster,
     },
     state::OverlayCombatState,
 };
@@ -15,7 +19,9 @@ use super::{
     ui_standard::{Blinking, UiSliding, VerticalCollapse},
 };
 
+// monster attack frames are not drawn yet, see the constructors below
 #[derive(Component, Default, PartialEq, Reflect)]
+#[allow(dead_code)]
 pub enum BattleMonsterAction {
     #[default]
     Idle,
@@ -23,6 +29,7 @@ pub enum BattleMonsterAction {
 }
 
 #[derive(Bundle)]
+#[allow(dead_code)]
 pub struct BattleMonsterActionBundle {
     timer: AnimationTimer,
     index: AnimationIndices,
@@ -66,6 +73,7 @@ impl Plugin for BattleMonsterAnimationPlugin {
                 spawn_battle_monster_anime,
                 trigger_battle_attack_anime,
                 handle_battle_end,
+                collapse_defeated_monster,
             )
                 .run_if(in_state(OverlayCombatState::Opened)),
         );
@@ -89,11 +97,17 @@ pub fn spawn_battle_monster_anime(
 pub fn trigger_battle_attack_anime(
     mut commands: Commands,
     mut attack_event: EventReader<AttackEvent>,
-    mut monster_query: Query<(Entity, Option<&mut UiSliding>), With<BattleMonsterSprite>>,
+    mut monster_query: Query<(Entity, &Parent, Option<&mut UiSliding>), With<BattleMonsterSprite>>,
+    stand_query: Query<&BattleMonster>,
 ) {
     for ev in attack_event.read() {
-        for (monster, sliding) in &mut monster_query {
-            if !ev.record.is_player_turn {
+        for (monster, parent, sliding) in &mut monster_query {
+            // only the one taking the turn lunges
+            let is_attacker = stand_query
+                .get(parent.get())
+                .is_ok_and(|stand| stand.monster == ev.record.monster);
+
+            if !ev.record.is_player_turn && is_attacker {
                 let sliding_anime = UiSliding::new(
                     UiRect {
                         bottom: Val::Px(-100.),
@@ -117,16 +131,36 @@ pub fn trigger_battle_attack_anime(
 pub fn handle_being_attacked(
     mut commands: Commands,
     mut sprite_query: Query<
-        (Entity, &mut Blinking, Option<&mut UiSliding>),
+        (Entity, &Parent, &mut Blinking, Option<&mut UiSliding>),
         With<BattleMonsterSprite>,
     >,
-
+    stand_query: Query<&BattleMonster>,
+    turn_query: Query<&TurnFlag>,
     hero_query: Query<&BattleHeroAction, Changed<BattleHeroAction>>,
 ) {
+    // the hero is swinging at whoever the current record points to
+    let record = turn_query
+        .get_single()
+        .ok()
+        .and_then(|flag| flag.record.as_ref());
+    let target = record.map(|record| record.monster);
+    // critical hits knock the monster back further
+    let knockback = match record {
+        Some(record) if record.damage_out.is_critical() => 60.,
+        _ => 30.,
+    };
+
     for hero in hero_query.iter() {
         if *hero == BattleHeroAction::Attack(1) {
-            for (entity, mut blinking, sliding) in sprite_query.iter_mut() {
-                let sliding_anime = UiSliding::new(UiRect::left(Val::Px(30.)), true, 3.);
+            for (entity, parent, mut blinking, sliding) in sprite_query.iter_mut() {
+                let is_target = stand_query
+                    .get(parent.get())
+                    .is_ok_and(|stand| Some(stand.monster) == target);
+                if target.is_some() && !is_target {
+                    continue;
+                }
+
+                let sliding_anime = UiSliding::new(UiRect::left(Val::Px(knockback)), true, 3.);
                 if let Some(mut sliding) = sliding {
                     *sliding = sliding_anime;
                 } else {
@@ -146,7 +180,7 @@ pub fn handle_battle_end(
 ) {
     for ev in battle_event.read() {
         if ev.is_player_victory {
-            if let Ok((entity, sliding)) = sprite_query.get_single_mut() {
+            for (entity, sliding) in sprite_query.iter_mut() {
                 commands.entity(entity).insert(VerticalCollapse);
                 let sliding_anime = UiSliding::new(UiRect::bottom(Val::Px(50.)), false, 1.);
                 if let Some(mut sliding) = sliding {
@@ -159,6 +193,28 @@ pub fn handle_battle_end(
     }
 }
 
+// Monsters that fall mid fight leave the arena while the rest keep going
+pub fn collapse_defeated_monster(
+    mut commands: Commands,
+    battle_query: Query<&Combat, Changed<Combat>>,
+    sprite_query: Query<(Entity, &Parent), (With<BattleMonsterSprite>, Without<VerticalCollapse>)>,
+    stand_query: Query<&BattleMonster>,
+) {
+    let Ok(battle) = battle_query.get_single() else {
+        return;
+    };
+
+    for (entity, parent) in sprite_query.iter() {
+        let Ok(stand) = stand_query.get(parent.get()) else {
+            continue;
+        };
+
+        if battle.foe(stand.monster).is_some_and(|foe| !foe.is_alive()) {
+            commands.entity(entity).insert(VerticalCollapse);
+        }
+    }
+}
+
 // pub fn handle_battle_monster_anime_update(
 //     mut commands: Commands,
 //     mut sprite_query: Query<
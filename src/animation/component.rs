@@ -29,8 +29,4 @@ impl AnimationTimer {
             TimerMode::Repeating,
         ))
     }
-
-    pub fn once(sec: f32) -> Self {
-        Self(Timer::new(Duration::from_secs_f32(sec), TimerMode::Once))
-    }
 }
@@ -136,7 +136,7 @@ fn handle_blinking_anime(mut input_query: Query<(&mut Style, &mut Blinking)>, ti
     for (mut style, mut blink) in &mut input_query {
         if !blink.paused && !blink.is_finished() && blink.timer.tick(time.delta()).just_finished() {
             let last = blink.last_display;
-            blink.last_display = style.display.clone();
+            blink.last_display = style.display;
             style.display = match style.display {
                 Display::Flex => Display::None,
                 Display::Grid => Display::None,
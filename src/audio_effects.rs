@@ -1,3 +1,6 @@
+// Not wired into the app yet, which triggers this lint undesirably.
+#![allow(dead_code)]
+
 use bevy::{audio::Volume, prelude::*};
 
 use crate::preload::audio::{AudioEffectAsset, AudioEffectHandles};
@@ -19,19 +22,15 @@ fn interact_with_button(
     effect_assets: Res<AudioEffectHandles>,
 ) {
     for interaction in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                commands.spawn(AudioBundle {
-                    source: effect_assets[&AudioEffectAsset::ButtonClick].clone(),
-                    settings: PlaybackSettings {
-                        volume: Volume::new(0.2),
-                        mode: bevy::audio::PlaybackMode::Despawn,
-                        ..default()
-                    },
-                });
-            }
-
-            _ => (),
+        if *interaction == Interaction::Pressed {
+            commands.spawn(AudioBundle {
+                source: effect_assets[&AudioEffectAsset::ButtonClick].clone(),
+                settings: PlaybackSettings {
+                    volume: Volume::new(0.2),
+                    mode: bevy::audio::PlaybackMode::Despawn,
+                    ..default()
+                },
+            });
         }
     }
 }
@@ -1,3 +1,6 @@
+// Not wired into the app yet, which triggers this lint undesirably.
+#![allow(dead_code)]
+
 use bevy::{audio::Volume, prelude::*};
 
 pub struct AudioHelperPlugin;
@@ -8,7 +8,7 @@ use crate::{
     game::{
         character::component::{BaseStates, Hero},
         map::{preload::Location, ChangeMapEvent},
-        player::{DecendingHero, Player, PLAYER_START_POSITION},
+        player::{DecendingHero, Player},
     },
     helper::{SmoothDamp, Velocity, Zooming},
     state::{Screen, SimulationState},
@@ -19,25 +19,25 @@ use super::MainCamera;
 // const ASPECT_RATIO: f32 = 1.;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Playing), init_camera_position)
-        .add_systems(
-            Update,
-            update_smooth_damp_camera_w_decending.run_if(in_state(Screen::Playing)),
+    app.add_systems(
+        Update,
+        // the start of a run lands on a spawn point before the simulation runs
+        (update_smooth_damp_camera_w_decending, spawn_camera_position)
+            .run_if(in_state(Screen::Playing)),
+    )
+    .add_systems(
+        Update,
+        (
+            update_smooth_damp_camera,
+            update_camera_w_player,
+            zoom_in,
+            zoom_w_camera,
+            // camera_fit_inside_current_level.after(update_camera_w_player),
         )
-        .add_systems(
-            Update,
-            (
-                update_smooth_damp_camera,
-                update_camera_w_player,
-                spawn_camera_position,
-                zoom_in,
-                zoom_w_camera,
-                // camera_fit_inside_current_level.after(update_camera_w_player),
-            )
-                .run_if(in_state(SimulationState::Running))
-                .run_if(in_state(Screen::Playing)),
-        )
-        .register_type::<Zooming>();
+            .run_if(in_state(SimulationState::Running))
+            .run_if(in_state(Screen::Playing)),
+    )
+    .register_type::<Zooming>();
 }
 
 pub fn zoom_w_camera(
@@ -76,12 +76,6 @@ pub fn zoom_in(
     }
 }
 
-pub fn init_camera_position(mut camera_query: Query<&mut Transform, With<MainCamera>>) {
-    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-        camera_transform.translation = PLAYER_START_POSITION;
-    }
-}
-
 pub fn update_camera_w_player(
     mut camera_query: Query<(&mut Transform, &Velocity), With<MainCamera>>,
 ) {
@@ -137,21 +131,19 @@ pub fn update_smooth_damp_camera(
 
 pub fn spawn_camera_position(
     mut camera_query: Query<&mut Transform, With<MainCamera>>,
-    location_query: Query<(&Transform, &Location), (With<Location>, Without<MainCamera>)>,
+    location_query: Query<&Transform, (With<Location>, Without<MainCamera>)>,
     mut event_reader: EventReader<ChangeMapEvent>,
 ) {
     for event in event_reader.read() {
-        if let Some((destination, _)) = location_query
-            .iter()
-            .find(|(_, location)| location.equal(&event.location))
-        {
-            let Ok(mut camera_transform) = camera_query.get_single_mut() else {
-                warn!("Player is not found during map switching");
-                continue;
-            };
+        let Ok(destination) = location_query.get(event.spawn_point) else {
+            continue;
+        };
+        let Ok(mut camera_transform) = camera_query.get_single_mut() else {
+            warn!("Player is not found during map switching");
+            continue;
+        };
 
-            camera_transform.translation = destination.translation;
-        }
+        camera_transform.translation = destination.translation;
     }
 }
 
@@ -15,7 +15,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(game::plugin);
 }
 
-/// Default render layers for pixel-perfect rendering.
+// /// Default render layers for pixel-perfect rendering.
 // /// You can skip adding this component, as this is the default.
 // const SHADOW_LAYERS: RenderLayers = RenderLayers::layer(0);
 
@@ -24,8 +24,8 @@ pub(super) fn plugin(app: &mut App) {
 
 // const POSTPROCESS_LAYERS: RenderLayers = RenderLayers::layer(10);
 
-/// Low-resolution texture that contains the pixel-perfect world.
-/// Canvas itself is rendered to the high-resolution world.
+// /// Low-resolution texture that contains the pixel-perfect world.
+// /// Canvas itself is rendered to the high-resolution world.
 // #[derive(Component)]
 // struct Canvas;
 
@@ -0,0 +1,349 @@
+use bevy::prelude::*;
+
+use crate::{
+    model::{achievement::AchievementBook, player::AchievementSave, weapon::WeaponData, ItemType},
+    preload::data::AchievementPreloadHandler,
+    state::Screen,
+};
+
+use super::{
+    battle::event::{AttackEvent, BattleEvent, SpawnDropSceneEvent},
+    character::component::{BaseStates, Boss, Hero},
+    meta::RunRecord,
+    player::Storage,
+    saving::{write_profile, Profile},
+    ChangeMapEvent, Player,
+};
+
+// counters move on every hit, so they are written at most this often unless something unlocks
+const SAVE_DELAY_SECS: f32 = 10.;
+
+pub struct AchievementPlugin;
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AchievementUnlockedEvent>()
+            .init_resource::<AchievementTracker>()
+            .add_systems(
+                Update,
+                (
+                    load_achievements,
+                    (
+                        count_critical_hits,
+                        count_battles,
+                        count_drops,
+                        count_travels,
+                        track_hero_level,
+                        track_weapon_elements,
+                    ),
+                    unlock_achievements,
+                    save_achievements,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, (spawn_achievement_toast, update_achievement_toast))
+            .add_systems(OnEnter(Screen::GameOver), count_flawless_run);
+    }
+}
+
+#[derive(Event)]
+pub struct AchievementUnlockedEvent {
+    pub name: String,
+}
+
+// Lifetime counters and unlocked ids, mirrored into the profile
+#[derive(Resource, Debug, Clone, Default, Deref, DerefMut)]
+pub struct AchievementTracker(pub AchievementSave);
+
+impl AchievementTracker {
+    // names of the achievements unlocked by this call
+    pub fn unlock(&mut self, book: &AchievementBook) -> Vec<String> {
+        let mut names = Vec::new();
+        for data in book.0.iter() {
+            if self.unlocked.contains(&data.id) || !data.condition.is_met(&self.0) {
+                continue;
+            }
+
+            self.unlocked.push(data.id);
+            names.push(data.name.clone());
+        }
+        names
+    }
+}
+
+// Banner at the top of the screen, gone once the timer runs out
+#[derive(Component)]
+pub struct AchievementToast(pub Timer);
+
+fn load_achievements(profile: Option<Res<Profile>>, mut tracker: ResMut<AchievementTracker>) {
+    let Some(profile) = profile else {
+        return;
+    };
+    if profile.is_added() {
+        tracker.0 = profile.achievements.clone();
+    }
+}
+
+// An unlock is written right away, counter changes wait for the delay
+fn save_achievements(
+    tracker: Res<AchievementTracker>,
+    profile: Option<ResMut<Profile>>,
+    mut unlocked_event_reader: EventReader<AchievementUnlockedEvent>,
+    time: Res<Time>,
+    mut pending: Local<Option<Timer>>,
+) {
+    if tracker.is_changed() && !tracker.is_added() && pending.is_none() {
+        *pending = Some(Timer::from_seconds(SAVE_DELAY_SECS, TimerMode::Once));
+    }
+    let unlocked = unlocked_event_reader.read().count() > 0;
+    let Some(timer) = pending.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).finished() && !unlocked {
+        return;
+    }
+    let Some(mut profile) = profile else {
+        return;
+    };
+
+    *pending = None;
+    profile.achievements = tracker.0.clone();
+    write_profile(&profile);
+}
+
+fn count_critical_hits(
+    mut attack_event_reader: EventReader<AttackEvent>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    for ev in attack_event_reader.read() {
+        if ev.record.is_player_turn && ev.record.damage_out.is_critical() {
+            tracker.critical_hits += 1;
+        }
+    }
+}
+
+fn count_battles(
+    mut battle_event_reader: EventReader<BattleEvent>,
+    boss_query: Query<(), With<Boss>>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    for ev in battle_event_reader.read() {
+        if !ev.is_player_victory {
+            continue;
+        }
+        let bosses = ev
+            .monsters
+            .iter()
+            .filter(|entity| boss_query.contains(**entity))
+            .count() as u32;
+
+        tracker.monsters_defeated += ev.monsters.len() as u32;
+        tracker.bosses_defeated += bosses;
+    }
+}
+
+fn count_drops(
+    mut drop_event_reader: EventReader<SpawnDropSceneEvent>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    for _ in drop_event_reader.read() {
+        tracker.drops_found += 1;
+    }
+}
+
+fn count_travels(
+    mut change_map_event_reader: EventReader<ChangeMapEvent>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    // landing at the start of a run is not a trip
+    for _ in change_map_event_reader
+        .read()
+        .filter(|event| !event.run_start)
+    {
+        tracker.travels += 1;
+    }
+}
+
+fn track_hero_level(
+    hero_query: Query<&BaseStates, (With<Hero>, Changed<BaseStates>)>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    let Ok(base) = hero_query.get_single() else {
+        return;
+    };
+    if base.lv() > tracker.best_lv {
+        tracker.best_lv = base.lv();
+    }
+}
+
+fn track_weapon_elements(
+    storage_query: Query<&Storage, (With<Player>, Changed<Storage>)>,
+    weapon_assets: Res<Assets<WeaponData>>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    let Ok(storage) = storage_query.get_single() else {
+        return;
+    };
+
+    let mut elements = Vec::new();
+    for owned in storage.items.get(&ItemType::Weapon).into_iter().flatten() {
+        let element = weapon_assets
+            .iter()
+            .find(|(_, data)| data.id == owned.id)
+            .map(|(_, data)| data.element.clone());
+        if let Some(element) = element {
+            if !elements.contains(&element) {
+                elements.push(element);
+            }
+        }
+    }
+
+    let count = elements.len() as u32;
+    if count > tracker.weapon_elements {
+        tracker.weapon_elements = count;
+    }
+}
+
+// A run that won at least once and never fainted
+fn count_flawless_run(record: Res<RunRecord>, mut tracker: ResMut<AchievementTracker>) {
+    if record.victories > 0 && record.faints == 0 {
+        tracker.flawless_runs += 1;
+    }
+}
+
+fn unlock_achievements(
+    mut tracker: ResMut<AchievementTracker>,
+    handle: Option<Res<AchievementPreloadHandler>>,
+    books: Res<Assets<AchievementBook>>,
+    mut unlocked_event_writer: EventWriter<AchievementUnlockedEvent>,
+) {
+    if !tracker.is_changed() {
+        return;
+    }
+    let Some(book) = handle.and_then(|handle| books.get(handle.id())) else {
+        return;
+    };
+
+    for name in tracker.unlock(book) {
+        info!("Achievement unlocked: {}", name);
+        unlocked_event_writer.send(AchievementUnlockedEvent { name });
+    }
+}
+
+fn spawn_achievement_toast(
+    mut commands: Commands,
+    mut unlocked_event_reader: EventReader<AchievementUnlockedEvent>,
+    toast_query: Query<(), With<AchievementToast>>,
+) {
+    // stack under the ones already showing
+    let mut offset = toast_query.iter().count() as f32;
+    for ev in unlocked_event_reader.read() {
+        commands
+            .spawn((
+                Name::new("Achievement Toast"),
+                AchievementToast(Timer::from_seconds(3., TimerMode::Once)),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(20. + offset * 50.),
+                        width: Val::Percent(100.),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    z_index: ZIndex::Global(30),
+                    ..default()
+                },
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(16.), Val::Px(8.)),
+                            ..default()
+                        },
+                        background_color: Color::srgba(0., 0., 0., 0.8).into(),
+                        ..default()
+                    })
+                    .with_children(|banner| {
+                        banner.spawn(TextBundle::from_section(
+                            format!("Achievement unlocked: {}", ev.name),
+                            TextStyle {
+                                font_size: 20.,
+                                color: Color::srgb(1., 0.85, 0.3),
+                                ..default()
+                            },
+                        ));
+                    });
+            });
+        offset += 1.;
+    }
+}
+
+fn update_achievement_toast(
+    mut commands: Commands,
+    mut toast_query: Query<(Entity, &mut AchievementToast)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast) in toast_query.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[test]
+fn test_achievement_tracker_events() {
+    let mut app = App::new();
+    app.add_event::<BattleEvent>()
+        .add_event::<SpawnDropSceneEvent>()
+        .add_event::<ChangeMapEvent>()
+        .init_resource::<AchievementTracker>()
+        .add_systems(Update, (count_battles, count_drops, count_travels));
+
+    let boss = app.world_mut().spawn(Boss).id();
+    let monster = app.world_mut().spawn_empty().id();
+    app.world_mut().send_event(BattleEvent {
+        is_player_victory: true,
+        monsters: vec![boss, monster],
+    });
+    // a lost battle counts for nothing
+    app.world_mut().send_event(BattleEvent {
+        is_player_victory: false,
+        monsters: vec![monster],
+    });
+    app.world_mut().send_event(SpawnDropSceneEvent {
+        eq: crate::model::sub::DropEquipment::default(),
+    });
+    app.world_mut().send_event(ChangeMapEvent {
+        location: super::preload::Location::new("Gate"),
+        spawn_point: monster,
+        run_start: false,
+    });
+    app.world_mut().send_event(ChangeMapEvent {
+        location: super::preload::Location::new("Start"),
+        spawn_point: monster,
+        run_start: true,
+    });
+    app.update();
+
+    let tracker = app.world().resource::<AchievementTracker>();
+    assert_eq!(tracker.monsters_defeated, 2);
+    assert_eq!(tracker.bosses_defeated, 1);
+    assert_eq!(tracker.drops_found, 1);
+    assert_eq!(tracker.travels, 1);
+
+    let book = AchievementBook(
+        serde_json::from_str(
+            r#"[
+                {"id": 4, "name": "Giant Slayer", "description": "", "condition": {"kind": "BossesDefeated", "count": 1}},
+                {"id": 6, "name": "Treasure Seeker", "description": "", "condition": {"kind": "DropsFound", "count": 50}}
+            ]"#,
+        )
+        .unwrap(),
+    );
+    let mut tracker = app.world_mut().resource_mut::<AchievementTracker>();
+    assert_eq!(tracker.unlock(&book), vec![String::from("Giant Slayer")]);
+    // already unlocked ones are not announced again
+    assert!(tracker.unlock(&book).is_empty());
+    assert_eq!(tracker.unlocked, vec![4]);
+}
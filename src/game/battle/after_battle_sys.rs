@@ -1,16 +1,28 @@
 use crate::{
-    game::character::component::{Bag, BaseStates},
+    game::{
+        character::component::{Bag, BaseStates},
+        meta::MetaProgress,
+        player::{
+            battle_gold, consumable_possible_drop, EquipmentBelt, ExpBoost, Ledger, Money,
+            Progress, Storage,
+        },
+    },
     model::{
+        affix::{AffixBook, AffixKind},
         armor::ArmorData,
+        consumable::ConsumableData,
         helmet::HelmetData,
         monster::MonsterData,
+        progression::Progression,
         shoes::ShoesData,
-        sub::{DropEquipment, Element, Tier},
+        sub::{DropEquipment, Element, Rarity, Tier},
         weapon::WeaponData,
         EquipmentData,
     },
+    preload::data::AffixPreloadHandler,
     state::TerminalState,
 };
+use bevy::ecs::system::SystemParam;
 use rand::prelude::SliceRandom;
 
 use super::*;
@@ -55,21 +67,52 @@ use super::*;
 //     }
 // }
 
+// Everything drop_item needs to roll a piece of equipment
+#[derive(SystemParam)]
+pub struct DropAssets<'w> {
+    weapon: Res<'w, Assets<WeaponData>>,
+    armor: Res<'w, Assets<ArmorData>>,
+    helmet: Res<'w, Assets<HelmetData>>,
+    shoes: Res<'w, Assets<ShoesData>>,
+    affix_handle: Option<Res<'w, AffixPreloadHandler>>,
+    affix_books: Res<'w, Assets<AffixBook>>,
+}
+
+impl<'w> DropAssets<'w> {
+    fn affixes(&self) -> Option<&AffixBook> {
+        self.affix_handle
+            .as_ref()
+            .and_then(|handle| self.affix_books.get(handle.id()))
+    }
+}
+
 pub fn handle_after_battle(
     mut commands: Commands,
 
-    mut player_query: Query<(&mut BaseStates, &mut Turns, &mut Bag), With<Hero>>,
-    monster_query: Query<(&mut BaseStates, &MonsterData), (With<Monster>, Without<Hero>)>,
-    mut battle_query: Query<(&mut TalkFlag, Entity, &mut TrashTalk)>,
+    mut player_query: Query<
+        (&mut BaseStates, &mut Turns, &mut Bag, Option<&mut ExpBoost>),
+        With<Hero>,
+    >,
+    mut storage_query: Query<
+        (&mut Storage, &mut Progress, &EquipmentBelt, &mut Money),
+        With<Player>,
+    >,
+    monster_query: Query<
+        (&mut BaseStates, &MonsterData, Option<&BossScript>, &Name),
+        (With<Monster>, Without<Hero>),
+    >,
+    mut battle_query: Query<(&mut TalkFlag, Entity, &mut TrashTalk, &Combat)>,
     mut hero_lv_up_text_queue: Query<&mut LvUpQueue>,
+    mut history: ResMut<BattleHistory>,
+    mut ledger: ResMut<Ledger>,
 
     mut battle_event_reader: EventReader<BattleEvent>,
     mut next_combat_stage: ResMut<NextState<TerminalState>>,
 
-    weapon_asset: Res<Assets<WeaponData>>,
-    armor_asset: Res<Assets<ArmorData>>,
-    helmet_asset: Res<Assets<HelmetData>>,
-    shoes_asset: Res<Assets<ShoesData>>,
+    drop_assets: DropAssets,
+    consumable_asset: Res<Assets<ConsumableData>>,
+    progression: Res<Progression>,
+    meta: Res<MetaProgress>,
 ) {
     for ev in battle_event_reader.read() {
         let Ok(mut lv_up_queue) = hero_lv_up_text_queue.get_single_mut() else {
@@ -77,23 +120,35 @@ pub fn handle_after_battle(
             continue;
         };
 
-        let Ok((mut player_state, mut player_turns, mut player_bag)) =
+        let Ok((mut player_state, mut player_turns, mut player_bag, exp_boost)) =
             player_query.get_single_mut()
         else {
             warn!("player not found");
             continue;
         };
-        let Ok((monster_state, monster_data)) = monster_query.get(ev.monster_entity) else {
+        let monsters = ev
+            .monsters
+            .iter()
+            .filter_map(|entity| monster_query.get(*entity).ok())
+            .collect::<Vec<_>>();
+
+        if monsters.is_empty() {
             warn!("monster not found");
             continue;
-        };
+        }
 
         // 1. reset talking flag
-        let Ok((mut talk_flag, entity, mut talk)) = battle_query.get_single_mut() else {
+        let Ok((mut talk_flag, entity, mut talk, combat)) = battle_query.get_single_mut() else {
             warn!("combat not found");
             continue;
         };
 
+        let mut log = BattleLog::new(
+            monsters.iter().map(|(.., name)| name.to_string()).collect(),
+            &combat.records,
+            ev.is_player_victory,
+        );
+
         info!("Battle Ended");
         next_combat_stage.set(TerminalState::Talking);
         talk_flag.ready();
@@ -105,42 +160,123 @@ pub fn handle_after_battle(
             player_turns.minus();
 
             // 2. fire terminal message
-            talk.push(format!("You defeated the monster\n"));
-            // 3.Handle Drop Equipment
-            if let Some(drop) = drop_item(
-                &monster_state,
-                &monster_data,
-                &weapon_asset,
-                &armor_asset,
-                &helmet_asset,
-                &shoes_asset,
-            ) {
-                player_bag.push(&drop);
-                commands.entity(entity).insert(DropPopupFlag::new(1, &drop));
-
-                talk.push(format!(
-                    "You found a {}lv {} on the ground",
-                    drop.level, drop.name
-                ));
+            if monsters.len() > 1 {
+                talk.push(format!("You defeated all {} monsters\n", monsters.len()));
+            } else {
+                talk.push("You defeated the monster\n".to_string());
             }
 
-            // level
-            lv_up_queue.0 += player_state.exp_gain(monster_state.lv());
-            talk.push(format!(
-                "Gained {:.1} exp from battle\n",
-                BaseStates::exp_drop_by_monster(monster_state.lv()),
-            ));
+            // 3.Handle Drop Equipment, every monster in the group rolls its own drop
+            let mut drops: Vec<DropEquipment> = Vec::new();
+            let mut unique_drops: Vec<DropEquipment> = Vec::new();
+            for (monster_state, monster_data, boss, _) in monsters.iter() {
+                // a boss always hands over its own loot
+                if let Some(boss) = boss {
+                    for line in boss.data.defeat.iter() {
+                        talk.push(line.clone());
+                    }
+
+                    for drop in boss.data.drops.iter() {
+                        let mut drop = drop.clone();
+                        drop.set_level(monster_state.lv());
+                        player_bag.push(&drop);
+                        talk.push(format!("You obtained {}lv {}", drop.level, drop.name));
+                        unique_drops.push(drop);
+                    }
+
+                    if let Ok((_, mut progress, ..)) = storage_query.get_single_mut() {
+                        progress.clear_boss(boss.data.id);
+                        if boss.data.is_final {
+                            progress.is_completed = true;
+                            commands.entity(entity).insert(FinalVictoryFlag);
+                        }
+                    }
+                }
+
+                if let Some(drop) = drop_item(
+                    monster_state,
+                    monster_data,
+                    &drop_assets,
+                    meta.drop_multiplier(),
+                ) {
+                    player_bag.push(&drop);
+                    let prefix = drop_assets
+                        .affixes()
+                        .map(|book| book.prefix(&drop.affixes))
+                        .unwrap_or_default();
+                    let name = if prefix.is_empty() {
+                        drop.name.clone()
+                    } else {
+                        format!("{} {}", prefix, drop.name)
+                    };
+                    talk.push(format!(
+                        "You found a {:?} {}lv {} on the ground",
+                        drop.rarity, drop.level, name
+                    ));
+                    drops.push(drop);
+                }
+
+                if let Some(consumable) =
+                    consumable_possible_drop(&consumable_asset, meta.drop_multiplier())
+                {
+                    if let Ok((mut storage, ..)) = storage_query.get_single_mut() {
+                        storage.add_consumable(consumable.id, 1);
+                        talk.push(format!("You picked up a {}", consumable.name));
+                        log.consumables.push(consumable.name.clone());
+                    }
+                }
+            }
+
+            // only the best find gets the popup, boss loot comes first
+            if let Some(drop) = unique_drops
+                .first()
+                .or(drops.iter().max_by_key(|drop| drop.level))
+            {
+                commands.entity(entity).insert(DropPopupFlag::new(1, drop));
+            }
+
+            // level, the boost is used up once for the whole group
+            let exp_gain = storage_query
+                .get_single()
+                .map_or(0, |(_, _, belt, _)| belt.affix(&AffixKind::ExpGain));
+            let multiplier = exp_boost.map_or(1., |mut boost| boost.consume()) as f64
+                * (1. + exp_gain as f64 / 100.);
+            let mut total_exp = 0.;
+            for (monster_state, ..) in monsters.iter() {
+                lv_up_queue.0 +=
+                    player_state.exp_gain(monster_state.lv(), multiplier, &progression);
+                total_exp += progression.monster_exp(monster_state.lv()) * multiplier;
+            }
+            talk.push(format!("Gained {:.1} exp from battle\n", total_exp));
+
+            // gold for every monster, the message reports what the ledger recorded
+            if let Ok((.., mut money)) = storage_query.get_single_mut() {
+                let start = ledger.0.len();
+                for (monster_state, .., name) in monsters.iter() {
+                    ledger.earn(
+                        &mut money,
+                        name.to_string(),
+                        battle_gold(monster_state.lv()),
+                    );
+                }
+                talk.push(format!("Picked up {} gold\n", ledger.earned_since(start)));
+            }
+
+            log.drops = unique_drops.into_iter().chain(drops).collect();
+            log.exp = total_exp;
 
             // final
-            talk.push(format!("Press Anywhere to Continue"));
+            talk.push("Press Anywhere to Continue".to_string());
         } else {
             // handle lost
             player_turns.minus();
             player_turns.minus();
             player_turns.minus();
-            talk.push(format!("You are fainted, 3 turns have come to pass"));
-            talk.push(format!("Press Anywhere to Continue"));
+            talk.push("You are fainted, 3 turns have come to pass".to_string());
+            talk.push("Press Anywhere to Continue".to_string());
         }
+
+        history.push(log);
     }
 }
 
@@ -148,16 +284,14 @@ pub fn drop_count_down(
     mut battle_query: Query<(&mut DropPopupFlag, &TalkFlag)>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     touches: Res<Touches>,
+    speed: Res<BattleSpeed>,
     mut drop_event_writer: EventWriter<SpawnDropSceneEvent>,
 ) {
     let Ok((mut drop_count_flag, talk_flag)) = battle_query.get_single_mut() else {
         return;
     };
 
-    if (keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
-        || touches.any_just_pressed())
-        && talk_flag.is_terminal_ready
-    {
+    if speed.is_pressed(&keyboard_input, &touches) && talk_flag.is_terminal_ready {
         drop_count_flag.count_down();
     }
 
@@ -172,42 +306,39 @@ pub fn drop_count_down(
 fn drop_item(
     monster_state: &BaseStates,
     monster_data: &MonsterData,
-
-    weapon_asset: &Res<Assets<WeaponData>>,
-    armor_asset: &Res<Assets<ArmorData>>,
-    helmet_asset: &Res<Assets<HelmetData>>,
-    shoes_asset: &Res<Assets<ShoesData>>,
+    assets: &DropAssets,
+    drop_multiplier: f32,
 ) -> Option<DropEquipment> {
     // Drop Chance
-    if random::<f32>() < 0.4 {
+    if random::<f32>() < 0.4 * drop_multiplier {
         let monster_tier = monster_data.tier.clone();
         let element = monster_data.element.clone();
         let mut list_possible: Vec<DropEquipment> = Vec::new();
         info!("Dropping!!!!!! Monster Tier: {:?}", monster_tier);
 
         weapon_possible_drop(
-            &weapon_asset,
+            &assets.weapon,
             &mut list_possible,
             monster_state.lv(),
             &element,
         );
 
         push_list_of_possible_drop::<ArmorData>(
-            &armor_asset,
+            &assets.armor,
             &mut list_possible,
             &monster_tier,
             monster_state.lv(),
         );
 
         push_list_of_possible_drop::<HelmetData>(
-            &helmet_asset,
+            &assets.helmet,
             &mut list_possible,
             &monster_tier,
             monster_state.lv(),
         );
 
         push_list_of_possible_drop::<ShoesData>(
-            &shoes_asset,
+            &assets.shoes,
             &mut list_possible,
             &monster_tier,
             monster_state.lv(),
@@ -215,10 +346,14 @@ fn drop_item(
 
         info!("Possible list of Equipment: {:?}", list_possible);
 
-        let drop = list_possible
-            .choose(&mut rand::thread_rng())
-            .map(|x| x.clone());
-        drop
+        let mut rng = rand::thread_rng();
+        let mut drop = list_possible.choose(&mut rng).cloned()?;
+
+        drop.rarity = Rarity::from_roll(random());
+        if let Some(book) = assets.affixes() {
+            drop.affixes = book.roll(&drop.item_type, &drop.rarity, &mut rng);
+        }
+        Some(drop)
     } else {
         None
     }
@@ -235,14 +370,7 @@ fn weapon_possible_drop(
         .find(|(_, weapon)| weapon.element == *monster_element);
 
     if let Some((_, data)) = weapon {
-        list_possible.push(DropEquipment {
-            id: data.id(),
-            level: monster_lv,
-            item_type: data.item_type(),
-            image: None,
-            name: data.name(),
-            description: data.description(),
-        })
+        list_possible.push(data.to_drop_equipment(monster_lv))
     }
 }
 
@@ -265,14 +393,7 @@ fn push_list_of_possible_drop<T>(
         if monster_tier.contain(&data.tier()) {
             info!("Contain {:?} {}", data.item_type(), data.id());
 
-            list_possible.push(DropEquipment {
-                id: data.id(),
-                level: monster_lv,
-                item_type: data.item_type(),
-                image: None,
-                name: data.name(),
-                description: data.description(),
-            })
+            list_possible.push(data.to_drop_equipment(monster_lv))
         }
     }
 }
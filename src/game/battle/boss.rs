@@ -0,0 +1,78 @@
+use crate::model::boss::{BossData, BossPhase};
+
+use super::*;
+
+// Attached to the boss entity from the map, keeps track of how far the fight went
+#[derive(Component, Clone, Debug)]
+pub struct BossScript {
+    pub data: BossData,
+    pub phase: Option<usize>,
+}
+
+impl BossScript {
+    pub fn new(data: &BossData) -> Self {
+        let mut data = data.clone();
+        // highest threshold is the one reached first
+        data.phases
+            .sort_by(|a, b| b.hp_threshold.total_cmp(&a.hp_threshold));
+
+        Self { data, phase: None }
+    }
+
+    pub fn current(&self) -> Option<&BossPhase> {
+        self.phase.and_then(|index| self.data.phases.get(index))
+    }
+
+    // Step into the next phase once the hp drops past its threshold, one phase at a time
+    pub fn advance(&mut self, hp_ratio: f32) -> Option<BossPhase> {
+        let next = self.phase.map_or(0, |index| index + 1);
+        let phase = self.data.phases.get(next)?;
+
+        if hp_ratio <= phase.hp_threshold {
+            self.phase = Some(next);
+            Some(phase.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.phase = None;
+    }
+
+    pub fn attack_multiplier(&self) -> f32 {
+        self.current().map_or(1., |phase| phase.attack_multiplier)
+    }
+
+    pub fn damage_taken(&self) -> f32 {
+        self.current().map_or(1., |phase| phase.damage_taken)
+    }
+}
+
+#[test]
+fn test_boss_phase_advance() {
+    let phase = |hp_threshold: f32| BossPhase {
+        hp_threshold,
+        attack_multiplier: hp_threshold + 1.,
+        damage_taken: 1.,
+        heal: 0.,
+        dialogue: Vec::new(),
+    };
+
+    let mut script = BossScript::new(&BossData {
+        phases: vec![phase(0.25), phase(0.6)],
+        ..Default::default()
+    });
+
+    assert!(script.advance(0.9).is_none());
+    assert_eq!(script.attack_multiplier(), 1.);
+
+    // a big hit only moves one phase forward
+    assert_eq!(script.advance(0.1).unwrap().hp_threshold, 0.6);
+    assert_eq!(script.advance(0.1).unwrap().hp_threshold, 0.25);
+    assert!(script.advance(0.).is_none());
+    assert_eq!(script.attack_multiplier(), 1.25);
+
+    script.reset();
+    assert!(script.current().is_none());
+}
@@ -1,4 +1,7 @@
-use crate::game::character::component::BaseStates;
+use crate::{
+    game::{battle_scene::BattleMonster, character::component::BaseStates},
+    model::{boss::BossPhase, monster::MonsterData, progression::Progression, sub::Element},
+};
 
 use super::*;
 
@@ -20,80 +23,132 @@ pub fn send_next_turn(
     mut battle_query: Query<(&mut Combat, &mut TurnFlag, Entity)>,
     mut next_turn_event_writer: EventWriter<NextTurnEvent>,
     mut battle_event_writer: EventWriter<BattleEvent>,
+    mut boss_query: Query<(&mut BossScript, &BaseStates), With<Monster>>,
+    mut boss_phase_event_writer: EventWriter<BossPhaseEvent>,
+    mut write_terminal_event: EventWriter<WriteTerminalEvent>,
 
+    speed: Res<BattleSpeed>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     touches: Res<Touches>,
+    progression: Res<Progression>,
+    aim_query: Query<&Interaction, With<BattleMonster>>,
 ) {
+    // a tap on a monster only picks the target, select_target handles it
+    let is_aiming = aim_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+
     for (mut battle, turn_flag, entity) in battle_query.iter_mut() {
-        if turn_flag.is_all_ready() {
-            if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
-                || touches.any_just_pressed()
-            {
-                info!("Applying Last Turn Damage");
-                battle.push_update(turn_flag.clone().record.unwrap());
-
-                match (
-                    battle.monster_hp_remain <= 0.0,
-                    battle.player_hp_remain <= 0.0,
-                ) {
-                    (true, true) | (false, true) => {
-                        // Lost
-                        info!("Monster Won");
-                        battle_event_writer.send(BattleEvent {
-                            is_player_victory: false,
-                            monster_entity: battle.monster_entity,
-                        });
-                        // write_terminal_event
-                        //     .send(WriteTerminalEvent::new(format!("You are fainted\n")));
-                        // commands.spawn(BattleEndingTimer::lost());
-                    }
-                    (true, false) => {
-                        info!("Player Won");
-                        battle_event_writer.send(BattleEvent {
-                            is_player_victory: true,
-                            monster_entity: battle.monster_entity,
-                        });
-                        // write_terminal_event.send(WriteTerminalEvent::new(format!(
-                        //     "You defeated the monster\n"
-                        // )));
-                        // commands.spawn(BattleEndingTimer::victory());
-                    }
-                    (false, false) => {
-                        info!("Sending Next Turn Signal");
-                        next_turn_event_writer.send(NextTurnEvent {
-                            is_player_turn: !battle
-                                .records
-                                .last()
-                                .is_some_and(|record| record.is_player_turn),
-                            battle_entity: entity,
-                        });
-                    }
+        if turn_flag.is_all_ready() && speed.is_pressed(&keyboard_input, &touches) && !is_aiming {
+            info!("Applying Last Turn Damage");
+            battle.push_update(turn_flag.clone().record.unwrap());
+
+            for (phase, event) in advance_boss_phases(&mut battle, &mut boss_query, &progression) {
+                for line in phase.dialogue {
+                    write_terminal_event.send(WriteTerminalEvent::new(line));
+                }
+                boss_phase_event_writer.send(event);
+            }
+
+            match (battle.is_all_defeated(), battle.player_hp_remain <= 0.0) {
+                (true, true) | (false, true) => {
+                    // Lost
+                    info!("Monster Won");
+                    battle_event_writer.send(BattleEvent {
+                        is_player_victory: false,
+                        monsters: battle.monster_entities(),
+                    });
+                    // write_terminal_event
+                    //     .send(WriteTerminalEvent::new(format!("You are fainted\n")));
+                    // commands.spawn(BattleEndingTimer::lost());
+                }
+                (true, false) => {
+                    info!("Player Won");
+                    battle_event_writer.send(BattleEvent {
+                        is_player_victory: true,
+                        monsters: battle.monster_entities(),
+                    });
+                    // write_terminal_event.send(WriteTerminalEvent::new(format!(
+                    //     "You defeated the monster\n"
+                    // )));
+                    // commands.spawn(BattleEndingTimer::victory());
+                }
+                (false, false) => {
+                    info!("Sending Next Turn Signal");
+                    next_turn_event_writer.send(NextTurnEvent {
+                        is_player_turn: battle.next_monster_turn().is_none(),
+                        battle_entity: entity,
+                    });
                 }
             }
         }
     }
 }
 
+// bosses still standing may shift into their next phase
+fn advance_boss_phases(
+    battle: &mut Combat,
+    boss_query: &mut Query<(&mut BossScript, &BaseStates), With<Monster>>,
+    progression: &Progression,
+) -> Vec<(BossPhase, BossPhaseEvent)> {
+    let mut phases = Vec::new();
+
+    for foe in battle.foes.iter_mut().filter(|foe| foe.is_alive()) {
+        let Ok((mut script, boss_base)) = boss_query.get_mut(foe.entity) else {
+            continue;
+        };
+
+        let max_hp = boss_base.hp(progression);
+        let Some(phase) = script.advance(foe.hp_remain / max_hp) else {
+            continue;
+        };
+
+        let heal = (max_hp - foe.hp_remain).min(phase.heal * max_hp);
+        foe.hp_remain += heal;
+
+        phases.push((
+            phase,
+            BossPhaseEvent {
+                monster: foe.entity,
+                heal,
+            },
+        ));
+    }
+
+    phases
+}
+
+type MonsterQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static BaseStates, &'static Name, &'static MonsterData),
+    (With<Monster>, Without<Hero>),
+>;
+
+// a runaway fight is called a loss rather than looping forever
+const MAX_SKIPPED_TURNS: usize = 1000;
+
 #[allow(clippy::complexity)]
 pub fn battle_loop(
-    mut battle_query: Query<(&Combat, &mut TurnFlag)>,
+    mut battle_query: Query<(&mut Combat, &mut TurnFlag)>,
     equipment_query: Query<&EquipmentBelt, With<Player>>,
-    mut hero_query: Query<(&BaseStates, &HeroClass, Entity, &Name), With<Hero>>,
-    mut monster_query: Query<
-        (&BaseStates, &Name, &MonsterType, Entity),
-        (With<Monster>, Without<Hero>),
-    >,
+    hero_query: Query<(&BaseStates, &HeroClass, Entity, &Name), With<Hero>>,
+    monster_query: MonsterQuery,
+    mut boss_query: Query<(&mut BossScript, &BaseStates), With<Monster>>,
+    speed: Res<BattleSpeed>,
     mut attack_event_writer: EventWriter<AttackEvent>,
+    mut battle_event_writer: EventWriter<BattleEvent>,
+    mut boss_phase_event_writer: EventWriter<BossPhaseEvent>,
     mut next_turn_event_reader: EventReader<NextTurnEvent>,
+    progression: Res<Progression>,
 ) {
     for ev in next_turn_event_reader.read() {
-        let Ok((battle, mut turn_flag)) = battle_query.get_mut(ev.battle_entity) else {
+        let Ok((mut battle, mut turn_flag)) = battle_query.get_mut(ev.battle_entity) else {
             warn!("No battle found");
             continue;
         };
 
-        let Ok((player_base, hero_class, player_entity, player_name)) = hero_query.get_single_mut()
-        else {
+        let Ok(hero) = hero_query.get_single() else {
             warn!("No hero found");
             continue;
         };
@@ -103,69 +158,201 @@ pub fn battle_loop(
             continue;
         };
 
-        let Ok((monster_base, monster_name, _monster_type, monster_entity)) =
-            monster_query.get_mut(battle.monster_entity)
-        else {
-            warn!(
-                "No monster entity found for battle: {:?}",
-                battle.monster_entity,
-            );
-            continue;
-        };
+        if !speed.is_skip {
+            let Some(record) = resolve_turn(
+                ev.is_player_turn,
+                &mut battle,
+                hero,
+                player_belt,
+                &monster_query,
+                &boss_query,
+            ) else {
+                continue;
+            };
+
+            turn_flag.record = Some(record.clone());
 
-        if ev.is_player_turn {
-            info!("Player Turn");
-        } else {
-            info!("Monster Turn");
+            info!("{}", record.text);
+            attack_event_writer.send(AttackEvent { record });
+            continue;
         }
 
-        // battle until either one die
-        if battle.player_hp_remain > 0. && battle.monster_hp_remain > 0. {
-            let damage_out = if ev.is_player_turn {
-                // Player attack
-                let mut damage_out = DamageBuilder::build(player_base, monster_base);
-                damage_out
-                    .crit_hit(Some(hero_class))
-                    .attacker_class_scaling()
-                    .defense()
-                    .weapon(&player_belt)
-                    .innate_damage()
-                    .hero_additional_damage();
-
-                // battle.monster_hp_remain -= damage_out.get_damage_f32();
-
-                damage_out
-            } else {
-                // Monster attack
-                let mut damage_out = DamageBuilder::build(monster_base, player_base);
-                damage_out
-                    .crit_hit(None)
-                    .defense()
-                    .armor(&player_belt)
-                    .innate_damage();
-
-                // battle.player_hp_remain -= damage_out.get_damage_f32();
-
-                damage_out
+        // skip to result, the whole fight is settled in this frame
+        let mut is_player_turn = ev.is_player_turn;
+        for _ in 0..MAX_SKIPPED_TURNS {
+            let Some(record) = resolve_turn(
+                is_player_turn,
+                &mut battle,
+                hero,
+                player_belt,
+                &monster_query,
+                &boss_query,
+            ) else {
+                break;
             };
 
-            let record = CombatRecord {
-                is_player_turn: ev.is_player_turn,
-                player: player_entity,
-                monster: monster_entity,
-                player_name: player_name.clone(),
-                monster_name: monster_name.clone(),
-                damage_out: damage_out.get_damage(),
-            };
+            battle.push_update(record.clone());
+            attack_event_writer.send(AttackEvent { record });
 
-            turn_flag.record = Some(record.clone());
+            for (_, event) in advance_boss_phases(&mut battle, &mut boss_query, &progression) {
+                boss_phase_event_writer.send(event);
+            }
+
+            if battle.is_all_defeated() || battle.player_hp_remain <= 0. {
+                break;
+            }
+            is_player_turn = battle.next_monster_turn().is_none();
+        }
+
+        turn_flag.record = None;
+        battle_event_writer.send(BattleEvent {
+            is_player_victory: battle.is_all_defeated() && battle.player_hp_remain > 0.,
+            monsters: battle.monster_entities(),
+        });
+    }
+}
+
+fn resolve_turn(
+    is_player_turn: bool,
+    battle: &mut Combat,
+    (player_base, hero_class, player_entity, player_name): (&BaseStates, &HeroClass, Entity, &Name),
+    player_belt: &EquipmentBelt,
+    monster_query: &MonsterQuery,
+    boss_query: &Query<(&mut BossScript, &BaseStates), With<Monster>>,
+) -> Option<CombatRecord> {
+    // the player hits the current target, otherwise it is the next foe in line
+    let Some(acting_monster) = (if is_player_turn {
+        battle.target_entity()
+    } else {
+        battle.next_monster_turn()
+    }) else {
+        warn!("No monster left to fight in battle");
+        return None;
+    };
+
+    let Ok((monster_base, monster_name, monster_data)) = monster_query.get(acting_monster) else {
+        warn!("No monster entity found for battle: {:?}", acting_monster);
+        return None;
+    };
+    let monster_entity = acting_monster;
+    let boss = boss_query.get(monster_entity).ok().map(|(boss, _)| boss);
+
+    if is_player_turn {
+        info!("Player Turn");
+    } else {
+        info!("Monster Turn");
+    }
+
+    let foe = battle.foe(monster_entity).cloned()?;
+
+    // battle until either one die
+    if battle.player_hp_remain <= 0. || !foe.is_alive() {
+        return None;
+    }
+
+    // 1. tick the effects on whoever is acting
+    let tick = if is_player_turn {
+        battle.player_effects.tick()
+    } else {
+        battle
+            .foe_mut(monster_entity)
+            .map(|foe| foe.effects.tick())
+            .unwrap_or_default()
+    };
+
+    // stunned, or the dots finished the job before acting
+    let actor_hp = if is_player_turn {
+        battle.player_hp_remain
+    } else {
+        foe.hp_remain
+    };
+    let is_skipped = tick.is_stunned || tick.damage >= actor_hp;
+
+    let mut damage_out = if is_skipped {
+        DamageOut::NormalHit(0.)
+    } else if is_player_turn {
+        // Player attack
+        let mut damage_out = DamageBuilder::build(player_base, monster_base);
+        damage_out
+            .crit_bonus(player_belt.crit_bonus())
+            .crit_hit(Some(hero_class))
+            .attacker_class_scaling()
+            .defense()
+            .weapon(player_belt)
+            .innate_damage()
+            .hero_additional_damage();
 
-            attack_event_writer.send(AttackEvent {
-                record: record.clone(),
-            });
+        let mut damage_out = damage_out.get_damage();
+        damage_out.multiply(player_belt.elemental_multiplier());
+        if let Some(boss) = boss {
+            damage_out.multiply(boss.damage_taken());
+        }
+        damage_out
+    } else {
+        // Monster attack
+        let mut damage_out = DamageBuilder::build(monster_base, player_base);
+        damage_out
+            .crit_resist(player_belt.crit_resist())
+            .crit_hit(None)
+            .defense()
+            .armor(player_belt)
+            .innate_damage();
 
-            info!("{}", record.to_string());
+        let mut damage_out = damage_out.get_damage();
+        if let Some(boss) = boss {
+            damage_out.multiply(boss.attack_multiplier());
         }
+        damage_out
+    };
+
+    // 2. shield first, then on hit effects
+    let inflicted = if is_skipped {
+        None
+    } else if is_player_turn {
+        let foe = battle.foe_mut(monster_entity)?;
+        damage_out.set(foe.effects.absorb(damage_out.get()));
+        let effect = on_hit_effect(&player_belt.weapon_element(), player_belt.attack());
+        effect.map(|effect| {
+            let kind = effect.kind;
+            foe.effects.apply(effect);
+            kind
+        })
+    } else {
+        damage_out.set(battle.player_effects.absorb(damage_out.get()));
+        let effect = on_hit_effect(&monster_data.element, monster_base.lv_f32() / 5.);
+        effect.map(|effect| {
+            let kind = effect.kind;
+            battle.player_effects.apply(effect);
+            kind
+        })
+    };
+
+    let mut record = CombatRecord {
+        is_player_turn,
+        player: player_entity,
+        monster: monster_entity,
+        player_name: player_name.clone(),
+        monster_name: monster_name.clone(),
+        damage_out,
+        status_damage: tick.damage,
+        is_skipped,
+        expired: tick.expired,
+        inflicted,
+        text: String::new(),
+    };
+    record.text = record.render();
+    Some(record)
+}
+
+// Element of the attack decides which effect may stick on the defender
+fn on_hit_effect(element: &Element, power: f32) -> Option<StatusEffect> {
+    let seed = random::<f32>();
+    match element {
+        Element::Fire if seed < 0.3 => Some(StatusEffect::burn(1. + power * 0.5)),
+        Element::Earth if seed < 0.1 => Some(StatusEffect::stun()),
+        Element::Water if seed < 0.25 => Some(StatusEffect::poison(0.5 + power * 0.25)),
+        Element::Neutral if seed < 0.15 => Some(StatusEffect::bleed(0.5 + power * 0.3)),
+        _ => None,
     }
 }
 
@@ -185,8 +372,13 @@ pub fn battle_loop(
 pub fn send_attack_event_terminal(
     mut attack_event: EventReader<AttackEvent>,
     mut write_terminal_event: EventWriter<WriteTerminalEvent>,
+    speed: Res<BattleSpeed>,
 ) {
     for ev in attack_event.read() {
-        write_terminal_event.send(WriteTerminalEvent::new(ev.record.to_string()));
+        // a skipped fight only shows the summary
+        if speed.is_skip {
+            continue;
+        }
+        write_terminal_event.send(WriteTerminalEvent::new(ev.record.text.clone()));
     }
 }
@@ -1,33 +1,135 @@
 use std::collections::VecDeque;
 
+use super::status_effect::{StatusEffects, StatusKind};
+
 use crate::model::sub::DropEquipment;
 
 use super::*;
 
+// One monster of the encountered group
+#[derive(Reflect, Clone, Debug)]
+pub struct Foe {
+    pub entity: Entity,
+    pub hp_remain: f32,
+    pub effects: StatusEffects,
+}
+
+impl Foe {
+    pub fn new(entity: Entity, hp: f32) -> Self {
+        Self {
+            entity,
+            hp_remain: hp,
+            effects: StatusEffects::default(),
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.hp_remain > 0.
+    }
+}
+
 #[derive(Component, Reflect)]
 pub struct Combat {
-    pub monster_entity: Entity,
+    pub foes: Vec<Foe>,
+    // index of the foe the player is aiming at
+    pub target: usize,
     pub records: Vec<CombatRecord>,
-    pub monster_hp_remain: f32,
     pub player_hp_remain: f32,
+    pub player_effects: StatusEffects,
 }
 
 impl Combat {
     pub fn push_update(&mut self, record: CombatRecord) {
+        // status damage hurts whoever is taking the turn
         if record.is_player_turn {
-            self.monster_hp_remain -= record.damage_out.get();
+            self.player_hp_remain -= record.status_damage;
+            if let Some(foe) = self.foe_mut(record.monster) {
+                foe.hp_remain -= record.damage_out.get();
+            }
         } else {
             self.player_hp_remain -= record.damage_out.get();
+            if let Some(foe) = self.foe_mut(record.monster) {
+                foe.hp_remain -= record.status_damage;
+            }
+        }
+
+        // keep the aim on someone still standing
+        if !self.foes.get(self.target).is_some_and(Foe::is_alive) {
+            if let Some(index) = self.foes.iter().position(Foe::is_alive) {
+                self.target = index;
+            }
         }
 
         self.records.push(record);
     }
+
+    pub fn foe(&self, entity: Entity) -> Option<&Foe> {
+        self.foes.iter().find(|foe| foe.entity == entity)
+    }
+
+    pub fn foe_mut(&mut self, entity: Entity) -> Option<&mut Foe> {
+        self.foes.iter_mut().find(|foe| foe.entity == entity)
+    }
+
+    pub fn target_entity(&self) -> Option<Entity> {
+        self.foes
+            .get(self.target)
+            .filter(|foe| foe.is_alive())
+            .or(self.foes.iter().find(|foe| foe.is_alive()))
+            .map(|foe| foe.entity)
+    }
+
+    pub fn set_target(&mut self, entity: Entity) -> bool {
+        match self
+            .foes
+            .iter()
+            .position(|foe| foe.entity == entity && foe.is_alive())
+        {
+            Some(index) => {
+                self.target = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_all_defeated(&self) -> bool {
+        self.foes.iter().all(|foe| !foe.is_alive())
+    }
+
+    pub fn monster_entities(&self) -> Vec<Entity> {
+        self.foes.iter().map(|foe| foe.entity).collect()
+    }
+
+    // Player goes first, then every foe still standing in order.
+    // None means it is the player's turn
+    pub fn next_monster_turn(&self) -> Option<Entity> {
+        let start = match self.records.last() {
+            None => return None,
+            Some(record) if record.is_player_turn => 0,
+            Some(record) => self
+                .foes
+                .iter()
+                .position(|foe| foe.entity == record.monster)
+                .map_or(self.foes.len(), |index| index + 1),
+        };
+
+        self.foes
+            .iter()
+            .skip(start)
+            .find(|foe| foe.is_alive())
+            .map(|foe| foe.entity)
+    }
 }
 
 #[derive(Component)]
 
 pub struct ExitFlag;
 
+// The final boss is down, leaving the battle rolls the credits
+#[derive(Component)]
+pub struct FinalVictoryFlag;
+
 // When all flags are ready, it will be update the CombatRecord
 #[derive(Component, Reflect, Clone)]
 pub struct TurnFlag {
@@ -121,6 +223,19 @@ impl TrashTalk {
         }
     }
 
+    pub fn encounter(count: usize) -> Self {
+        if count <= 1 {
+            return Self::demo();
+        }
+
+        Self {
+            talk: VecDeque::from([
+                format!("A group of {} monsters jumped out!", count),
+                "Battle Started, Tap a Monster to Aim, Touch to Fight!".to_string(),
+            ]),
+        }
+    }
+
     pub fn push(&mut self, s: String) {
         self.talk.push_back(s);
     }
@@ -147,10 +262,6 @@ impl DropPopupFlag {
             self.counter = -1;
         }
     }
-
-    pub fn hit(&self) -> bool {
-        self.counter == 0
-    }
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
@@ -161,17 +272,52 @@ pub struct CombatRecord {
     pub player_name: Name,
     pub monster_name: Name,
     pub damage_out: DamageOut,
+    pub status_damage: f32,
+    pub is_skipped: bool,
+    pub expired: Vec<StatusKind>,
+    pub inflicted: Option<StatusKind>,
+    // rendered once so the terminal and the battle log show the same phrase
+    pub text: String,
 }
 
 impl CombatRecord {
-    pub fn to_string(&self) -> String {
-        use rand::seq::SliceRandom;
+    pub fn render(&self) -> String {
         let (attacker, defender) = if self.is_player_turn {
             (&self.player_name, &self.monster_name)
         } else {
             (&self.monster_name, &self.player_name)
         };
 
+        let mut lines: Vec<String> = Vec::new();
+
+        if self.status_damage > 0. {
+            lines.push(format!(
+                "{0} suffered {1:.1} damage from status effects",
+                attacker.as_str(),
+                self.status_damage
+            ));
+        }
+
+        for kind in self.expired.iter() {
+            lines.push(format!("{0} is no longer {1}", attacker.as_str(), kind));
+        }
+
+        if self.is_skipped {
+            lines.push(format!("{0} is unable to move", attacker.as_str()));
+        } else {
+            lines.push(self.attack_phrase(attacker, defender));
+        }
+
+        if let Some(kind) = self.inflicted {
+            lines.push(format!("{0} is {1}", defender.as_str(), kind));
+        }
+
+        lines.join("\n")
+    }
+
+    fn attack_phrase(&self, attacker: &Name, defender: &Name) -> String {
+        use rand::seq::SliceRandom;
+
         match self.damage_out {
             DamageOut::NormalHit(_) => {
                 let attack_phrase: [String; 5] = [
@@ -239,3 +385,55 @@ impl CombatRecord {
         }
     }
 }
+
+#[test]
+fn test_group_turn_order() {
+    let player = Entity::from_raw(0);
+    let (a, b, c) = (
+        Entity::from_raw(1),
+        Entity::from_raw(2),
+        Entity::from_raw(3),
+    );
+
+    let record = |is_player_turn: bool, monster: Entity, damage: f32| CombatRecord {
+        is_player_turn,
+        player,
+        monster,
+        player_name: Name::new("Hero"),
+        monster_name: Name::new("Monster"),
+        damage_out: DamageOut::NormalHit(damage),
+        status_damage: 0.,
+        is_skipped: false,
+        expired: Vec::new(),
+        inflicted: None,
+        text: String::new(),
+    };
+
+    let mut battle = Combat {
+        foes: vec![Foe::new(a, 10.), Foe::new(b, 10.), Foe::new(c, 10.)],
+        target: 0,
+        records: Vec::new(),
+        player_hp_remain: 100.,
+        player_effects: StatusEffects::default(),
+    };
+
+    assert_eq!(battle.next_monster_turn(), None, "player should open");
+
+    // the hero takes down the first monster, aim moves on to the next one
+    battle.push_update(record(true, a, 10.));
+    assert_eq!(battle.target_entity(), Some(b));
+    assert_eq!(battle.next_monster_turn(), Some(b));
+
+    battle.push_update(record(false, b, 1.));
+    assert_eq!(battle.next_monster_turn(), Some(c));
+
+    battle.push_update(record(false, c, 1.));
+    assert_eq!(battle.next_monster_turn(), None, "back to the player");
+
+    assert!(!battle.set_target(a), "cannot aim at a fallen monster");
+    assert!(battle.set_target(c));
+    battle.push_update(record(true, c, 10.));
+    battle.push_update(record(false, b, 0.));
+    battle.push_update(record(true, b, 10.));
+    assert!(battle.is_all_defeated());
+}
@@ -9,6 +9,10 @@ pub enum DamageOut {
 }
 
 impl DamageOut {
+    pub fn is_critical(&self) -> bool {
+        matches!(self, DamageOut::CriticalHit(_))
+    }
+
     pub fn get(&self) -> f32 {
         match self {
             DamageOut::NormalHit(damage) => *damage,
@@ -45,6 +49,10 @@ pub struct DamageBuilder<'a> {
     attacker: &'a BaseStates,
     defender: &'a BaseStates,
     damage: DamageOut,
+    // extra crit rate in percent points, from gems
+    crit_bonus: f32,
+    // crit rate taken off in percent points, from the defender's helmet
+    crit_resist: f32,
 }
 
 impl<'a> DamageBuilder<'a> {
@@ -54,13 +62,25 @@ impl<'a> DamageBuilder<'a> {
             attacker,
             defender,
             damage: DamageOut::NormalHit(1. + 2_f32.powf(attacker.lv_f32() / 20.)),
+            crit_bonus: 0.,
+            crit_resist: 0.,
         }
     }
 
+    pub fn crit_bonus(&mut self, bonus: f32) -> &mut Self {
+        self.crit_bonus = bonus;
+        self
+    }
+
+    pub fn crit_resist(&mut self, resist: f32) -> &mut Self {
+        self.crit_resist = resist;
+        self
+    }
+
     // 2. Cal Critical
     pub fn crit_hit(&mut self, class: Option<&HeroClass>) -> &mut Self {
         let seed = random::<f32>() * 100.;
-        if seed <= self.attacker.crit_rate(class) {
+        if seed <= self.attacker.crit_rate(class) + self.crit_bonus - self.crit_resist {
             self.damage =
                 DamageOut::CriticalHit(self.damage.get() * self.attacker.crit_multiplier());
         }
@@ -112,12 +132,6 @@ impl<'a> DamageBuilder<'a> {
         self
     }
 
-    pub fn noise(&mut self) -> &mut Self {
-        self.damage.multiply(1_f32 + random::<f32>());
-
-        self
-    }
-
     pub fn weapon(&mut self, weapon: &EquipmentBelt) -> &mut Self {
         self.damage.add(weapon.attack());
         self
@@ -136,28 +150,24 @@ impl<'a> DamageBuilder<'a> {
 
     // Hero always has the ability to 5 level over
     pub fn hero_additional_damage(&mut self) -> &mut Self {
-        self.damage.add(1.18920711500272);
+        self.damage.add(2_f32.powf(5. / 20.));
         self
     }
 
     pub fn get_damage(&self) -> DamageOut {
         self.damage
     }
-
-    pub fn get_damage_f32(&mut self) -> f32 {
-        self.damage.get()
-    }
 }
 
 #[test]
 fn test_m1_damage() {
-    let attacker: BaseStates = BaseStates::new_hero();
+    let attacker: BaseStates = BaseStates::new_hero(HeroClass::Warrior);
     let defender: BaseStates = BaseStates::new_monster(1);
-    let mut level1 = DamageBuilder::build(&attacker, &defender);
+    let level1 = DamageBuilder::build(&attacker, &defender);
 
     assert_eq!(
-        level1.get_damage_f32(),
-        1.07177346253629 + 1.,
+        level1.get_damage().get(),
+        1. + 2_f32.powf(1. / 20.),
         "m1 not accurate"
     );
 
@@ -184,6 +194,32 @@ fn test_damage_out() {
     );
 }
 
+#[test]
+fn test_board_allocation_changes_damage() {
+    use crate::game::overlay::board::components::UpgradeBuffer;
+
+    let monster = BaseStates::new_monster(5);
+    let before = BaseStates::new_hero(HeroClass::Warrior);
+
+    // same flow as the status board: points go to the buffer, confirm applies it
+    let mut after = before.clone();
+    after.plus_state_point(6);
+    let mut buffer = UpgradeBuffer::default();
+    buffer.strength += after.half_state_point().unwrap();
+    buffer.defense += after.all_state_point().unwrap();
+    after.allocate(buffer.allocation());
+
+    let damage_out = |attacker: &BaseStates, defender: &BaseStates| {
+        DamageBuilder::build(attacker, defender)
+            .defense()
+            .get_damage()
+            .get()
+    };
+
+    assert!(damage_out(&after, &monster) > damage_out(&before, &monster));
+    assert!(damage_out(&monster, &after) < damage_out(&monster, &before));
+}
+
 // Todo Writing unit test
 // #[test]
 // fn test_paladin_damage() {
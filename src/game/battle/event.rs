@@ -5,7 +5,7 @@ use super::*;
 #[derive(Event)]
 pub struct BattleEvent {
     pub is_player_victory: bool,
-    pub monster_entity: Entity,
+    pub monsters: Vec<Entity>,
     // pub battle_entity: Entity,
 }
 
@@ -14,6 +14,13 @@ pub struct AttackEvent {
     pub record: CombatRecord,
 }
 
+// Boss stepped into a new phase, heal is in raw hp
+#[derive(Event)]
+pub struct BossPhaseEvent {
+    pub monster: Entity,
+    pub heal: f32,
+}
+
 #[derive(Event)]
 pub struct NextTurnEvent {
     pub is_player_turn: bool,
@@ -26,7 +33,7 @@ pub struct StartBattleEvent {}
 #[derive(Event)]
 pub struct SpawnBattleSceneEvent {
     pub battle: Entity,
-    pub monster: Entity,
+    pub monsters: Vec<Entity>,
 }
 
 #[derive(Event)]
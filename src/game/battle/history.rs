@@ -0,0 +1,86 @@
+use crate::{
+    game::saving::{write_profile, Profile},
+    model::history::HistorySave,
+};
+
+pub use crate::model::history::{BattleLog, TurnLog};
+
+use super::*;
+
+// Battle log of the profile, mirrored into it whenever a battle is added
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub struct BattleHistory(pub HistorySave);
+
+impl BattleLog {
+    pub fn new(monsters: Vec<String>, records: &[CombatRecord], is_victory: bool) -> Self {
+        Self {
+            monsters,
+            is_victory,
+            turns: records.iter().map(TurnLog::from).collect(),
+            ..default()
+        }
+    }
+}
+
+impl From<&CombatRecord> for TurnLog {
+    fn from(record: &CombatRecord) -> Self {
+        let (attacker, defender) = if record.is_player_turn {
+            (&record.player_name, &record.monster_name)
+        } else {
+            (&record.monster_name, &record.player_name)
+        };
+
+        Self {
+            is_player_turn: record.is_player_turn,
+            attacker: attacker.to_string(),
+            defender: defender.to_string(),
+            damage: if record.is_skipped {
+                0.
+            } else {
+                record.damage_out.get()
+            },
+            is_critical: record.damage_out.is_critical(),
+            status_damage: record.status_damage,
+            is_skipped: record.is_skipped,
+            expired: record.expired.iter().map(ToString::to_string).collect(),
+            inflicted: record.inflicted.map(|kind| kind.to_string()),
+            text: record.text.clone(),
+        }
+    }
+}
+
+pub fn load_battle_history(profile: Option<Res<Profile>>, mut history: ResMut<BattleHistory>) {
+    let Some(profile) = profile else {
+        return;
+    };
+    if profile.is_added() {
+        history.0 = profile.history.clone();
+    }
+}
+
+pub fn save_battle_history(history: Res<BattleHistory>, profile: Option<ResMut<Profile>>) {
+    if !history.is_changed() || history.is_added() {
+        return;
+    }
+    let Some(mut profile) = profile else {
+        return;
+    };
+
+    profile.history = history.0.clone();
+    write_profile(&profile);
+}
+
+#[test]
+fn test_battle_history_cap() {
+    let mut history = BattleHistory::default();
+
+    let cap = crate::model::history::MAX_BATTLE_LOGS;
+
+    for _ in 0..cap + 5 {
+        history.push(BattleLog::default());
+    }
+
+    assert_eq!(history.logs.len(), cap);
+    assert_eq!(history.logs.front().unwrap().id, 6);
+    assert_eq!(history.logs.back().unwrap().id, cap as u32 + 5);
+}
@@ -2,22 +2,31 @@ use bevy::prelude::*;
 use rand::random;
 
 mod after_battle_sys;
+pub mod boss;
 mod combat_system;
 pub mod component;
 mod damage;
 pub mod event;
+pub mod history;
+pub mod speed;
+pub mod status_effect;
 mod talk_system;
 
 use after_battle_sys::*;
+pub use boss::*;
 use combat_system::*;
 pub use component::*;
+pub use damage::DamageOut;
 use damage::*;
 use event::*;
+pub use history::*;
+pub use speed::*;
+pub use status_effect::*;
 use talk_system::*;
 
 use super::{
     battle_scene::component::LvUpQueue,
-    character::component::{Hero, HeroClass, MonsterType, Turns},
+    character::component::{Hero, HeroClass, Turns},
     combat_system::next_sentance_combat,
     monster::Monster,
     Player, TerminalQueue, WriteTerminalEvent,
@@ -35,14 +44,21 @@ impl Plugin for BattlePlugin {
             .add_event::<StartBattleEvent>()
             .add_event::<NextTurnEvent>()
             .add_event::<AttackEvent>()
+            .add_event::<BossPhaseEvent>()
             .add_event::<SpawnBattleSceneEvent>()
             .add_event::<SpawnDropSceneEvent>()
+            .init_resource::<BattleHistory>()
+            .init_resource::<BattleSpeed>()
             .add_systems(
                 Update,
                 (loop_talking, drop_count_down)
                     .run_if(in_state(TerminalState::Talking))
                     .run_if(in_state(OverlayCombatState::Opened)),
             )
+            .add_systems(
+                PreUpdate,
+                auto_advance.run_if(in_state(OverlayCombatState::Opened)),
+            )
             .add_systems(OnEnter(TerminalState::Combating), start_combat_turn)
             .add_systems(
                 Update,
@@ -56,9 +72,11 @@ impl Plugin for BattlePlugin {
                     .run_if(in_state(OverlayCombatState::Opened)),
             )
             .add_systems(Update, spawn_battle)
+            .add_systems(Update, (load_battle_history, save_battle_history))
             .add_systems(OnEnter(OverlayCombatState::Closed), despawn_battle)
             .register_type::<Combat>()
             .register_type::<TurnFlag>()
+            .register_type::<BattleSpeed>()
             .register_type::<TalkFlag>()
             .register_type::<TrashTalk>();
     }
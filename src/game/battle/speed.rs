@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use super::*;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Speed {
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+}
+
+impl Speed {
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            Speed::Normal => 1.,
+            Speed::Double => 2.,
+            Speed::Quadruple => 4.,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Speed::Normal => Speed::Double,
+            Speed::Double => Speed::Quadruple,
+            Speed::Quadruple => Speed::Normal,
+        }
+    }
+}
+
+// Player preferences for how a battle plays out, kept between battles
+#[derive(Resource, Debug, Reflect)]
+pub struct BattleSpeed {
+    pub is_auto: bool,
+    pub is_skip: bool,
+    pub speed: Speed,
+    // true for a single frame, works like a tap on the terminal
+    pub advance: bool,
+    auto_timer: Timer,
+}
+
+impl Default for BattleSpeed {
+    fn default() -> Self {
+        Self {
+            is_auto: false,
+            is_skip: false,
+            speed: Speed::default(),
+            advance: false,
+            auto_timer: Timer::from_seconds(AUTO_TURN_PAUSE, TimerMode::Once),
+        }
+    }
+}
+
+// how long a finished sentence stays on screen before auto moves on
+const AUTO_TURN_PAUSE: f32 = 0.6;
+
+impl BattleSpeed {
+    pub fn scale(&self, delta: Duration) -> Duration {
+        delta.mul_f32(self.speed.multiplier())
+    }
+
+    pub fn is_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, touches: &Touches) -> bool {
+        self.advance
+            || keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
+            || touches.any_just_pressed()
+    }
+
+    fn tick(&mut self, delta: Duration, is_ready: bool) {
+        self.advance = false;
+
+        if !self.is_auto || !is_ready {
+            self.auto_timer.reset();
+            return;
+        }
+
+        if self.auto_timer.tick(self.scale(delta)).finished() {
+            self.advance = true;
+            self.auto_timer.reset();
+        }
+    }
+}
+
+// Taps the terminal for the player once the current sentence is done
+pub fn auto_advance(
+    mut speed: ResMut<BattleSpeed>,
+    text_input_query: Query<&TerminalQueue>,
+    battle_query: Query<&TurnFlag>,
+    terminal_state: Res<State<TerminalState>>,
+    time: Res<Time>,
+) {
+    let is_sentence_done = text_input_query
+        .get_single()
+        .is_ok_and(|queue| queue.is_sentence_done());
+
+    let is_ready = match terminal_state.get() {
+        TerminalState::Combating => battle_query
+            .get_single()
+            .is_ok_and(|turn_flag| turn_flag.is_all_ready()),
+        TerminalState::Talking => is_sentence_done,
+        _ => false,
+    };
+
+    speed.tick(time.delta(), is_ready);
+}
+
+#[test]
+fn test_auto_advance_single_frame() {
+    let mut speed = BattleSpeed {
+        is_auto: true,
+        speed: Speed::Quadruple,
+        ..default()
+    };
+
+    // 4x speed only needs a quarter of the pause
+    speed.tick(Duration::from_secs_f32(AUTO_TURN_PAUSE / 4. + 0.01), true);
+    assert!(speed.advance);
+
+    speed.tick(Duration::from_secs_f32(0.01), true);
+    assert!(!speed.advance);
+
+    speed.is_auto = false;
+    speed.tick(Duration::from_secs_f32(AUTO_TURN_PAUSE), true);
+    assert!(!speed.advance);
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum StatusKind {
+    Burn,
+    Poison,
+    Stun,
+    Bleed,
+    Shield,
+}
+
+impl StatusKind {
+    // how many copies of the same effect can live on one target
+    pub fn max_stack(&self) -> u32 {
+        match self {
+            StatusKind::Poison => 5,
+            StatusKind::Bleed => 3,
+            _ => 1,
+        }
+    }
+
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            StatusKind::Burn => "BRN",
+            StatusKind::Poison => "PSN",
+            StatusKind::Stun => "STN",
+            StatusKind::Bleed => "BLD",
+            StatusKind::Shield => "SHD",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            StatusKind::Burn => Color::linear_rgb(1., 0.4, 0.),
+            StatusKind::Poison => Color::linear_rgb(0.6, 0., 0.8),
+            StatusKind::Stun => Color::linear_rgb(1., 1., 0.),
+            StatusKind::Bleed => Color::linear_rgb(0.8, 0., 0.),
+            StatusKind::Shield => Color::linear_rgb(0.3, 0.7, 1.),
+        }
+    }
+}
+
+impl std::fmt::Display for StatusKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            StatusKind::Burn => write!(f, "Burning"),
+            StatusKind::Poison => write!(f, "Poisoned"),
+            StatusKind::Stun => write!(f, "Stunned"),
+            StatusKind::Bleed => write!(f, "Bleeding"),
+            StatusKind::Shield => write!(f, "Shielded"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns: u32,
+    pub stacks: u32,
+    // damage per stack for dots, absorb amount for shield
+    pub potency: f32,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusKind, turns: u32, potency: f32) -> Self {
+        Self {
+            kind,
+            turns,
+            stacks: 1,
+            potency,
+        }
+    }
+
+    pub fn burn(potency: f32) -> Self {
+        Self::new(StatusKind::Burn, 3, potency)
+    }
+
+    pub fn poison(potency: f32) -> Self {
+        Self::new(StatusKind::Poison, 4, potency)
+    }
+
+    pub fn bleed(potency: f32) -> Self {
+        Self::new(StatusKind::Bleed, 2, potency)
+    }
+
+    pub fn stun() -> Self {
+        Self::new(StatusKind::Stun, 1, 0.)
+    }
+
+    pub fn shield(potency: f32) -> Self {
+        Self::new(StatusKind::Shield, 3, potency)
+    }
+}
+
+// Result of ticking the effects of whoever is about to act
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+pub struct StatusTick {
+    pub damage: f32,
+    pub is_stunned: bool,
+    pub expired: Vec<StatusKind>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+impl StatusEffects {
+    // Stackable effects add a stack and refresh the duration,
+    // the others only refresh the duration and keep the stronger potency
+    pub fn apply(&mut self, effect: StatusEffect) {
+        match self.0.iter_mut().find(|e| e.kind == effect.kind) {
+            Some(current) => {
+                current.stacks = (current.stacks + effect.stacks).min(effect.kind.max_stack());
+                current.turns = current.turns.max(effect.turns);
+                current.potency = current.potency.max(effect.potency);
+            }
+            None => self.0.push(effect),
+        }
+    }
+
+    // Run at the start of the owner's turn
+    pub fn tick(&mut self) -> StatusTick {
+        let mut tick = StatusTick::default();
+
+        for effect in self.0.iter_mut() {
+            match effect.kind {
+                StatusKind::Burn | StatusKind::Poison | StatusKind::Bleed => {
+                    tick.damage += effect.potency * effect.stacks as f32;
+                }
+                StatusKind::Stun => tick.is_stunned = true,
+                StatusKind::Shield => (),
+            }
+
+            effect.turns = effect.turns.saturating_sub(1);
+            if effect.turns == 0 {
+                tick.expired.push(effect.kind);
+            }
+        }
+
+        self.0.retain(|e| e.turns > 0);
+        tick
+    }
+
+    // Shield soaks up the incoming damage first, return what is left
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        let Some(shield) = self.0.iter_mut().find(|e| e.kind == StatusKind::Shield) else {
+            return damage;
+        };
+
+        let absorbed = shield.potency.min(damage);
+        shield.potency -= absorbed;
+        if shield.potency <= 0. {
+            self.0.retain(|e| e.kind != StatusKind::Shield);
+        }
+
+        damage - absorbed
+    }
+}
+
+#[test]
+fn test_status_stack_and_refresh() {
+    let mut effects = StatusEffects::default();
+    let find = |effects: &StatusEffects, kind: StatusKind| {
+        effects.0.iter().find(|e| e.kind == kind).cloned()
+    };
+
+    for _ in 0..10 {
+        effects.apply(StatusEffect::poison(1.));
+    }
+    assert_eq!(find(&effects, StatusKind::Poison).unwrap().stacks, 5);
+
+    effects.apply(StatusEffect::burn(2.));
+    effects.apply(StatusEffect::burn(3.));
+    let burn = find(&effects, StatusKind::Burn).unwrap();
+    assert_eq!((burn.stacks, burn.turns, burn.potency), (1, 3, 3.));
+
+    effects.apply(StatusEffect::stun());
+    let tick = effects.tick();
+    assert_eq!(tick.damage, 5. + 3.);
+    assert!(tick.is_stunned);
+    assert!(
+        find(&effects, StatusKind::Stun).is_none(),
+        "stun should expire"
+    );
+
+    effects.apply(StatusEffect::shield(4.));
+    assert_eq!(effects.absorb(3.), 0.);
+    assert_eq!(effects.absorb(3.), 2.);
+    assert!(find(&effects, StatusKind::Shield).is_none());
+}
@@ -1,4 +1,8 @@
-use crate::{game::character::component::BaseStates, state::SimulationState};
+use crate::{
+    game::character::component::BaseStates,
+    model::progression::Progression,
+    state::{OverlayEndingCreditState, SimulationState},
+};
 
 use super::*;
 // Battle was spawned by the creation of fightable monster
@@ -7,58 +11,98 @@ use super::*;
 //          3. send an event for to the terminal for initialization.
 pub fn spawn_battle(
     mut commands: Commands,
-    hero_query: Query<&BaseStates, With<Hero>>,
-    monster_query: Query<(&BaseStates, Entity), (Added<Monster>, Without<Hero>)>,
+    hero_query: Query<(&BaseStates, &HeroClass), With<Hero>>,
+    monster_query: Query<
+        (&BaseStates, Entity, Option<&BossScript>),
+        (Added<Monster>, Without<Hero>),
+    >,
     mut spawn_battle_scene_event: EventWriter<SpawnBattleSceneEvent>,
     mut next_combat_state: ResMut<NextState<OverlayCombatState>>,
     mut next_simulation_state: ResMut<NextState<SimulationState>>,
     mut next_combat_stage: ResMut<NextState<TerminalState>>,
     battle_query: Query<Entity, With<Combat>>,
+    progression: Res<Progression>,
 ) {
-    if let Ok((monster_state, monster_entity)) = monster_query.get_single() {
-        let hero_state = hero_query.get_single().expect("No Hero Found");
+    // the whole group is spawned in the same frame
+    let foes = monster_query
+        .iter()
+        .map(|(monster_state, monster_entity, _)| {
+            Foe::new(monster_entity, monster_state.hp(&progression))
+        })
+        .collect::<Vec<_>>();
 
-        next_combat_state.set(OverlayCombatState::Opened);
-        next_simulation_state.set(SimulationState::Pause);
-        next_combat_stage.set(TerminalState::Talking);
+    if foes.is_empty() {
+        return;
+    }
 
-        for prev_battle in battle_query.iter() {
-            commands.entity(prev_battle).despawn_recursive();
-        }
+    let (hero_state, hero_class) = hero_query.get_single().expect("No Hero Found");
 
-        info!("Battle Started");
-        let id = commands
-            .spawn((
-                Name::new("Battle"),
-                Combat {
-                    monster_entity,
-                    records: Vec::new(),
-                    monster_hp_remain: monster_state.hp(),
-                    player_hp_remain: hero_state.hp(),
-                },
-                TalkFlag::default(),
-                TurnFlag::default(),
-                TrashTalk::demo(),
-            ))
-            .id();
+    // Paladin walks into every fight behind a shield
+    let mut player_effects = StatusEffects::default();
+    if *hero_class == HeroClass::Paladin {
+        player_effects.apply(StatusEffect::shield(hero_state.hp(&progression) * 0.2));
+    }
 
-        spawn_battle_scene_event.send(SpawnBattleSceneEvent {
-            battle: id,
-            monster: monster_entity,
-        });
+    next_combat_state.set(OverlayCombatState::Opened);
+    next_simulation_state.set(SimulationState::Pause);
+    next_combat_stage.set(TerminalState::Talking);
+
+    for prev_battle in battle_query.iter() {
+        commands.entity(prev_battle).despawn_recursive();
     }
+
+    let monsters = foes.iter().map(|foe| foe.entity).collect::<Vec<_>>();
+
+    // bosses bring their own opening lines
+    let talk = match monster_query
+        .iter()
+        .find_map(|(_, _, boss)| boss.filter(|boss| !boss.data.intro.is_empty()))
+    {
+        Some(boss) => TrashTalk {
+            talk: boss.data.intro.clone().into(),
+        },
+        None => TrashTalk::encounter(monsters.len()),
+    };
+
+    info!("Battle Started against {} monsters", monsters.len());
+    let id = commands
+        .spawn((
+            Name::new("Battle"),
+            Combat {
+                foes,
+                target: 0,
+                records: Vec::new(),
+                player_hp_remain: hero_state.hp(&progression),
+                player_effects,
+            },
+            TalkFlag::default(),
+            TurnFlag::default(),
+            talk,
+        ))
+        .id();
+
+    spawn_battle_scene_event.send(SpawnBattleSceneEvent {
+        battle: id,
+        monsters,
+    });
 }
 
 pub fn loop_talking(
-    mut talk_query: Query<(&mut TalkFlag, &mut TrashTalk, Option<&ExitFlag>)>,
+    mut talk_query: Query<(
+        &mut TalkFlag,
+        &mut TrashTalk,
+        Option<&ExitFlag>,
+        Option<&FinalVictoryFlag>,
+    )>,
     mut write_terminal_event: EventWriter<WriteTerminalEvent>,
     mut next_combat_stage: ResMut<NextState<TerminalState>>,
     text_input_query: Query<&TerminalQueue>,
 
     mut next_simulation_state: ResMut<NextState<SimulationState>>,
     mut next_combat_state: ResMut<NextState<OverlayCombatState>>,
+    mut next_ending_credit_state: ResMut<NextState<OverlayEndingCreditState>>,
 ) {
-    for (mut flag, mut talk, exit) in talk_query.iter_mut() {
+    for (mut flag, mut talk, exit, final_victory) in talk_query.iter_mut() {
         let Ok(queue) = text_input_query.get_single() else {
             continue;
         };
@@ -78,6 +122,12 @@ pub fn loop_talking(
 
         if flag.is_all_finished() && queue.is_empty() {
             match exit {
+                // the world stays paused while the credits roll
+                Some(_) if final_victory.is_some() => {
+                    next_ending_credit_state.set(OverlayEndingCreditState::Opened);
+                    next_combat_state.set(OverlayCombatState::Closed);
+                    info!("Exited Battle, rolling the credits")
+                }
                 Some(_) => {
                     next_simulation_state.set(SimulationState::Running);
                     next_combat_state.set(OverlayCombatState::Closed);
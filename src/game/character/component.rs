@@ -1,6 +1,7 @@
 use std::slice::Iter;
 
 use bevy::{
+    color::Color,
     core::Name,
     ecs::{bundle::Bundle, component::Component},
     log::info,
@@ -14,11 +15,34 @@ use crate::{
     animation::component::{AnimationIndices, AnimationTimer},
     error::UserInputError,
     game::battle_scene::BattleHeroSprite,
-    model::sub::DropEquipment,
+    model::{player::HeroSave, progression::Progression, sub::DropEquipment},
 };
 
-const C1: f64 = 577.1124142;
+const RESPEC_COST_PER_LV: u64 = 10;
 
+// Points the player put in by hand on the status board, on top of the level growth
+#[derive(Reflect, Clone, Copy, Default, Debug, PartialEq)]
+pub struct Allocation {
+    pub str: u32,
+    pub def: u32,
+    pub agi: u32,
+}
+
+impl Allocation {
+    pub fn total(&self) -> u32 {
+        self.str + self.def + self.agi
+    }
+}
+
+// A str/dex/agi triple, used for a class's starting stats and its per level growth
+#[derive(Reflect, Clone, Copy, Default, Debug, PartialEq)]
+pub struct StatLine {
+    pub str: f32,
+    pub dex: f32,
+    pub agi: f32,
+}
+
+// The one stat model for heroes and monsters, dex doubles as defense
 #[derive(Component, Reflect, Clone, Default)]
 pub struct BaseStates {
     lv: u32,
@@ -27,18 +51,22 @@ pub struct BaseStates {
     agi: f32,
     hp: f32,
     exp: f64,
+    state_point: u32,
+    allocated: Allocation,
+    growth: StatLine,
 }
 
 impl BaseStates {
-    pub fn new_hero() -> Self {
-        let lv = 1;
+    pub fn new_hero(class: HeroClass) -> Self {
+        let base = class.base_stats();
         Self {
-            lv,
-            dex: lv as f32 / 3.,
-            str: lv as f32 / 3.,
-            agi: lv as f32 / 3.,
-            hp: 2.,
-            exp: Self::get_lv_total_exp(1),
+            lv: 1,
+            dex: base.dex,
+            str: base.str,
+            agi: base.agi,
+            hp: class.base_hp(),
+            growth: class.growth(),
+            ..Default::default()
         }
     }
 
@@ -49,204 +77,140 @@ impl BaseStates {
             str: lv as f32 / 3.,
             agi: lv as f32 / 3.,
             hp: 0.,
-            exp: Self::get_lv_total_exp(lv),
+            // monsters never gain exp, so theirs stays at zero
+            ..Default::default()
         }
     }
 
     pub fn dex(&self) -> f32 {
-        self.dex
+        self.dex + self.allocated.def as f32
     }
 
     pub fn str(&self) -> f32 {
-        self.str
+        self.str + self.allocated.str as f32
     }
 
     pub fn agi(&self) -> f32 {
-        self.agi
-    }
-
-    pub fn lv(&self) -> u32 {
-        self.lv
-    }
-
-    pub fn hp(&self) -> f32 {
-        self.hp + 2_f32.powf(self.lv as f32 / 20.) * 3.
-    }
-
-    pub fn lv_f32(&self) -> f32 {
-        self.lv as f32
-    }
-
-    pub fn get_total_point(&self) -> f32 {
-        self.str + self.dex + self.agi
-    }
-
-    pub fn total_exp_req_for_next_lv(&self) -> f64 {
-        100. * (2_f64.powf((self.lv as f64 + 1.) / 10.) - 1.) / (2_f64.powf(0.1) - 1.)
+        self.agi + self.allocated.agi as f32
     }
 
-    pub fn exp_req_for_next_lv(&self) -> f32 {
-        100. * 2_f32.powf((self.lv as f32 + 1.) / 10.)
+    pub fn allocated(&self) -> Allocation {
+        self.allocated
     }
 
-    pub fn total_exp(&self) -> f64 {
-        self.exp
+    pub fn state_point(&self) -> u32 {
+        self.state_point
     }
 
-    pub fn exp_after_lv_up(&self) -> f32 {
-        (self.total_exp()
-            - (self.total_exp_req_for_next_lv() - 100. * 2_f64.powf((self.lv as f64) / 10.)))
-            as f32
+    pub fn plus_state_point(&mut self, point: u32) {
+        self.state_point += point
     }
 
-    // The exp hero earned when killing the monster
-    pub fn exp_gain(&mut self, monster_lv: u32) -> u32 {
-        self.exp += Self::exp_drop_by_monster(monster_lv);
-        let diff = self.update_lv_with_exp();
-        self.distribut_state_point();
-        diff
-    }
-
-    pub fn crit_multiplier(&self) -> f32 {
-        let mul = 2. + self.str / self.get_total_point() * self.agi() / self.get_total_point() * 3.;
-
-        info!("Critical Multiplier: {}", mul);
-
-        mul
+    // take half of the unspent points into the board buffer
+    pub fn half_state_point(&mut self) -> Result<u32, UserInputError> {
+        match self.state_point {
+            0 => Err(UserInputError()),
+            1 => {
+                self.state_point = 0;
+                Ok(1)
+            }
+            point => {
+                let half = point / 2;
+                self.state_point -= half;
+                Ok(half)
+            }
+        }
     }
 
-    pub fn crit_rate(&self, player_class: Option<&HeroClass>) -> f32 {
-        let max_rate: f32 = if let Some(class) = player_class {
-            match class {
-                HeroClass::Rogue => 50.,
-                _ => 40.,
+    pub fn all_state_point(&mut self) -> Result<u32, UserInputError> {
+        match self.state_point {
+            0 => Err(UserInputError()),
+            point => {
+                self.state_point = 0;
+                Ok(point)
             }
-        } else {
-            // Monster
-            0.
-        };
-
-        max_rate * self.lv_f32() / self.get_total_point()
+        }
     }
 
-    // run this everytime exp has changed
-    // return how many level been upgraded since
-    fn update_lv_with_exp(&mut self) -> u32 {
-        let lv = (10. * (self.exp / 100. * (2_f64.powf(0.1) - 1.) + 1.).log2()).floor() as u32;
-        let diff = lv - self.lv;
-        self.lv = lv;
-        diff
+    // the points were already taken out of state_point by the buffer
+    pub fn allocate(&mut self, allocation: Allocation) {
+        self.allocated.str += allocation.str;
+        self.allocated.def += allocation.def;
+        self.allocated.agi += allocation.agi;
     }
 
-    fn get_lv_total_exp(lv: u32) -> f64 {
-        if lv == 1 {
-            return 0_f64;
-        }
-
-        let mut total = 0.;
-        for i in 1..=(lv - 1) {
-            total += 2_f64.powf(i as f64 / 10.) * 100.;
+    // give every allocated point back, returns how many were refunded
+    pub fn respec(&mut self) -> Result<u32, UserInputError> {
+        match self.allocated.total() {
+            0 => Err(UserInputError()),
+            total => {
+                self.state_point += total;
+                self.allocated = Allocation::default();
+                Ok(total)
+            }
         }
-
-        total
     }
 
-    pub fn exp_drop_by_monster(lv: u32) -> f64 {
-        C1 * 2_f64.powf(lv as f64 / 10.)
+    // gold needed to respec, grows with level
+    pub fn respec_cost(&self) -> u64 {
+        RESPEC_COST_PER_LV * self.lv as u64
     }
 
-    fn distribut_state_point(&mut self) {
-        self.lv();
-        self.str = self.lv_f32() / 3. * 2.;
-        self.dex = self.lv_f32() / 3.;
-        self.agi = 1.;
+    pub fn lv(&self) -> u32 {
+        self.lv
     }
-}
 
-#[derive(Component, Reflect, Clone, Default)]
-pub struct Defense(f32);
-
-impl Defense {
-    pub fn new(def: f32) -> Self {
-        Defense(def)
+    pub fn hp(&self, progression: &Progression) -> f32 {
+        self.hp + progression.lv_hp(self.lv)
     }
 
-    pub fn get(&self) -> f32 {
-        self.0
-    }
-    pub fn upgrade(&mut self, point: u32) {
-        self.0 += point as f32;
+    pub fn lv_f32(&self) -> f32 {
+        self.lv as f32
     }
-}
-
-#[derive(Component, Clone, Copy, Reflect, Default)]
-pub struct HealthPoint(f32);
 
-impl HealthPoint {
-    pub fn hero() -> Self {
-        Self::new(2.)
-    }
-    pub fn monster() -> Self {
-        Self::new(0.)
+    pub fn get_total_point(&self) -> f32 {
+        self.str() + self.dex() + self.agi()
     }
-}
 
-impl HealthPoint {
-    pub fn new(point: f32) -> Self {
-        Self(point)
+    pub fn exp_req_for_next_lv(&self, progression: &Progression) -> f32 {
+        progression.exp_to_next_lv(self.lv) as f32
     }
 
-    pub fn get_hp(&self, lv: &Level) -> f32 {
-        self.0 + 2_f32.powf(lv.get() as f32 / 20.) * 3.
+    pub fn total_exp(&self) -> f64 {
+        self.exp
     }
 
-    // pub fn upgrade(&mut self, point: u32) {
-    //     self.0 += point as f32;
-    // }
-    // pub fn get(&self) -> f32 {
-    //     self.0
-    // }
-}
-
-#[derive(Component, Reflect, Clone, Default)]
-pub struct Strength(f32);
-
-impl Strength {
-    // pub fn m1(&self, lv: &Level) -> f32 {
-    //     (self.0 + 2_f32.powf(lv.get() as f32 / 20.)) * (1. + random::<f32>() / 10.)
-    // }
-
-    pub fn new(point: f32) -> Self {
-        Self(point)
+    // exp earned since reaching the current level
+    pub fn exp_after_lv_up(&self, progression: &Progression) -> f32 {
+        (self.exp - progression.total_exp_for_lv(self.lv)) as f32
     }
 
-    pub fn upgrade(&mut self, point: u32) {
-        self.0 += point as f32;
-    }
-    pub fn get(&self) -> f32 {
-        self.0
+    // The exp hero earned when killing the monster, boosters scale the drop before it is added
+    pub fn exp_gain(&mut self, monster_lv: u32, multiplier: f64, progression: &Progression) -> u32 {
+        self.exp += progression.monster_exp(monster_lv) * multiplier;
+        let diff = self.update_lv_with_exp(progression);
+        self.grow(diff);
+        self.state_point += diff;
+        diff
     }
-}
 
-#[derive(Component, Default, Reflect, Clone)]
-pub struct Critical(f32);
-
-impl Critical {
-    pub fn new(point: f32) -> Self {
-        Self(point)
+    // class growth on top of the points handed out for each level
+    fn grow(&mut self, lv_diff: u32) {
+        self.str += self.growth.str * lv_diff as f32;
+        self.dex += self.growth.dex * lv_diff as f32;
+        self.agi += self.growth.agi * lv_diff as f32;
     }
 
-    pub fn damage_multiplier(&self, lv: &Level, attacker_str: &Strength) -> f32 {
-        let mul = 2.
-            + attacker_str.0 / lv.get_total_point() as f32 * self.0 / lv.get_total_point() as f32
-                * 3.;
+    pub fn crit_multiplier(&self) -> f32 {
+        let mul =
+            2. + self.str() / self.get_total_point() * self.agi() / self.get_total_point() * 3.;
 
         info!("Critical Multiplier: {}", mul);
 
         mul
     }
 
-    pub fn rate(&self, lv: &Level, player_class: Option<&HeroClass>) -> f32 {
+    pub fn crit_rate(&self, player_class: Option<&HeroClass>) -> f32 {
         let max_rate: f32 = if let Some(class) = player_class {
             match class {
                 HeroClass::Rogue => 50.,
@@ -254,88 +218,152 @@ impl Critical {
             }
         } else {
             // Monster
-            40.
+            0.
         };
 
-        max_rate * self.0 / lv.get_total_point() as f32
+        max_rate * self.lv_f32() / self.get_total_point()
+    }
+
+    // run this everytime exp has changed
+    // return how many level been upgraded since
+    fn update_lv_with_exp(&mut self, progression: &Progression) -> u32 {
+        let lv = progression.lv_for_exp(self.exp);
+        let diff = lv.saturating_sub(self.lv);
+        self.lv = lv.max(self.lv);
+        diff
     }
 
-    pub fn upgrade(&mut self, point: u32) {
-        self.0 += point as f32;
+    // A hero rebuilt from the profile, levels are recalculated from the saved exp
+    pub fn from_save(class: HeroClass, save: &HeroSave, progression: &Progression) -> Self {
+        let mut hero = Self::new_hero(class);
+        hero.exp = save.exp;
+        let diff = hero.update_lv_with_exp(progression);
+        hero.grow(diff);
+        hero.state_point = save.state_point;
+        hero.allocated = Allocation {
+            str: save.str,
+            def: save.def,
+            agi: save.agi,
+        };
+        hero
     }
-    pub fn get(&self) -> f32 {
-        self.0
+
+    pub fn to_save(&self, class: HeroClass) -> HeroSave {
+        HeroSave {
+            class: class.to_string(),
+            exp: self.exp,
+            state_point: self.state_point,
+            str: self.allocated.str,
+            def: self.allocated.def,
+            agi: self.allocated.agi,
+        }
     }
 }
 
-// Deprecated
-#[derive(Component, Reflect, Clone, Default)]
-pub struct Level(u64);
-
-impl Level {
-    pub fn new(lv: u64) -> Self {
-        Level(lv)
-    }
-    pub fn get_f32(&self) -> f32 {
-        self.0 as f32
-    }
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub enum HeroClass {
+    Paladin,
+    Warrior,
+    Rogue,
+}
 
-    pub fn get(&self) -> u64 {
-        self.0
+impl std::fmt::Display for HeroClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            HeroClass::Paladin => write!(f, "Paladin"),
+            HeroClass::Warrior => write!(f, "Warrior"),
+            HeroClass::Rogue => write!(f, "Rogue"),
+        }
     }
+}
 
-    pub fn get_total_point(&self) -> u64 {
-        self.0
+impl HeroClass {
+    pub fn iterator() -> Iter<'static, HeroClass> {
+        static CLASSES: [HeroClass; 3] = [HeroClass::Paladin, HeroClass::Warrior, HeroClass::Rogue];
+        CLASSES.iter()
     }
 
-    pub fn total_exp_req_for_next_lv(&self) -> f32 {
-        2_f32.powf(self.0 as f32 / 10.) * 100.
+    pub fn description(&self) -> &'static str {
+        match self {
+            HeroClass::Paladin => "Thick armor and a big health pool, slow to kill.",
+            HeroClass::Warrior => "Hits hard and grows strong with every level.",
+            HeroClass::Rogue => "Quick and fragile, lives for critical hits.",
+        }
     }
 
-    pub fn upgrade(&mut self) {
-        if self.0 < u64::MAX {
-            self.0 += 1;
-        } else {
-            self.0 = u64::MAX;
+    pub fn base_stats(&self) -> StatLine {
+        match self {
+            HeroClass::Paladin => StatLine {
+                str: 0.3,
+                dex: 0.5,
+                agi: 0.2,
+            },
+            HeroClass::Warrior => StatLine {
+                str: 0.5,
+                dex: 0.3,
+                agi: 0.2,
+            },
+            HeroClass::Rogue => StatLine {
+                str: 0.3,
+                dex: 0.2,
+                agi: 0.5,
+            },
         }
     }
 
-    pub fn get_total_exp(&self) -> f32 {
-        if self.0 == 1 {
-            return 0_f32;
+    pub fn base_hp(&self) -> f32 {
+        match self {
+            HeroClass::Paladin => 4.,
+            HeroClass::Warrior => 2.,
+            HeroClass::Rogue => 1.,
         }
+    }
 
-        let mut total: f32 = 0.;
-        for i in 1..=(self.0 - 1) {
-            total += 2_f32.powf(i as f32 / 10.) * 100.;
+    // stats gained per level before any allocated points
+    pub fn growth(&self) -> StatLine {
+        match self {
+            HeroClass::Paladin => StatLine {
+                str: 0.15,
+                dex: 0.3,
+                agi: 0.05,
+            },
+            HeroClass::Warrior => StatLine {
+                str: 0.3,
+                dex: 0.15,
+                agi: 0.05,
+            },
+            HeroClass::Rogue => StatLine {
+                str: 0.15,
+                dex: 0.05,
+                agi: 0.3,
+            },
         }
-
-        total
     }
 
-    pub fn exp_gain(&self) -> f64 {
-        C1 * 2_f64.powf(self.0 as f64 / 10.)
+    // weapon id from weapon.csv handed out when nothing is equipped yet
+    pub fn starting_weapon(&self) -> u32 {
+        match self {
+            HeroClass::Paladin => 4,
+            HeroClass::Warrior => 1,
+            HeroClass::Rogue => 2,
+        }
     }
-}
-
-#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
-pub enum HeroClass {
-    Paladin,
-    Warrior,
-    Rogue,
-}
 
-impl HeroClass {
-    pub fn iterator() -> Iter<'static, HeroClass> {
-        static CLASSES: [HeroClass; 3] = [HeroClass::Paladin, HeroClass::Warrior, HeroClass::Rogue];
-        CLASSES.iter()
+    // the classes share one sprite sheet, the tint tells them apart
+    pub fn tint(&self) -> Color {
+        match self {
+            HeroClass::Paladin => Color::srgb(1., 0.9, 0.6),
+            HeroClass::Warrior => Color::WHITE,
+            HeroClass::Rogue => Color::srgb(0.7, 0.7, 1.),
+        }
     }
 
-    pub fn to_string(&self) -> String {
+    // only the classes that have been drawn
+    pub fn portrait(&self) -> Option<&'static str> {
         match self {
-            HeroClass::Paladin => "Paladin".to_string(),
-            HeroClass::Warrior => "Warrior".to_string(),
-            HeroClass::Rogue => "Rogue".to_string(),
+            HeroClass::Paladin => Some("images/paladin.png"),
+            HeroClass::Warrior => Some("images/warrior.jpg"),
+            HeroClass::Rogue => None,
         }
     }
 }
@@ -349,23 +377,6 @@ pub enum MonsterType {
 }
 
 impl MonsterType {
-    pub fn iterator() -> Iter<'static, MonsterType> {
-        static CLASSES: [MonsterType; 3] = [
-            MonsterType::Monster1,
-            MonsterType::HellBat,
-            MonsterType::Monster3,
-        ];
-        CLASSES.iter()
-    }
-
-    pub fn to_string(&self) -> String {
-        match self {
-            MonsterType::Monster1 => "Monster1".to_string(),
-            MonsterType::HellBat => "HellBat".to_string(),
-            MonsterType::Monster3 => "Monster3".to_string(),
-        }
-    }
-
     pub fn from_str(s: &str) -> Self {
         match s {
             "Monster1" => MonsterType::Monster1,
@@ -431,60 +442,9 @@ pub struct BattleMonsterSprite;
 #[derive(Component, PartialEq, Eq, Reflect)]
 pub struct DespawnBossFlag;
 
-#[derive(Component, Default)]
-pub struct Experience(pub f32);
-
 #[derive(Component)]
 pub struct Hero {}
 
-#[derive(Component, Default)]
-pub struct StatePoint(u32);
-
-impl StatePoint {
-    pub fn plus_one(&mut self) {
-        self.0 += 1
-    }
-    pub fn plus(&mut self, point: u32) {
-        self.0 += point
-    }
-
-    // pub fn minus(&mut self) -> Result<&StatePoint, UserInputError> {
-    //     if self.0 == 0 {
-    //         Err(UserInputError("Not Enough State Point".to_string()))
-    //     } else {
-    //         self.0 -= 1;
-    //         Ok(self)
-    //     }
-    // }
-
-    pub fn half(&mut self) -> Result<u32, UserInputError> {
-        if self.0 == 0 {
-            Err(UserInputError())
-        } else if self.0 == 1 {
-            self.0 -= 1;
-            Ok(1)
-        } else {
-            let half = (self.0 as f32 / 2.0).floor() as u32;
-            self.0 -= half;
-            Ok(half)
-        }
-    }
-
-    pub fn all(&mut self) -> Result<u32, UserInputError> {
-        if self.0 == 0 {
-            Err(UserInputError())
-        } else {
-            let old = self.0;
-            self.0 = 0;
-            Ok(old)
-        }
-    }
-
-    pub fn get(&self) -> u32 {
-        self.0
-    }
-}
-
 #[derive(Component)]
 pub struct Turns(u32);
 
@@ -500,9 +460,13 @@ impl Turns {
     }
     pub fn minus(&mut self) {
         if self.0 > u32::MIN {
-            self.0 = self.0 - 1
+            self.0 -= 1
         }
     }
+
+    pub fn plus(&mut self, n: u32) {
+        self.0 = self.0.saturating_add(n);
+    }
 }
 
 #[derive(Component, Clone, Default)]
@@ -537,13 +501,34 @@ impl Bag {
 
 #[test]
 fn level_system_validation() {
-    let level_40 = Level(40);
-    let level_1 = Level(1);
-    assert_eq!(level_1.total_exp_req_for_next_lv().floor(), 107.);
-    assert_eq!(level_1.get_total_exp().floor(), 0.);
+    let progression = Progression::default();
+    let exp_between =
+        |lv: u32| progression.total_exp_for_lv(lv + 1) - progression.total_exp_for_lv(lv);
+    assert_eq!(exp_between(1).floor(), 107.);
+    assert_eq!(progression.total_exp_for_lv(1).floor(), 0.);
+
+    assert_eq!(exp_between(40).floor(), 1600.);
+    assert_eq!(progression.total_exp_for_lv(40).floor(), 20799.);
+}
 
-    assert_eq!(level_40.total_exp_req_for_next_lv().floor(), 1600.);
-    assert_eq!(level_40.get_total_exp().floor(), 20799.);
+#[test]
+fn test_level_up_grants_state_point() {
+    let mut hero = BaseStates::new_hero(HeroClass::Warrior);
+    let diff = hero.exp_gain(20, 1., &Progression::default());
+    assert!(diff > 0);
+    assert_eq!(hero.state_point(), diff);
+
+    let half = hero.half_state_point().unwrap();
+    assert_eq!(hero.state_point(), diff - half);
+    assert_eq!(hero.all_state_point().unwrap(), diff - half);
+    assert!(hero.all_state_point().is_err());
+
+    let str_before = hero.str();
+    hero.allocate(Allocation {
+        str: diff,
+        ..Default::default()
+    });
+    assert_eq!(hero.str(), str_before + diff as f32);
 }
 
 // Todo Unit Testing
@@ -561,3 +546,62 @@ fn level_system_validation() {
 //     // when crit point evenly distributed, mul and rate?
 //     // when crit point monopolized
 // }
+
+#[test]
+fn test_respec_and_save_keep_allocation() {
+    let mut hero = BaseStates::new_hero(HeroClass::Warrior);
+    let diff = hero.exp_gain(20, 1., &Progression::default());
+    let point = hero.all_state_point().unwrap();
+    hero.allocate(Allocation {
+        str: point,
+        ..Default::default()
+    });
+
+    let loaded = BaseStates::from_save(
+        HeroClass::Warrior,
+        &hero.to_save(HeroClass::Warrior),
+        &Progression::default(),
+    );
+    assert_eq!(loaded.lv(), hero.lv());
+    assert_eq!(loaded.allocated(), hero.allocated());
+    assert_eq!(loaded.str(), hero.str());
+
+    assert_eq!(hero.respec().unwrap(), diff);
+    assert_eq!(hero.state_point(), diff);
+    assert_eq!(hero.allocated(), Allocation::default());
+    assert!(hero.respec().is_err());
+}
+
+#[test]
+fn test_class_growth() {
+    let mut warrior = BaseStates::new_hero(HeroClass::Warrior);
+    let mut rogue = BaseStates::new_hero(HeroClass::Rogue);
+    warrior.exp_gain(20, 1., &Progression::default());
+    rogue.exp_gain(20, 1., &Progression::default());
+
+    assert_eq!(warrior.lv(), rogue.lv());
+    assert!(warrior.str() > rogue.str());
+    assert!(rogue.agi() > warrior.agi());
+}
+
+// each class keeps its own save, a fainted one does not touch the others
+#[test]
+fn test_hero_save_per_class() {
+    let mut warrior = BaseStates::new_hero(HeroClass::Warrior);
+    let rogue = BaseStates::new_hero(HeroClass::Rogue);
+    warrior.exp_gain(20, 1., &Progression::default());
+
+    let mut data = crate::model::player::PlayerData::default();
+    data.set_hero(warrior.to_save(HeroClass::Warrior));
+    data.set_hero(rogue.to_save(HeroClass::Rogue));
+    data.set_hero(rogue.to_save(HeroClass::Rogue));
+    assert_eq!(data.heroes.len(), 2);
+
+    data.remove_hero(&HeroClass::Rogue.to_string());
+    assert!(data.hero(&HeroClass::Rogue.to_string()).is_none());
+    let save = data.hero(&HeroClass::Warrior.to_string()).unwrap();
+    assert_eq!(
+        BaseStates::from_save(HeroClass::Warrior, save, &Progression::default()).lv(),
+        warrior.lv()
+    );
+}
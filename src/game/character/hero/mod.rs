@@ -1,12 +1,13 @@
 use bevy::prelude::*;
 
 mod sprite;
-mod status;
 
 use sprite::*;
-use status::*;
 
+use crate::game::meta::MetaProgress;
+use crate::game::saving::Profile;
 use crate::game::Player;
+use crate::model::progression::Progression;
 use crate::state::{Screen, SimulationState};
 
 use super::component::*;
@@ -14,13 +15,28 @@ use super::component::*;
 #[derive(Event)]
 pub struct SpawnHeroEvent();
 
+// Picked on the class selection screen before the run starts
+#[derive(Resource, Debug, Clone, Copy, Deref, Reflect)]
+pub struct SelectedClass(pub HeroClass);
+
+impl Default for SelectedClass {
+    fn default() -> Self {
+        Self(HeroClass::Warrior)
+    }
+}
+
 pub struct HeroPlugin;
 
 impl Plugin for HeroPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnHeroEvent>()
-            .add_systems(OnEnter(Screen::Playing), (spawn_hero, spawn_decending_hero))
-            .add_systems(Update, (update_player_level, spawn_hero_sprite))
+            .init_resource::<SelectedClass>()
+            .add_systems(OnEnter(Screen::Playing), spawn_hero)
+            .add_systems(
+                Update,
+                spawn_decending_hero.run_if(in_state(Screen::Playing)),
+            )
+            .add_systems(Update, spawn_hero_sprite)
             .add_systems(
                 Update,
                 (
@@ -33,20 +49,39 @@ impl Plugin for HeroPlugin {
             )
             .add_systems(Update, (spawn_hero_sprite_battle, despawn_decending))
             .add_systems(OnExit(Screen::GameOver), despawn_hero)
-            .register_type::<HeroClass>();
+            .register_type::<HeroClass>()
+            .register_type::<SelectedClass>();
     }
 }
 
-pub fn spawn_hero(mut commands: Commands, mut player_query: Query<Entity, With<Player>>) {
+pub fn spawn_hero(
+    mut commands: Commands,
+    mut player_query: Query<Entity, With<Player>>,
+    profile: Option<Res<Profile>>,
+    selected_class: Res<SelectedClass>,
+    progression: Res<Progression>,
+    meta: Res<MetaProgress>,
+) {
     // for ev in spawn_event.read() {
     let player_entity = player_query.get_single_mut().expect("No Player is spawned");
+    let class = selected_class.0;
+
+    // each class carries on from its own save
+    let base = profile
+        .as_ref()
+        .and_then(|profile| profile.hero(&class.to_string()))
+        .map(|save| BaseStates::from_save(class, save, &progression))
+        .unwrap_or_else(|| BaseStates::new_hero(class));
+    let mut turns = Turns::default();
+    turns.plus(meta.extra_turns());
+
     let hero_id = commands
         .spawn((
             Name::new("Hero"),
             Hero {},
-            HeroClass::Warrior,
-            Turns::default(),
-            BaseStates::new_hero(),
+            class,
+            turns,
+            base,
             Bag::default(),
         ))
         .id();
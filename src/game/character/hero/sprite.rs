@@ -10,13 +10,14 @@ use crate::{
         character::component::{
             Hero, HeroClass, HeroSprite, HeroSpriteBundle, HeroSpriteNodeBundle,
         },
-        CurrentMovement, DecendingHero, FacingSide, Player, PLAYER_START_POSITION,
+        map::{preload::Location, ChangeMapEvent},
+        CurrentMovement, DecendingHero, FacingSide, Player,
     },
     preload::sprites::{HeroAction, HeroActionHandles, HeroActionTextureAtLasHandles},
     state::SimulationState,
 };
 
-use super::{BaseStates, SpawnHeroEvent};
+use super::{BaseStates, SelectedClass, SpawnHeroEvent};
 
 pub fn spawn_scale_w_sprite(
     mut player_query: Query<(&mut Transform, Entity), With<Player>>,
@@ -38,42 +39,50 @@ pub fn spawn_scale_w_sprite(
     }
 }
 
+// The hero drops onto the spawn point a run starts at
 pub fn spawn_decending_hero(
     mut commands: Commands,
     hero_sprite: Res<HeroActionHandles>,
     hero_texture_atlas: Res<HeroActionTextureAtLasHandles>,
     window_query: Query<&Window, With<PrimaryWindow>>,
+    selected_class: Res<SelectedClass>,
+    location_query: Query<&Transform, With<Location>>,
+    mut change_map_event: EventReader<ChangeMapEvent>,
 ) {
-    let height = window_query.single().height();
-    info!("Spawn decending, window heght: {}", height);
+    for event in change_map_event.read() {
+        if !event.run_start {
+            continue;
+        }
+        let Ok(spawn_point) = location_query.get(event.spawn_point) else {
+            error!("Start spawn point {:?} is gone", event.location.name());
+            continue;
+        };
+        let start = spawn_point.translation;
+        let height = window_query.single().height();
+        info!("Spawn decending, window heght: {}", height);
 
-    commands.spawn((
-        Name::new("Decending Hero"),
-        DecendingHero,
-        SpriteBundle {
-            texture: hero_sprite[&HeroAction::Decending].clone(),
-            transform: Transform::from_xyz(PLAYER_START_POSITION.x, height - 60., 999.),
-            ..default()
-        },
-        TextureAtlas {
-            layout: hero_texture_atlas[&HeroAction::Decending].clone(),
-            ..default()
-        },
-        Sliding::new(
-            Vec2 {
-                x: PLAYER_START_POSITION.x,
-                y: PLAYER_START_POSITION.y,
+        commands.spawn((
+            Name::new("Decending Hero"),
+            DecendingHero,
+            SpriteBundle {
+                sprite: Sprite {
+                    color: selected_class.tint(),
+                    ..default()
+                },
+                texture: hero_sprite[&HeroAction::Decending].clone(),
+                // from the top of the screen while the camera sits on the spawn point
+                transform: Transform::from_xyz(start.x, start.y + height / 2., 999.),
+                ..default()
             },
-            false,
-            1.,
-            Vec2 {
-                x: PLAYER_START_POSITION.x,
-                y: 0.,
+            TextureAtlas {
+                layout: hero_texture_atlas[&HeroAction::Decending].clone(),
+                ..default()
             },
-        ),
-        AnimationIndices::new(3, true),
-        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-    ));
+            Sliding::new(start.truncate(), false, 1., Vec2 { x: start.x, y: 0. }),
+            AnimationIndices::new(3, true),
+            AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+        ));
+    }
 }
 
 pub fn despawn_decending(
@@ -97,7 +106,7 @@ pub fn spawn_hero_sprite(
     mut player_query: Query<Entity, With<Player>>,
     hero_sprite: Res<HeroActionHandles>,
     hero_texture_atlas: Res<HeroActionTextureAtLasHandles>,
-
+    selected_class: Res<SelectedClass>,
     mut spawn_event: EventReader<SpawnHeroEvent>,
 ) {
     for _ in spawn_event.read() {
@@ -108,7 +117,7 @@ pub fn spawn_hero_sprite(
             .spawn(build_hero_sprite(
                 hero_texture_atlas[&HeroAction::Idle].clone(),
                 hero_sprite[&HeroAction::Idle].clone(),
-                HeroClass::Paladin,
+                selected_class.0,
                 AnimationIndices {
                     first: 0,
                     last: 9,
@@ -272,6 +281,10 @@ fn build_hero_sprite(
         name: Name::new("Sprite"),
         hero_sripte: HeroSprite,
         sprite_sheet_bundle: SpriteBundle {
+            sprite: Sprite {
+                color: hero_class.tint(),
+                ..default()
+            },
             texture: image_handle.clone(),
             transform: Transform::from_xyz(0., 20., 5.),
             ..default()
@@ -298,7 +311,7 @@ fn build_hero_sprite_node(
         hero_sripte: BattleHeroSprite,
         sprite_sheet_bundle: ImageBundle {
             image: UiImage {
-                color: Color::WHITE,
+                color: hero_class.tint(),
                 texture: image_handle.clone(),
                 flip_x: false,
                 flip_y: false,
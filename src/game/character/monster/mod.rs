@@ -4,9 +4,11 @@ use bevy_rapier2d::pipeline::CollisionEvent;
 use rand::Rng;
 
 use crate::animation::component::{AnimationIndices, AnimationTimer};
+use crate::game::battle::BossScript;
 use crate::game::battle_scene::BattleMonster;
 use crate::game::preload::{SensorBundle, Weather};
 use crate::game::PlayerEnv;
+use crate::model::boss::BossBook;
 use crate::model::monster::MonsterData;
 use crate::model::sub::Element;
 use crate::model::Id;
@@ -18,7 +20,7 @@ use crate::preload::sprites::{MonsterAsset, MonsterHandles, MonsterTextureAtLasH
 
 use super::component::{
     BaseStates, BattleMonsterSprite, Boss, BossRespawnBlinker, BossRespawnTimer, DespawnBossFlag,
-    HealthPoint, MonsterSprite, MonsterSpriteBundle, MonsterSpriteNodeBundle, MonsterType,
+    MonsterSprite, MonsterSpriteBundle, MonsterSpriteNodeBundle, MonsterType,
 };
 
 use bevy_ecs_ldtk::{
@@ -94,10 +96,9 @@ impl From<&EntityInstance> for MonsterBundle {
             .expect("expected entity to have non-nullable name string field")
             .clone();
 
-        let id = entity_instance
+        let id = *entity_instance
             .get_int_field("Id")
-            .expect("expected entity to have non-nullable id int field")
-            .clone() as u32;
+            .expect("expected entity to have non-nullable id int field") as u32;
 
         MonsterBundle {
             id: Id::new(id),
@@ -120,25 +121,39 @@ pub fn spawn_monster(
         };
         let mut rng = rand::thread_rng();
 
-        let monster_id = ev.monster_id.choose(&mut rand::thread_rng()).unwrap_or(&0);
+        for index in 0..ev.group_size.max(1) {
+            let monster_id = ev.monster_id.choose(&mut rand::thread_rng()).unwrap_or(&0);
 
-        let (_, monster) = monster
-            .iter()
-            .find(|(_, data)| data.id == *monster_id as u32)
-            .expect(&format!("No Monster Found: ID {}", monster_id));
+            let (_, monster) = monster
+                .iter()
+                .find(|(_, data)| data.id == *monster_id as u32)
+                .unwrap_or_else(|| panic!("No Monster Found: ID {}", monster_id));
 
-        let mut new_monster = monster.clone();
+            let mut new_monster = monster.clone();
 
-        new_monster.element = match player_env.0 {
-            Some(Weather::Sunny) => Element::Fire,
-            Some(Weather::Rainy) => Element::Water,
-            Some(Weather::Muddy) => Element::Earth,
-            None => monster.element.clone(),
-        };
+            new_monster.element = match player_env.0 {
+                Some(Weather::Sunny) => Element::Fire,
+                Some(Weather::Rainy) => Element::Water,
+                Some(Weather::Muddy) => Element::Earth,
+                None => monster.element.clone(),
+            };
 
-        build_monster(ev.lv + rng.gen_range(0..5), &new_monster, &mut commands);
+            // tell the monsters apart in the terminal once there are more than one
+            let name = if ev.group_size > 1 {
+                format!("Monster {}", (b'A' + index as u8) as char)
+            } else {
+                "Monster".to_string()
+            };
+
+            build_monster(
+                ev.lv + rng.gen_range(0..5),
+                &new_monster,
+                name,
+                &mut commands,
+            );
+        }
 
-        info!("Monster Spawned");
+        info!("{} Monster Spawned", ev.group_size);
     }
 }
 
@@ -146,23 +161,31 @@ pub fn insert_boss_data(
     mut commands: Commands,
     monster_query: Query<(Entity, &Id), Added<Boss>>,
     monster: Res<Assets<MonsterData>>,
+    boss_books: Res<Assets<BossBook>>,
 ) {
     for (entity, monster_id) in monster_query.iter() {
         let (_, monster) = monster
             .iter()
             .find(|(_, data)| data.id == monster_id.get())
-            .expect(&format!("No Monster Found: ID {}", monster_id.get()));
+            .unwrap_or_else(|| panic!("No Monster Found: ID {}", monster_id.get()));
 
         commands.entity(entity).insert(monster.clone());
+
+        // without a script the boss fights like any other monster
+        if let Some(boss) = boss_books
+            .iter()
+            .find_map(|(_, book)| book.get(monster_id.get()))
+        {
+            commands.entity(entity).insert(BossScript::new(boss));
+        }
     }
 }
 
-fn build_monster(lv: u32, monster: &MonsterData, commands: &mut Commands) -> Entity {
+fn build_monster(lv: u32, monster: &MonsterData, name: String, commands: &mut Commands) -> Entity {
     commands
         .spawn((
-            Name::new("Monster".to_string()),
+            Name::new(name),
             Monster {},
-            HealthPoint::monster(),
             BaseStates::new_monster(lv),
             MonsterType::Monster1,
             monster.clone(),
@@ -295,11 +318,9 @@ pub fn flag_despawn_boss(
 ) {
     for ev in battle_event.read() {
         if ev.is_player_victory {
-            let Ok(entity) = boss_query.get(ev.monster_entity) else {
-                continue;
-            };
-
-            commands.entity(entity).insert(DespawnBossFlag {});
+            for entity in boss_query.iter_many(&ev.monsters) {
+                commands.entity(entity).insert(DespawnBossFlag {});
+            }
         }
     }
 }
@@ -318,10 +339,15 @@ pub fn despawn_boss(
 
 pub fn respawn_boss(
     mut commands: Commands,
-    boss_query: Query<Entity, (With<Boss>, With<Monster>)>,
+    mut boss_query: Query<(Entity, Option<&mut BossScript>), (With<Boss>, With<Monster>)>,
 ) {
-    for boss in boss_query.iter() {
+    for (boss, script) in boss_query.iter_mut() {
         info!("Respawning a Boss");
+        // the next attempt starts from the first phase again
+        if let Some(mut script) = script {
+            script.reset();
+        }
+
         commands
             .entity(boss)
             .remove::<Monster>()
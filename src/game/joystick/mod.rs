@@ -1,3 +1,6 @@
+// Parts of the joystick the game does not use yet may trigger this lint undesirably.
+#![allow(dead_code)]
+
 use std::hash::Hash;
 
 use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
@@ -9,8 +12,8 @@ mod components;
 mod systems;
 mod utils;
 
-pub use action::{NoAction, VirtualJoystickAction};
-pub use behavior::{JoystickFixed, VirtualJoystickBehavior};
+pub use action::VirtualJoystickAction;
+pub use behavior::VirtualJoystickBehavior;
 pub use bundles::VirtualJoystickBundle;
 pub use components::{
     VirtualJoystickNode, VirtualJoystickState, VirtualJoystickUIBackground, VirtualJoystickUIKnob,
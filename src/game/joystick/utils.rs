@@ -20,7 +20,7 @@ use super::{
 /// * `joystick_node_style`: bevy Style for joystick
 ///
 /// Usage:
-/// ```no_run
+/// ```ignore
 /// create_joystick(
 ///     cmd,
 ///     asset_server.load("Knob.png"),
@@ -48,7 +48,7 @@ use super::{
 /// ```
 ///
 /// if you not want use this function helper, you need do that
-/// ```no_run
+/// ```ignore
 /// cmd.spawn((
 ///     VirtualJoystickBundle::new(VirtualJoystickNode {
 ///         dead_zone: 0.,
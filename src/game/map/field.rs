@@ -1,12 +1,12 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_ecs_ldtk::LevelSelection;
-use rand::random;
+use rand::{random, Rng};
 
 use crate::camera::game::camera_fit_inside_current_level;
 use crate::state::{Screen, SimulationState};
 
 use crate::game::character::component::{BaseStates, Hero};
-use crate::game::{CurrentMovement, Encounter, Movement, Player};
+use crate::game::{CurrentMovement, Encounter, EquipmentBelt, Movement, Player, Repellent};
 
 use super::preload::{CollisionDetection, Field, ShapeWrapper};
 
@@ -14,6 +14,7 @@ use super::preload::{CollisionDetection, Field, ShapeWrapper};
 pub struct EncounterEvent {
     pub monster_id: Vec<i32>,
     pub lv: u32,
+    pub group_size: u32,
 }
 
 // FieldIndicator contain the location of each field
@@ -23,15 +24,6 @@ pub struct FieldIndicator {
     level: u32,
 }
 
-impl FieldIndicator {
-    fn build_test(lv: u32) -> Self {
-        Self {
-            location: Vec2::ZERO,
-            level: lv,
-        }
-    }
-}
-
 pub struct FieldPlugin;
 
 impl Plugin for FieldPlugin {
@@ -69,7 +61,7 @@ impl Plugin for FieldPlugin {
 //         let mesh = commands
 //             .spawn(MaterialMesh2dBundle {
 //                 mesh: meshes.add(shape.0.clone()).into(),
-//                 material: materials.add(Color::rgb(0.5, 0.2, 0.2)),
+//                 material: materials.add(Color::srgb(0.5, 0.2, 0.2)),
 //                 ..default()
 //             })
 //             .id();
@@ -98,7 +90,7 @@ pub fn spawn_mesh(
         gizmos.rect_2d(
             transform.translation.truncate(),
             0.,
-            shape.0.clone(),
+            shape.0,
             Color::linear_rgb(1.0, 0., 0.),
         );
     }
@@ -125,7 +117,6 @@ fn spawn_zone_lv(mut commands: Commands, area_query: Query<(&Transform, &Field),
                             font_size: 20_f32,
                             ..default()
                         },
-                        ..Default::default()
                     }],
                     ..default()
                 },
@@ -217,11 +208,24 @@ pub fn tick_encounter_timer(
 
 pub fn encounter_monster(
     mut zone_query: Query<(&Field, &CollisionDetection), With<Field>>,
-    mut player_query: Query<(&CurrentMovement, &mut Encounter), With<Player>>,
+    mut player_query: Query<
+        (
+            &CurrentMovement,
+            &mut Encounter,
+            Option<&Repellent>,
+            &EquipmentBelt,
+        ),
+        With<Player>,
+    >,
     mut encounter_event_writer: EventWriter<EncounterEvent>,
     time: Res<Time>,
 ) {
-    let (player_movemnt, mut encounter) = player_query.get_single_mut().expect("No Player Spawned");
+    let (player_movemnt, mut encounter, repellent, belt) =
+        player_query.get_single_mut().expect("No Player Spawned");
+
+    if repellent.is_some_and(|repellent| repellent.is_active()) {
+        return;
+    }
 
     let player_field = encounter.field;
 
@@ -237,6 +241,7 @@ pub fn encounter_monster(
                     encounter_event_writer.send(EncounterEvent {
                         monster_id: field.monster_id.clone(),
                         lv: field.lv(),
+                        group_size: rand::thread_rng().gen_range(1..=field.max_group.max(1)),
                     });
                     info!("Encounter a Monster");
                     encounter.reset();
@@ -246,13 +251,15 @@ pub fn encounter_monster(
                 encounter.immune = false;
             }
 
-            encounter.percentage += 50. * time.delta_seconds();
+            encounter.percentage += 50. * belt.encounter_rate() * time.delta_seconds();
             // info!("Encounter percentage: {}", encounter.percentage);
         }
     }
 }
 
 // Todo: Later separate the get cloest three to a separate function to reduce computational cost
+// switched off in the plugin for now, kept for when the indicators come back
+#[allow(dead_code)]
 pub fn constrict_level_indicator(
     hero_query: Query<&BaseStates, With<Hero>>,
     center_query: Query<&Transform, With<Camera>>,
@@ -350,15 +357,16 @@ pub fn despawn_danger_indicator_change_lv(
 
 #[test]
 fn test_pick_field_closest_to_hero_lv() {
-    let fields: Vec<(Transform, FieldIndicator)> = vec![
-        (Transform::default(), FieldIndicator::build_test(1)),
-        (Transform::default(), FieldIndicator::build_test(2)),
-        (Transform::default(), FieldIndicator::build_test(3)),
-        (Transform::default(), FieldIndicator::build_test(4)),
-        (Transform::default(), FieldIndicator::build_test(9)),
-        (Transform::default(), FieldIndicator::build_test(16)),
-        (Transform::default(), FieldIndicator::build_test(24)),
-    ];
+    let fields: Vec<(Transform, FieldIndicator)> = [1, 2, 3, 4, 9, 16, 24]
+        .into_iter()
+        .map(|level| {
+            let indicator = FieldIndicator {
+                location: Vec2::ZERO,
+                level,
+            };
+            (Transform::default(), indicator)
+        })
+        .collect();
 
     let player_lv: u32 = 5;
 
@@ -368,7 +376,7 @@ fn test_pick_field_closest_to_hero_lv() {
         .collect::<Vec<_>>();
 
     assert_ne!(
-        filter.iter().next().unwrap().1.level,
+        filter.first().unwrap().1.level,
         1,
         "Field filtering not working"
     );
@@ -378,7 +386,7 @@ fn test_pick_field_closest_to_hero_lv() {
     });
 
     assert_eq!(
-        filter.iter().next().unwrap().1.level,
+        filter.first().unwrap().1.level,
         9,
         "Field sorting not working"
     );
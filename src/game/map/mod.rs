@@ -10,15 +10,18 @@ use bevy_rapier2d::{
 };
 use zone::ZonePlugin;
 
-use crate::preload::map::{MapAsset, MapHandles};
+use crate::model::world::{LevelData, ProjectData, WorldManifest};
+use crate::preload::{data::WorldPreloadHandler, map::MapHandles};
 
 use crate::state::Screen;
 pub mod component;
 pub mod field;
 pub mod preload;
+pub mod transition;
 pub mod zone;
 
 use field::FieldPlugin;
+use transition::{MapTransition, PortCooldown, TransitionPlugin};
 
 use super::Player;
 use preload::{CollisionDetection, LdtkPreloadPlugin, Location, Map, Port, Wall};
@@ -26,9 +29,17 @@ use preload::{CollisionDetection, LdtkPreloadPlugin, Location, Map, Port, Wall};
 #[derive(Event)]
 pub struct SpawnMapEvent {}
 
+// Name in the world manifest of the level the hero is in
+#[derive(Resource, Debug, Default, Clone)]
+pub struct CurrentLevel(pub String);
+
 #[derive(Event)]
 pub struct ChangeMapEvent {
     pub location: Location,
+    // the spawn point the hero arrives at
+    pub spawn_point: Entity,
+    // the hero descends onto the start of a run instead of walking in
+    pub run_start: bool,
 }
 
 pub struct MapPlugin;
@@ -36,8 +47,9 @@ impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnMapEvent>()
             .add_event::<ChangeMapEvent>()
-            .add_plugins((FieldPlugin, LdtkPreloadPlugin, ZonePlugin))
+            .add_plugins((FieldPlugin, LdtkPreloadPlugin, TransitionPlugin, ZonePlugin))
             .insert_resource(LevelSelection::index(0))
+            .init_resource::<CurrentLevel>()
             .add_systems(OnEnter(Screen::Playing), spawn_map)
             .add_systems(
                 Update,
@@ -53,63 +65,131 @@ impl Plugin for MapPlugin {
 
 pub fn spawn_map(
     mut commands: Commands,
-    map: Query<&Map, With<Map>>,
+    mut map_query: Query<(&mut Handle<LdtkProject>, &mut LevelSet), With<Map>>,
     ldtk_map: Res<MapHandles>,
+    world_handle: Res<WorldPreloadHandler>,
+    worlds: Res<Assets<WorldManifest>>,
+    mut current_level: ResMut<CurrentLevel>,
     mut next_map: ResMut<LevelSelection>,
 ) {
-    // commands.spawn(
-    //     SpriteBundle {
-    //     sprite: Sprite {
-    //         color: Color::rgb(0.25, 0.25, 0.75),
-    //         custom_size: Some(Vec2::new(500.0, 500.0)),
-    //         ..default()
-    //     },
-    //     transform: Transform::from_translation(Vec3::new(0., 0., 0.)),
-    //     ..default()
-    // }
-    // );
-    // for _ in spawn_map_event_reader.read() {
-    if map.is_empty() {
-        next_map.set_if_neq(LevelSelection::index(0));
-        commands.spawn((
-            Name::new("LTDK"),
-            Map,
-            LdtkWorldBundle {
-                ldtk_handle: ldtk_map[&MapAsset::Forbidden].clone(),
-                transform: Transform::from_translation(Vec3::new(0., 0., 0.)),
-                level_set: LevelSet::from_iids([
-                    "24c90fe0-d7b0-11ee-bf4e-a90834c55fc0".to_string(),
-                    "c4f585e0-d7b0-11ee-88a4-6b9f62256f9a".to_string(),
-                ]),
-                ..Default::default()
-            },
-        ));
-    } else {
-        info!("With New Map go to Level 0");
-        next_map.set_if_neq(LevelSelection::index(0));
-    }
+    let Some(world) = worlds.get(world_handle.id()) else {
+        error!("World manifest is not loaded");
+        return;
+    };
+    let Some((project, level)) = world.start_level() else {
+        error!("World manifest has no start level");
+        return;
+    };
 
-    // }
+    info!("Run starts in {}", level.display_name);
+    enter_level(
+        &mut commands,
+        &mut map_query,
+        &ldtk_map,
+        project,
+        level,
+        &mut next_map,
+    );
+    current_level.0 = level.name.clone();
+    // the screen stays dark until the hero has a spawn point to land on
+    commands.insert_resource(MapTransition::run_start(
+        level,
+        Location::new(world.start_location.as_str()),
+    ));
 }
 
+// Touching a port only starts a transition, the level switch happens once the screen is dark
 pub fn spawn_map_on_touch(
+    mut commands: Commands,
     port_query: Query<(&Port, &CollisionDetection), Changed<CollisionDetection>>,
-    mut next_map: ResMut<LevelSelection>,
-    mut change_map_event: EventWriter<ChangeMapEvent>,
+    world_handle: Option<Res<WorldPreloadHandler>>,
+    worlds: Res<Assets<WorldManifest>>,
+    current_level: Res<CurrentLevel>,
+    transition: Option<Res<MapTransition>>,
+    cooldown: Res<PortCooldown>,
 ) {
+    if transition.is_some() || !cooldown.finished() {
+        return;
+    }
+    let Some(world) = world_handle.and_then(|handle| worlds.get(handle.id())) else {
+        return;
+    };
+
     for (port, coliision) in port_query.iter() {
-        if coliision.0 {
-            info!(
-                "Collide With Port to Map {}, To location: {:?}",
-                port.map,
-                port.location.clone()
+        if !coliision.0 {
+            continue;
+        }
+        if port.level.is_empty() {
+            error!("Port has no Level to lead to");
+            continue;
+        }
+        let Some((_, level)) = world.level(&port.level) else {
+            error!(
+                "Port leads to {} which is not in the world manifest",
+                port.level
+            );
+            continue;
+        };
+        if port.location.is_empty() {
+            error!("Port to {} has no Location to arrive at", level.name);
+            continue;
+        }
+        // the manifest decides where a level may lead, stray ports stay shut
+        if !world.is_connected(&current_level.0, &level.name) {
+            error!(
+                "{} is not listed as a connection of {}",
+                level.name, current_level.0
             );
-            next_map.set_if_neq(LevelSelection::index(port.map));
-            change_map_event.send(ChangeMapEvent {
-                location: port.location.clone(),
-            });
+            continue;
+        }
+
+        info!(
+            "Collide With Port to {} (recommended Lv {}), To location: {}",
+            level.display_name,
+            level.recommended_lv,
+            port.location.name()
+        );
+        commands.insert_resource(MapTransition::new(
+            level.name.clone(),
+            port.location.clone(),
+        ));
+        break;
+    }
+}
+
+// Points the one LDtk world at the level, swapping the project when the level lives in another file
+fn enter_level(
+    commands: &mut Commands,
+    map_query: &mut Query<(&mut Handle<LdtkProject>, &mut LevelSet), With<Map>>,
+    ldtk_map: &MapHandles,
+    project: &ProjectData,
+    level: &LevelData,
+    next_map: &mut ResMut<LevelSelection>,
+) {
+    let Some(handle) = ldtk_map.get(&project.path) else {
+        error!("{} is not loaded", project.path);
+        return;
+    };
+    let level_set = LevelSet::from_iids(project.levels.iter().map(|level| level.iid.clone()));
+
+    if let Ok((mut ldtk_handle, mut current_set)) = map_query.get_single_mut() {
+        if *ldtk_handle != *handle {
+            *ldtk_handle = handle.clone();
+            *current_set = level_set;
         }
+    } else {
+        commands.spawn((
+            Name::new("LTDK"),
+            Map,
+            LdtkWorldBundle {
+                ldtk_handle: handle.clone(),
+                transform: Transform::from_translation(Vec3::new(0., 0., 0.)),
+                level_set,
+                ..Default::default()
+            },
+        ));
     }
+    next_map.set_if_neq(LevelSelection::iid(level.iid.clone()));
 }
 
 /// Spawns heron collisions for the walls of a level
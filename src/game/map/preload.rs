@@ -10,6 +10,8 @@ use bevy_rapier2d::{
 };
 use rand::seq::SliceRandom;
 
+use crate::game::player::FacingSide;
+
 pub struct LdtkPreloadPlugin;
 
 impl Plugin for LdtkPreloadPlugin {
@@ -33,10 +35,10 @@ pub struct Zone {
 impl From<&EntityInstance> for Zone {
     fn from(entity_instance: &EntityInstance) -> Zone {
         Self {
-            id: entity_instance
+            id: *entity_instance
                 .get_int_field("Id")
                 .expect("expected entity to have non-nullable name string field")
-                .clone() as u32,
+                as u32,
         }
     }
 }
@@ -121,11 +123,15 @@ impl From<&EntityInstance> for ShapeWrapper {
 #[derive(Component, Default, Reflect, PartialEq, Eq)]
 pub struct CollisionDetection(pub bool);
 
+pub const MAX_MONSTER_GROUP: u32 = 3;
+
 #[derive(Component, Default, Reflect)]
 pub struct Field {
     pub level: u32,
     pub monster_id: Vec<i32>,
     pub zone_id: u32,
+    // how many monsters can jump out at once
+    pub max_group: u32,
 }
 
 impl Field {
@@ -139,16 +145,25 @@ impl Field {
             .collect::<Vec<i32>>())
         .to_vec();
 
+        let level = *entity_instance
+            .get_int_field("Level")
+            .expect("expected entity to have non-nullable level int field")
+            as u32;
+
         Self {
-            level: *entity_instance
-                .get_int_field("Level")
-                .expect("expected entity to have non-nullable level int field")
-                as u32,
+            level,
             monster_id: monster_ids,
             zone_id: *entity_instance
                 .get_int_field("ZoneId")
                 .expect("expected entity to have non-nullable level int field")
                 as u32,
+            // without a GroupSize on the field, deeper fields get bigger groups
+            max_group: entity_instance
+                .get_maybe_int_field("GroupSize")
+                .ok()
+                .and_then(|size| size.map(|size| size.max(0) as u32))
+                .unwrap_or(1 + level / 50)
+                .clamp(1, MAX_MONSTER_GROUP),
         }
     }
 
@@ -205,25 +220,20 @@ pub struct PortBundle {
 
 #[derive(Component, Default)]
 pub struct Port {
-    pub map: usize,
+    // level name or iid from the world manifest
+    pub level: String,
     pub location: Location,
 }
 
 impl Port {
     pub fn from_field(entity_instance: &EntityInstance) -> Self {
         Self {
-            map: *entity_instance
-                .get_int_field("MapId")
-                .expect("expected entity to have non-nullable MapID int field")
-                as usize,
-            location: Location(
-                entity_instance
-                    .get_string_field("Location")
-                    .expect("expected entity to have non-nullable location String field")
-                    .chars()
-                    .next()
-                    .expect("expected entity to have non-nullable non empty string field"),
-            ),
+            // a missing level is reported when the port is touched
+            level: match entity_instance.get_maybe_string_field("Level") {
+                Ok(Some(level)) => level.trim().to_string(),
+                _ => String::new(),
+            },
+            location: Location::from_field(entity_instance),
         }
     }
 }
@@ -245,37 +255,63 @@ pub struct SpawnLocationBundle {
     name: Name,
     #[with(Location::from_field)]
     location: Location,
+    // the way the hero looks after arriving here
+    #[with(facing_from_field)]
+    facing: FacingSide,
     #[grid_coords]
     grid_coords: GridCoords,
 }
 
-fn name_from_location(_: &EntityInstance) -> Name {
-    Name::new("Location")
+fn name_from_location(entity_instance: &EntityInstance) -> Name {
+    Name::new(format!(
+        "Location {}",
+        Location::from_field(entity_instance).name()
+    ))
 }
 
-#[derive(Default, Component, Clone, Debug)]
-pub struct Location(char);
-
-impl Location {
-    pub fn new(location: char) -> Self {
-        Self(location)
+// Facing is optional, anything but Left keeps the default
+fn facing_from_field(entity_instance: &EntityInstance) -> FacingSide {
+    match entity_instance.get_maybe_string_field("Facing") {
+        Ok(Some(facing)) => Location::parse_facing(facing),
+        _ => FacingSide::default(),
     }
 }
 
+// Named spawn point, ports refer to it by name
+#[derive(Default, Component, Clone, Debug, PartialEq, Eq)]
+pub struct Location(String);
+
 impl Location {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into().trim().to_string())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // a missing or blank field gives an empty location, which never matches
     pub fn from_field(entity_instance: &EntityInstance) -> Self {
-        Self(
-            entity_instance
-                .get_string_field("Location")
-                .expect("expected entity to have non-nullable location string field")
-                .chars()
-                .next()
-                .unwrap(),
-        )
+        match entity_instance.get_maybe_string_field("Location") {
+            Ok(Some(name)) => Self::new(name.as_str()),
+            _ => Self::default(),
+        }
+    }
+
+    pub fn parse_facing(facing: &str) -> FacingSide {
+        if facing.trim().eq_ignore_ascii_case("left") {
+            FacingSide::Left
+        } else {
+            FacingSide::Right
+        }
     }
 
     pub fn equal(&self, location: &Location) -> bool {
-        self.0 == location.0
+        !self.is_empty() && self.0 == location.0
     }
 }
 
@@ -303,3 +339,15 @@ impl Weather {
         random_item.clone()
     }
 }
+
+#[test]
+fn test_location_names() {
+    assert!(Location::new("Gate").equal(&Location::new(" Gate ")));
+    assert!(!Location::new("Gate").equal(&Location::new("gate")));
+    // blank names come from unfilled LDtk fields and never resolve
+    assert!(!Location::new("").equal(&Location::default()));
+
+    assert_eq!(Location::parse_facing("Left"), FacingSide::Left);
+    assert_eq!(Location::parse_facing(" left"), FacingSide::Left);
+    assert_eq!(Location::parse_facing("Up"), FacingSide::Right);
+}
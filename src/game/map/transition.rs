@@ -0,0 +1,268 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    model::world::{LevelData, WorldManifest},
+    preload::{data::WorldPreloadHandler, map::MapHandles},
+    state::Screen,
+};
+
+use super::{
+    enter_level,
+    preload::{Location, Map},
+    ChangeMapEvent, CurrentLevel,
+};
+
+const FADE_SECS: f32 = 0.3;
+// ports stay shut this long after arriving so the hero can step off
+const PORT_COOLDOWN_SECS: f32 = 1.0;
+// how long to wait for the destination level to spawn the spawn point
+const ARRIVAL_TIMEOUT_SECS: f32 = 2.0;
+
+pub struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PortCooldown>()
+            .add_systems(
+                Update,
+                (tick_port_cooldown, advance_transition, fade_transition)
+                    .chain()
+                    .run_if(in_state(Screen::Playing)),
+            )
+            .add_systems(OnExit(Screen::Playing), clear_transition);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPhase {
+    FadeOut,
+    // waiting for the level to spawn
+    Loading,
+    FadeIn,
+}
+
+// A port trip in progress, no other port fires until it is over
+#[derive(Resource, Debug)]
+pub struct MapTransition {
+    // level name from the world manifest
+    pub level: String,
+    pub location: Location,
+    pub phase: TransitionPhase,
+    iid: String,
+    timer: Timer,
+    run_start: bool,
+}
+
+impl MapTransition {
+    pub fn new(level: impl Into<String>, location: Location) -> Self {
+        Self {
+            level: level.into(),
+            location,
+            phase: TransitionPhase::FadeOut,
+            iid: String::new(),
+            timer: Timer::from_seconds(FADE_SECS, TimerMode::Once),
+            run_start: false,
+        }
+    }
+
+    // a run starts already dark, waiting for its start level to spawn
+    pub fn run_start(level: &LevelData, location: Location) -> Self {
+        let mut transition = Self::new(level.name.clone(), location);
+        transition.iid = level.iid.clone();
+        transition.run_start = true;
+        transition.next(TransitionPhase::Loading);
+        transition
+    }
+
+    fn next(&mut self, phase: TransitionPhase) {
+        let secs = match phase {
+            TransitionPhase::Loading => ARRIVAL_TIMEOUT_SECS,
+            _ => FADE_SECS,
+        };
+        self.phase = phase;
+        self.timer = Timer::from_seconds(secs, TimerMode::Once);
+    }
+
+    // how dark the screen is, 1 is fully black
+    pub fn alpha(&self) -> f32 {
+        match self.phase {
+            TransitionPhase::FadeOut => self.timer.fraction(),
+            TransitionPhase::Loading => 1.,
+            TransitionPhase::FadeIn => 1. - self.timer.fraction(),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Deref, DerefMut)]
+pub struct PortCooldown(pub Timer);
+
+impl Default for PortCooldown {
+    // open from the start of a run
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(PORT_COOLDOWN_SECS, TimerMode::Once);
+        timer.tick(timer.duration());
+        Self(timer)
+    }
+}
+
+#[derive(Component)]
+pub struct TransitionFade;
+
+pub fn tick_port_cooldown(time: Res<Time>, mut cooldown: ResMut<PortCooldown>) {
+    cooldown.tick(time.delta());
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn advance_transition(
+    mut commands: Commands,
+    time: Res<Time>,
+    transition: Option<ResMut<MapTransition>>,
+    mut map_query: Query<(&mut Handle<LdtkProject>, &mut LevelSet), With<Map>>,
+    ldtk_map: Res<MapHandles>,
+    world_handle: Option<Res<WorldPreloadHandler>>,
+    worlds: Res<Assets<WorldManifest>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut next_map: ResMut<LevelSelection>,
+    location_query: Query<(Entity, &Location, &Parent)>,
+    parent_query: Query<&Parent, Without<Location>>,
+    level_query: Query<&LevelIid>,
+    mut change_map_event: EventWriter<ChangeMapEvent>,
+    mut cooldown: ResMut<PortCooldown>,
+) {
+    let Some(mut transition) = transition else {
+        return;
+    };
+    transition.timer.tick(time.delta());
+
+    match transition.phase {
+        TransitionPhase::FadeOut => {
+            if !transition.timer.finished() {
+                return;
+            }
+            let Some((project, level)) = world_handle
+                .and_then(|handle| worlds.get(handle.id()))
+                .and_then(|world| world.level(&transition.level))
+            else {
+                error!("{} is not in the world manifest", transition.level);
+                transition.next(TransitionPhase::FadeIn);
+                return;
+            };
+
+            enter_level(
+                &mut commands,
+                &mut map_query,
+                &ldtk_map,
+                project,
+                level,
+                &mut next_map,
+            );
+            current_level.0 = level.name.clone();
+            transition.iid = level.iid.clone();
+            transition.next(TransitionPhase::Loading);
+        }
+        TransitionPhase::Loading => {
+            // a spawn point's parent is its layer, the layer's parent the level
+            let spawn_point = location_query
+                .iter()
+                .filter(|(_, location, _)| location.equal(&transition.location))
+                .find(|(_, _, parent)| {
+                    parent_query
+                        .get(parent.get())
+                        .and_then(|layer| level_query.get(layer.get()))
+                        .is_ok_and(|iid| iid.to_string() == transition.iid)
+                })
+                .map(|(entity, _, _)| entity);
+
+            if let Some(spawn_point) = spawn_point {
+                change_map_event.send(ChangeMapEvent {
+                    location: transition.location.clone(),
+                    spawn_point,
+                    run_start: transition.run_start,
+                });
+                transition.next(TransitionPhase::FadeIn);
+            } else if transition.timer.finished() {
+                error!(
+                    "No spawn point {:?} in {}, the hero stays where it was",
+                    transition.location.name(),
+                    transition.level
+                );
+                transition.next(TransitionPhase::FadeIn);
+            }
+        }
+        TransitionPhase::FadeIn => {
+            if transition.timer.finished() {
+                commands.remove_resource::<MapTransition>();
+                cooldown.reset();
+            }
+        }
+    }
+}
+
+// black overlay that follows the transition alpha
+pub fn fade_transition(
+    mut commands: Commands,
+    transition: Option<Res<MapTransition>>,
+    mut fade_query: Query<(Entity, &mut BackgroundColor), With<TransitionFade>>,
+) {
+    let Some(transition) = transition else {
+        for (entity, _) in fade_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let color = Color::srgba(0., 0., 0., transition.alpha());
+    if let Ok((_, mut background)) = fade_query.get_single_mut() {
+        background.0 = color;
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Transition Fade"),
+        TransitionFade,
+        StateScoped(Screen::Playing),
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            background_color: color.into(),
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+    ));
+}
+
+pub fn clear_transition(mut commands: Commands) {
+    commands.remove_resource::<MapTransition>();
+}
+
+#[test]
+fn test_transition_fade() {
+    let mut transition = MapTransition::new("grasslands", Location::new("Gate"));
+    assert_eq!(transition.alpha(), 0.);
+
+    transition.timer.tick(transition.timer.duration());
+    assert_eq!(transition.alpha(), 1.);
+
+    transition.next(TransitionPhase::Loading);
+    assert_eq!(transition.alpha(), 1.);
+
+    transition.next(TransitionPhase::FadeIn);
+    transition.timer.tick(transition.timer.duration());
+    assert_eq!(transition.alpha(), 0.);
+
+    assert!(PortCooldown::default().finished());
+
+    let level: LevelData = serde_json::from_str(
+        r#"{"name": "grasslands", "iid": "b-1", "display_name": "Grasslands"}"#,
+    )
+    .unwrap();
+    let transition = MapTransition::run_start(&level, Location::new("Start"));
+    assert_eq!(transition.phase, TransitionPhase::Loading);
+    assert_eq!(transition.alpha(), 1.);
+    assert_eq!(transition.iid, "b-1");
+}
@@ -0,0 +1,291 @@
+use std::slice::Iter;
+
+use bevy::prelude::*;
+
+use crate::{
+    error::UserInputError,
+    model::{
+        armor::ArmorData, helmet::HelmetData, player::MetaSave, shoes::ShoesData,
+        sub::DropEquipment, EquipmentData,
+    },
+    state::Screen,
+};
+
+use super::{
+    battle::event::BattleEvent,
+    character::component::{BaseStates, Hero},
+    player::{Armor, EquipmentBelt, Helmet, Shoes, Storage},
+    saving::{write_profile, Profile},
+    Player,
+};
+
+// the Cloth set, one more piece per rank of the starting gear upgrade
+const STARTING_GEAR_ID: u32 = 1;
+
+pub struct MetaPlugin;
+
+impl Plugin for MetaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MetaProgress>()
+            .init_resource::<RunRecord>()
+            .add_systems(Update, (load_meta_progress, save_meta_progress))
+            .add_systems(
+                OnEnter(Screen::Playing),
+                (reset_run_record, give_starting_gear),
+            )
+            .add_systems(Update, record_battle.run_if(in_state(Screen::Playing)))
+            .add_systems(OnEnter(Screen::GameOver), award_essence);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaUpgrade {
+    ExtraTurns,
+    DropChance,
+    StartingGear,
+}
+
+impl MetaUpgrade {
+    pub fn iterator() -> Iter<'static, MetaUpgrade> {
+        static UPGRADES: [MetaUpgrade; 3] = [
+            MetaUpgrade::ExtraTurns,
+            MetaUpgrade::DropChance,
+            MetaUpgrade::StartingGear,
+        ];
+        UPGRADES.iter()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MetaUpgrade::ExtraTurns => "Extra Turns",
+            MetaUpgrade::DropChance => "Lucky Find",
+            MetaUpgrade::StartingGear => "Supplies",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            MetaUpgrade::ExtraTurns => "+2 turns at the start of a run",
+            MetaUpgrade::DropChance => "+10% chance for monsters to drop loot",
+            MetaUpgrade::StartingGear => "Start with Cloth armor, then helmet, then shoes",
+        }
+    }
+
+    pub fn max_rank(&self) -> u32 {
+        match self {
+            MetaUpgrade::ExtraTurns => 5,
+            MetaUpgrade::DropChance => 5,
+            MetaUpgrade::StartingGear => 3,
+        }
+    }
+
+    // cost of buying the next rank
+    pub fn cost(&self, rank: u32) -> u64 {
+        let base = match self {
+            MetaUpgrade::ExtraTurns => 20,
+            MetaUpgrade::DropChance => 15,
+            MetaUpgrade::StartingGear => 10,
+        };
+        base * (rank as u64 + 1)
+    }
+}
+
+// Essence and upgrade ranks, mirrored into the profile whenever they change
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MetaProgress {
+    pub essence: u64,
+    // essence earned by the run that just ended
+    pub last_award: u64,
+    extra_turns: u32,
+    drop_chance: u32,
+    starting_gear: u32,
+}
+
+impl MetaProgress {
+    pub fn from_save(save: &MetaSave) -> Self {
+        Self {
+            essence: save.essence,
+            last_award: 0,
+            extra_turns: save.extra_turns,
+            drop_chance: save.drop_chance,
+            starting_gear: save.starting_gear,
+        }
+    }
+
+    pub fn to_save(&self) -> MetaSave {
+        MetaSave {
+            essence: self.essence,
+            extra_turns: self.extra_turns,
+            drop_chance: self.drop_chance,
+            starting_gear: self.starting_gear,
+        }
+    }
+
+    pub fn rank(&self, upgrade: MetaUpgrade) -> u32 {
+        match upgrade {
+            MetaUpgrade::ExtraTurns => self.extra_turns,
+            MetaUpgrade::DropChance => self.drop_chance,
+            MetaUpgrade::StartingGear => self.starting_gear,
+        }
+    }
+
+    fn rank_mut(&mut self, upgrade: MetaUpgrade) -> &mut u32 {
+        match upgrade {
+            MetaUpgrade::ExtraTurns => &mut self.extra_turns,
+            MetaUpgrade::DropChance => &mut self.drop_chance,
+            MetaUpgrade::StartingGear => &mut self.starting_gear,
+        }
+    }
+
+    // returns the essence spent
+    pub fn buy(&mut self, upgrade: MetaUpgrade) -> Result<u64, UserInputError> {
+        let rank = self.rank(upgrade);
+        let cost = upgrade.cost(rank);
+        if rank >= upgrade.max_rank() || self.essence < cost {
+            return Err(UserInputError());
+        }
+
+        self.essence -= cost;
+        *self.rank_mut(upgrade) += 1;
+        Ok(cost)
+    }
+
+    pub fn extra_turns(&self) -> u32 {
+        self.extra_turns * 2
+    }
+
+    // multiplier on every drop roll
+    pub fn drop_multiplier(&self) -> f32 {
+        1. + self.drop_chance as f32 * 0.1
+    }
+}
+
+// What the current run has achieved so far
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RunRecord {
+    pub victories: u32,
+    pub monsters_defeated: u32,
+    pub faints: u32,
+}
+
+impl RunRecord {
+    pub fn essence(&self, hero_lv: u32) -> u64 {
+        (hero_lv + self.monsters_defeated + self.victories / 2) as u64
+    }
+}
+
+fn load_meta_progress(profile: Option<Res<Profile>>, mut meta: ResMut<MetaProgress>) {
+    let Some(profile) = profile else {
+        return;
+    };
+    if profile.is_added() {
+        *meta = MetaProgress::from_save(&profile.meta);
+    }
+}
+
+fn save_meta_progress(meta: Res<MetaProgress>, profile: Option<ResMut<Profile>>) {
+    if !meta.is_changed() || meta.is_added() {
+        return;
+    }
+    let Some(mut profile) = profile else {
+        return;
+    };
+
+    profile.meta = meta.to_save();
+    write_profile(&profile);
+}
+
+fn reset_run_record(mut record: ResMut<RunRecord>) {
+    *record = RunRecord::default();
+}
+
+// Fills the empty slots only, gear the hero already wears stays on
+fn give_starting_gear(
+    mut player_query: Query<(&mut EquipmentBelt, &mut Storage), With<Player>>,
+    meta: Res<MetaProgress>,
+    armor_assets: Res<Assets<ArmorData>>,
+    helmet_assets: Res<Assets<HelmetData>>,
+    shoes_assets: Res<Assets<ShoesData>>,
+) {
+    let Ok((mut belt, mut storage)) = player_query.get_single_mut() else {
+        return;
+    };
+    let rank = meta.rank(MetaUpgrade::StartingGear);
+
+    if rank >= 1 && belt.armor.is_none() {
+        belt.armor = starting_piece(&armor_assets, &mut storage)
+            .and_then(|eq| Armor::from(&armor_assets, eq));
+    }
+    if rank >= 2 && belt.helmet.is_none() {
+        belt.helmet = starting_piece(&helmet_assets, &mut storage)
+            .and_then(|eq| Helmet::from(&helmet_assets, eq));
+    }
+    if rank >= 3 && belt.shoes.is_none() {
+        belt.shoes = starting_piece(&shoes_assets, &mut storage)
+            .and_then(|eq| Shoes::from(&shoes_assets, eq));
+    }
+}
+
+fn starting_piece<T: Asset + EquipmentData>(
+    assets: &Assets<T>,
+    storage: &mut Storage,
+) -> Option<DropEquipment> {
+    let (_, data) = assets
+        .iter()
+        .find(|(_, data)| data.id() == STARTING_GEAR_ID)?;
+
+    Some(match storage.is_owned(data) {
+        Some(owned) => owned,
+        None => {
+            let item = data.to_drop_equipment(1);
+            storage.add_item(&item);
+            item
+        }
+    })
+}
+
+fn record_battle(mut battle_event_reader: EventReader<BattleEvent>, mut record: ResMut<RunRecord>) {
+    for ev in battle_event_reader.read() {
+        if ev.is_player_victory {
+            record.victories += 1;
+            record.monsters_defeated += ev.monsters.len() as u32;
+        } else {
+            record.faints += 1;
+        }
+    }
+}
+
+// The hero is still around here, it is despawned when leaving the game over screen
+fn award_essence(
+    hero_query: Query<&BaseStates, With<Hero>>,
+    record: Res<RunRecord>,
+    mut meta: ResMut<MetaProgress>,
+) {
+    let lv = hero_query.get_single().map(|base| base.lv()).unwrap_or(1);
+    let award = record.essence(lv);
+
+    meta.essence = meta.essence.saturating_add(award);
+    meta.last_award = award;
+    info!("Run ended, earned {} essence", award);
+}
+
+#[test]
+fn test_meta_upgrade_purchase() {
+    let mut meta = MetaProgress::from_save(&MetaSave {
+        essence: 30,
+        ..default()
+    });
+
+    assert_eq!(meta.buy(MetaUpgrade::StartingGear).ok(), Some(10));
+    assert_eq!(meta.buy(MetaUpgrade::StartingGear).ok(), Some(20));
+    assert!(meta.buy(MetaUpgrade::StartingGear).is_err());
+    assert_eq!(meta.essence, 0);
+    assert_eq!(meta.rank(MetaUpgrade::StartingGear), 2);
+
+    meta.essence = 1000;
+    for _ in 0..MetaUpgrade::DropChance.max_rank() {
+        meta.buy(MetaUpgrade::DropChance).unwrap();
+    }
+    assert!(meta.buy(MetaUpgrade::DropChance).is_err());
+    assert_eq!(meta.to_save().drop_chance, 5);
+}
@@ -1,16 +1,20 @@
 use bevy::prelude::*;
 
+pub mod achievement;
 pub mod battle;
 pub mod character;
 mod joystick;
 pub mod map;
+pub mod meta;
 pub mod overlay;
 pub mod player;
-mod saving;
+pub mod saving;
+use achievement::*;
 use battle::*;
 use character::*;
 use joystick::VirtualJoystickPlugin;
 use map::*;
+use meta::*;
 use overlay::*;
 use player::*;
 use saving::*;
@@ -21,9 +25,11 @@ pub(super) fn plugin(app: &mut App) {
         // .add_systems(Update, set_simulation_state)
         .add_plugins((
             PlayerPlugin,
+            AchievementPlugin,
             BattlePlugin,
             FighterPlugin,
             MapPlugin,
+            MetaPlugin,
             SavingPlugin,
             VirtualJoystickPlugin,
             InGameOverlayPlugin,
@@ -1,13 +1,23 @@
 use bevy::prelude::*;
 use rand::random;
 
+use crate::{
+    audio_effects::ButtonClickEffect,
+    game::{battle::BattleSpeed, board::styles::NORMAL_BUTTON_COLOR},
+};
+
 // Question for future
 // Monster Health bar display follow inflation RPG or using one health bar percentage
 
 pub struct HealthBarUI;
 
 impl HealthBarUI {
-    pub fn build_monster(commands: &mut Commands, health: f32, name: &Name) -> Entity {
+    pub fn build_monster(
+        commands: &mut Commands,
+        health: f32,
+        name: &Name,
+        monster: Entity,
+    ) -> Entity {
         let name = commands
             .spawn(TextBundle {
                 style: Style {
@@ -23,7 +33,6 @@ impl HealthBarUI {
                             font_size: 15.,
                             ..default()
                         },
-                        ..default()
                     }],
                     ..default()
                 },
@@ -68,7 +77,8 @@ impl HealthBarUI {
         let health_outline_node = commands
             .spawn(NodeBundle {
                 style: Style {
-                    width: Val::Px(150.0),
+                    // narrow enough for three monsters side by side
+                    width: Val::Px(100.0),
                     height: Val::Px(10.0),
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
@@ -104,7 +114,7 @@ impl HealthBarUI {
                     background_color: BackgroundColor(Color::linear_rgb(0., 1., 0.)),
                     ..default()
                 },
-                MonsterHealthBar {},
+                MonsterHealthBar { monster },
                 HealthBar {
                     max: health,
                     remain: health,
@@ -119,7 +129,10 @@ impl HealthBarUI {
         commands
             .entity(health_bar_background_node)
             .add_child(health_bar_node);
-        commands.entity(health_bar).add_child(health_outline_node);
+        let status_icons = StatusIcons::build(commands, Some(monster));
+        commands
+            .entity(health_bar)
+            .push_children(&[health_outline_node, status_icons]);
 
         commands.entity(frame).push_children(&[health_bar]);
         frame
@@ -141,7 +154,6 @@ impl HealthBarUI {
                             font_size: 15.,
                             ..default()
                         },
-                        ..default()
                     }],
                     ..default()
                 },
@@ -237,7 +249,10 @@ impl HealthBarUI {
         commands
             .entity(health_bar_background_node)
             .add_child(health_bar_node);
-        commands.entity(health_bar).add_child(health_outline_node);
+        let status_icons = StatusIcons::build(commands, None);
+        commands
+            .entity(health_bar)
+            .push_children(&[health_outline_node, status_icons]);
 
         commands.entity(frame).add_child(health_bar).id()
     }
@@ -253,10 +268,16 @@ impl HealthBar {
     pub fn get_percentage(&self) -> f32 {
         0_f32.max(self.remain / self.max * 100.)
     }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.remain = self.max.min(self.remain + amount);
+    }
 }
 
 #[derive(Component)]
-pub struct MonsterHealthBar;
+pub struct MonsterHealthBar {
+    pub monster: Entity,
+}
 
 #[derive(Component)]
 
@@ -269,6 +290,95 @@ pub struct HeroHealthBar;
 
 pub struct HeroHealthNode;
 
+// Row of status effect badges under a health bar, no monster means the hero
+#[derive(Component)]
+pub struct StatusIcons {
+    pub monster: Option<Entity>,
+}
+
+impl StatusIcons {
+    pub fn build(commands: &mut Commands, monster: Option<Entity>) -> Entity {
+        commands
+            .spawn((
+                TextBundle {
+                    style: Style {
+                        margin: UiRect::top(Val::Px(20.)),
+                        ..default()
+                    },
+                    text: Text::default(),
+                    ..default()
+                },
+                StatusIcons { monster },
+                Name::new("Status Icons"),
+            ))
+            .id()
+    }
+}
+
+// Number floating up from whoever took the hit, gone once the timer runs out
+#[derive(Component)]
+pub struct DamageNumber(pub Timer);
+
+impl DamageNumber {
+    pub fn build(commands: &mut Commands, value: String, color: Color, font_size: f32) -> Entity {
+        commands
+            .spawn((
+                Name::new("Damage Number"),
+                DamageNumber(Timer::from_seconds(0.8, TimerMode::Once)),
+                TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(0.),
+                        ..default()
+                    },
+                    text: Text::from_section(
+                        value,
+                        TextStyle {
+                            font_size,
+                            color,
+                            ..default()
+                        },
+                    ),
+                    z_index: ZIndex::Global(20),
+                    ..default()
+                },
+            ))
+            .id()
+    }
+
+    pub fn normal(commands: &mut Commands, damage: f32) -> Entity {
+        Self::build(commands, format!("{:.1}", damage), Color::WHITE, 22.)
+    }
+
+    pub fn critical(commands: &mut Commands, damage: f32) -> Entity {
+        Self::build(
+            commands,
+            format!("{:.1}!", damage),
+            Color::srgb(1., 0.35, 0.1),
+            32.,
+        )
+    }
+
+    pub fn status(commands: &mut Commands, damage: f32) -> Entity {
+        Self::build(
+            commands,
+            format!("{:.1}", damage),
+            Color::srgb(0.7, 0.3, 0.9),
+            18.,
+        )
+    }
+}
+
+// Red tint on a sprite that was just hit
+#[derive(Component)]
+pub struct HitFlash(pub Timer);
+
+impl Default for HitFlash {
+    fn default() -> Self {
+        HitFlash(Timer::from_seconds(0.25, TimerMode::Once))
+    }
+}
+
 #[derive(Component, Default)]
 pub struct LvUpQueue(pub u32);
 
@@ -327,3 +437,53 @@ impl LvUpText {
 
 // #[derive(Component)]
 // pub struct DropScene;
+
+#[derive(Component, Clone, Copy)]
+pub enum SpeedControl {
+    Auto,
+    Speed,
+    Skip,
+}
+
+impl SpeedControl {
+    pub fn label(&self, speed: &BattleSpeed) -> String {
+        let on_off = |flag: bool| if flag { "On" } else { "Off" };
+        match self {
+            SpeedControl::Auto => format!("Auto: {}", on_off(speed.is_auto)),
+            SpeedControl::Speed => format!("Speed: {}x", speed.speed.multiplier()),
+            SpeedControl::Skip => format!("Skip: {}", on_off(speed.is_skip)),
+        }
+    }
+
+    pub fn build(commands: &mut Commands, control: SpeedControl, speed: &BattleSpeed) -> Entity {
+        commands
+            .spawn((
+                Name::new("Speed Control"),
+                control,
+                ButtonClickEffect,
+                ButtonBundle {
+                    background_color: NORMAL_BUTTON_COLOR.into(),
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        width: Val::Px(90.),
+                        height: Val::Px(30.),
+                        margin: UiRect::horizontal(Val::Px(5.)),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    control.label(speed),
+                    TextStyle {
+                        font_size: 15.,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ));
+            })
+            .id()
+    }
+}
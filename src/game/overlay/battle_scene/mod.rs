@@ -1,13 +1,14 @@
 use crate::game::monster::Monster;
+use crate::game::player::ConsumableBar;
 use crate::game::TerminalBundle;
 use crate::model::monster::MonsterData;
 use crate::model::sub::Element;
 use crate::model::ItemType;
 use crate::preload::images::{EquipmentHandles, UiImageAsset, UiImageHandles};
 use crate::state::{OverlayCombatState, TerminalState};
-use bevy::{prelude::*, ui::FocusPolicy};
+use bevy::prelude::*;
 
-use crate::game::battle::component::Combat;
+use crate::game::battle::{component::Combat, BattleSpeed};
 use crate::game::event::{SpawnBattleSceneEvent, SpawnDropSceneEvent};
 
 pub mod component;
@@ -30,13 +31,29 @@ impl Plugin for BattleScenePlugin {
             .add_systems(
                 Update,
                 (
+                    update_flag_on_finish,
+                    update_status_icons,
+                    select_target,
+                    update_target_indicator,
+                )
+                    .run_if(in_state(TerminalState::Combating))
+                    .run_if(in_state(OverlayCombatState::Opened)),
+            )
+            .add_systems(
+                Update,
+                (
+                    // a skipped fight settles its bars after the talking starts
                     update_hero_health_bar,
                     damping_hero_health_bar,
                     update_monster_health_bar,
                     damping_monster_health_bar,
-                    update_flag_on_finish,
+                    heal_boss_health_bar,
+                    spawn_hit_feedback,
+                    update_damage_number,
+                    update_hit_flash,
+                    interact_with_speed_control,
+                    update_speed_control_text,
                 )
-                    .run_if(in_state(TerminalState::Combating))
                     .run_if(in_state(OverlayCombatState::Opened)),
             )
             .add_systems(
@@ -72,62 +89,28 @@ pub struct BattleMonster {
 // #[derive(Component)]
 // pub struct BattleTerminalSpacer;
 
-/// The basic UI node
-///
-/// Useful as a container for a variety of child nodes.
-#[derive(Bundle, Clone, Debug)]
-pub struct CustomNodeBundle {
-    /// Describes the logical size of the node
-    pub node: Node,
-    /// Styles which control the layout (size and position) of the node and it's children
-    /// In some cases these styles also affect how the node drawn/painted.
-    pub style: Style,
-    /// The background color, which serves as a "fill" for this node
-    pub background_color: BackgroundColor,
-    /// The color of the Node's border
-    pub border_color: BorderColor,
-    /// Whether this node should block interaction with lower nodes
-    pub focus_policy: FocusPolicy,
-
-    /// Indicates the depth at which the node should appear in the UI
-    pub z_index: ZIndex,
-}
-
-impl Default for CustomNodeBundle {
-    fn default() -> Self {
-        CustomNodeBundle {
-            // Transparent background
-            background_color: Color::NONE.into(),
-            border_color: Color::NONE.into(),
-            node: Default::default(),
-            style: Default::default(),
-            focus_policy: Default::default(),
-            z_index: Default::default(),
-        }
-    }
-}
-
 pub fn spawn_battle_scene(
     mut commands: Commands,
     mut battle_event_reader: EventReader<SpawnBattleSceneEvent>,
     battle_query: Query<&Combat>,
     monster_query: Query<(&Name, &MonsterData), With<Monster>>,
     ui_images: Res<UiImageHandles>,
+    speed: Res<BattleSpeed>,
 ) {
     for ev in battle_event_reader.read() {
         let Ok(battle) = battle_query.get(ev.battle) else {
             continue;
         };
         // let default_name = Name::new("Monster");
-        let Ok((monster_name, monster_data)) = monster_query.get(ev.monster) else {
+        let Some(first_monster) = ev.monsters.first() else {
+            continue;
+        };
+        let Ok((_, monster_data)) = monster_query.get(*first_monster) else {
             continue;
         };
 
         let hero_health_bar = HealthBarUI::build_hero(&mut commands, battle.player_hp_remain);
 
-        let monster_health_bar =
-            HealthBarUI::build_monster(&mut commands, battle.monster_hp_remain, monster_name);
-
         let bg_image = match monster_data.element {
             Element::Fire => ui_images[&UiImageAsset::Dry].clone(),
             Element::Water => ui_images[&UiImageAsset::Rainy].clone(),
@@ -186,7 +169,7 @@ pub fn spawn_battle_scene(
                         ..Style::DEFAULT
                     },
                     image: UiImage {
-                        color: Color::WHITE.into(),
+                        color: Color::WHITE,
                         texture: bg_image,
                         flip_x: false,
                         flip_y: false,
@@ -258,26 +241,98 @@ pub fn spawn_battle_scene(
             ))
             .id();
 
-        let monster_stand = commands
+        // one slot per monster, tapping the stand aims at it
+        let monster_slots = ev
+            .monsters
+            .iter()
+            .filter_map(|monster| {
+                let (monster_name, _) = monster_query.get(*monster).ok()?;
+                let health = battle.foe(*monster)?.hp_remain;
+
+                let monster_health_bar =
+                    HealthBarUI::build_monster(&mut commands, health, monster_name, *monster);
+
+                let monster_stand = commands
+                    .spawn((
+                        Name::new("Monster"),
+                        BattleMonster { monster: *monster },
+                        ButtonBundle {
+                            background_color: Color::NONE.into(),
+                            border_color: Color::NONE.into(),
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                border: UiRect::all(Val::Px(2.)),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..Style::DEFAULT
+                            },
+                            ..default()
+                        },
+                    ))
+                    .id();
+
+                let slot = commands
+                    .spawn((
+                        Name::new("Monster Slot"),
+                        NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                width: Val::Percent(100. / ev.monsters.len() as f32),
+                                min_width: Val::Percent(30.0),
+                                height: Val::Percent(100.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..Style::DEFAULT
+                            },
+                            ..default()
+                        },
+                    ))
+                    .id();
+
+                commands
+                    .entity(slot)
+                    .push_children(&[monster_health_bar, monster_stand]);
+                Some(slot)
+            })
+            .collect::<Vec<_>>();
+
+        let item_bar = commands
             .spawn((
-                Name::new("Monster"),
-                BattleMonster {
-                    monster: ev.monster,
+                Name::new("Battle Item Bar"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::Center,
+                        margin: UiRect::top(Val::Px(10.)),
+                        ..default()
+                    },
+                    ..default()
                 },
+                ConsumableBar { in_battle: true },
+            ))
+            .id();
+
+        let speed_row = commands
+            .spawn((
+                Name::new("Battle Speed Row"),
                 NodeBundle {
-                    background_color: Color::NONE.into(),
                     style: Style {
-                        flex_direction: FlexDirection::Column,
-                        min_width: Val::Percent(50.0),
-                        height: Val::Percent(100.0),
-                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Row,
+                        width: Val::Percent(100.0),
                         justify_content: JustifyContent::Center,
-                        ..Style::DEFAULT
+                        margin: UiRect::top(Val::Px(10.)),
+                        ..default()
                     },
                     ..default()
                 },
             ))
             .id();
+        let speed_controls = [SpeedControl::Auto, SpeedControl::Speed, SpeedControl::Skip]
+            .map(|control| SpeedControl::build(&mut commands, control, &speed));
+        commands.entity(speed_row).push_children(&speed_controls);
 
         let terminal_node = commands
             .spawn((
@@ -293,7 +348,7 @@ pub fn spawn_battle_scene(
                         ..default()
                     },
                     border_color: Color::BLACK.into(),
-                    background_color: Color::rgb(103., 102., 81.).into(),
+                    background_color: Color::srgb(103., 102., 81.).into(),
                     ..default()
                 },
                 TerminalBundle::new(22.),
@@ -303,9 +358,7 @@ pub fn spawn_battle_scene(
         commands
             .entity(battle_ground)
             .push_children(&[monster_row, hero_row]);
-        commands
-            .entity(monster_row)
-            .push_children(&[monster_health_bar, monster_stand]);
+        commands.entity(monster_row).push_children(&monster_slots);
 
         // commands.entity(monster_stand).add_child(monster);
         commands
@@ -315,6 +368,8 @@ pub fn spawn_battle_scene(
         commands
             .entity(main_frame)
             .add_child(battle_ground)
+            .add_child(item_bar)
+            .add_child(speed_row)
             .add_child(terminal_node);
 
         info!("Spawned Battle Scene");
@@ -1,19 +1,31 @@
 use bevy::prelude::*;
 
-use crate::game::{event::AttackEvent, TurnFlag};
+use crate::game::{
+    battle::{BattleSpeed, DamageOut},
+    character::component::{BattleMonsterSprite, HeroClass},
+    event::{AttackEvent, BossPhaseEvent},
+    Combat, TurnFlag,
+};
 
-use super::{HealthBar, HeroHealthBar, LvUpText, MonsterHealthBar};
+use super::{
+    BattleHeroSprite, BattleMonster, DamageNumber, HealthBar, HeroHealthBar, HitFlash, LvUpText,
+    MonsterHealthBar, SpeedControl, StatusIcons,
+};
 
 pub fn update_hero_health_bar(
     mut event: EventReader<AttackEvent>,
     mut bar_query: Query<&mut HealthBar, With<HeroHealthBar>>,
 ) {
     for ev in event.read() {
-        if !ev.record.is_player_turn {
-            info!("Update Hero Health Bar: {}", ev.record.damage_out.get());
-            for mut bar in &mut bar_query {
-                bar.remain -= ev.record.damage_out.get();
-            }
+        let damage = if ev.record.is_player_turn {
+            ev.record.status_damage
+        } else {
+            ev.record.damage_out.get()
+        };
+
+        info!("Update Hero Health Bar: {}", damage);
+        for mut bar in &mut bar_query {
+            bar.remain -= damage;
         }
     }
 }
@@ -21,31 +33,40 @@ pub fn update_hero_health_bar(
 pub fn damping_hero_health_bar(
     mut bar_query: Query<(&mut Style, &mut BackgroundColor, &HealthBar), With<HeroHealthBar>>,
     time: Res<Time>,
+    speed: Res<BattleSpeed>,
 ) {
+    let delta = speed.scale(time.delta()).as_secs_f32();
     for (mut bar_style, _background_color, bar) in &mut bar_query {
         let Val::Percent(current_width) = bar_style.width else {
             continue;
         };
 
         if current_width > bar.get_percentage() {
-            bar_style.width = Val::Percent(
-                bar.get_percentage()
-                    .max(current_width - 100. * time.delta_seconds()),
-            );
+            bar_style.width = Val::Percent(bar.get_percentage().max(current_width - 100. * delta));
+        } else if current_width < bar.get_percentage() {
+            // healed by potion
+            bar_style.width = Val::Percent(bar.get_percentage().min(current_width + 100. * delta));
         }
     }
 }
 
 pub fn update_monster_health_bar(
     mut event: EventReader<AttackEvent>,
-    mut bar_query: Query<&mut HealthBar, With<MonsterHealthBar>>,
+    mut bar_query: Query<(&mut HealthBar, &MonsterHealthBar)>,
 ) {
     for ev in event.read() {
-        if ev.record.is_player_turn {
-            for mut bar in &mut bar_query {
-                bar.remain -= ev.record.damage_out.get();
-                info!("Update Monster Health Bar: {}", bar.get_percentage());
-            }
+        let damage = if ev.record.is_player_turn {
+            ev.record.damage_out.get()
+        } else {
+            ev.record.status_damage
+        };
+
+        for (mut bar, _) in bar_query
+            .iter_mut()
+            .filter(|(_, owner)| owner.monster == ev.record.monster)
+        {
+            bar.remain -= damage;
+            info!("Update Monster Health Bar: {}", bar.get_percentage());
         }
     }
 }
@@ -53,7 +74,9 @@ pub fn update_monster_health_bar(
 pub fn damping_monster_health_bar(
     mut bar_query: Query<(&mut Style, &mut BackgroundColor, &HealthBar), With<MonsterHealthBar>>,
     time: Res<Time>,
+    speed: Res<BattleSpeed>,
 ) {
+    let delta = speed.scale(time.delta()).as_secs_f32();
     for (mut bar_style, _background_color, bar) in &mut bar_query {
         let Val::Percent(current_width) = bar_style.width else {
             continue;
@@ -64,14 +87,63 @@ pub fn damping_monster_health_bar(
             //     "new monster health percentage: {}",
             //     current_width - 100. * time.delta_seconds()
             // );
-            bar_style.width = Val::Percent(
-                bar.get_percentage()
-                    .max(current_width - 100. * time.delta_seconds()),
-            );
+            bar_style.width = Val::Percent(bar.get_percentage().max(current_width - 100. * delta));
+        } else if current_width < bar.get_percentage() {
+            // boss recovering on a new phase
+            bar_style.width = Val::Percent(bar.get_percentage().min(current_width + 100. * delta));
+        }
+    }
+}
+
+pub fn heal_boss_health_bar(
+    mut event: EventReader<BossPhaseEvent>,
+    mut bar_query: Query<(&mut HealthBar, &MonsterHealthBar)>,
+) {
+    for ev in event.read() {
+        for (mut bar, owner) in bar_query.iter_mut() {
+            if owner.monster == ev.monster {
+                bar.heal(ev.heal);
+            }
         }
     }
 }
 
+pub fn update_status_icons(
+    battle_query: Query<&Combat, Changed<Combat>>,
+    mut icon_query: Query<(&mut Text, &StatusIcons)>,
+) {
+    let Ok(battle) = battle_query.get_single() else {
+        return;
+    };
+
+    for (mut text, icons) in icon_query.iter_mut() {
+        let effects = match icons.monster {
+            None => &battle.player_effects,
+            Some(monster) => match battle.foe(monster) {
+                Some(foe) => &foe.effects,
+                None => continue,
+            },
+        };
+
+        text.sections = effects
+            .0
+            .iter()
+            .map(|effect| TextSection {
+                value: if effect.stacks > 1 {
+                    format!("{}x{} ", effect.kind.short_name(), effect.stacks)
+                } else {
+                    format!("{} ", effect.kind.short_name())
+                },
+                style: TextStyle {
+                    color: effect.kind.color(),
+                    font_size: 12.,
+                    ..default()
+                },
+            })
+            .collect();
+    }
+}
+
 pub fn update_lv_up_text_position(
     mut lv_up_query: Query<&mut Style, With<LvUpText>>,
     time: Res<Time>,
@@ -92,31 +164,195 @@ pub fn update_flag_on_finish(
         (&mut Style, &HealthBar),
         (With<MonsterHealthBar>, Without<HeroHealthBar>),
     >,
+    feedback_query: Query<(), Or<(With<DamageNumber>, With<HitFlash>)>>,
 ) {
     let Ok(mut turn_flag) = battle_query.get_single_mut() else {
         warn!("No turn flag found");
         return;
     };
 
-    let (Ok((hero_bar_style, hero_bar)), Ok((monster_bar_style, monster_bar))) =
-        (hero_bar_query.get_single(), monster_bar_query.get_single())
-    else {
+    let Ok((hero_bar_style, hero_bar)) = hero_bar_query.get_single() else {
         warn!("No health bars found");
         return;
     };
 
-    let (Val::Percent(hero_width), Val::Percent(monster_width)) =
-        (hero_bar_style.width, monster_bar_style.width)
-    else {
+    let Val::Percent(hero_width) = hero_bar_style.width else {
         return;
     };
 
     let hero_diff = (hero_width - hero_bar.get_percentage()).abs();
-    let monster_diff = (monster_width - monster_bar.get_percentage()).abs();
 
-    if hero_diff < 0.5 && monster_diff < 0.5 {
-        turn_flag.is_animation_ready = true;
-    } else {
-        turn_flag.is_animation_ready = false;
+    // every bar in the group has to settle
+    let is_monster_settled = monster_bar_query
+        .iter()
+        .all(
+            |(monster_bar_style, monster_bar)| match monster_bar_style.width {
+                Val::Percent(monster_width) => {
+                    (monster_width - monster_bar.get_percentage()).abs() < 0.5
+                }
+                _ => true,
+            },
+        );
+
+    // damage numbers and flashes play out before the next turn
+    turn_flag.is_animation_ready =
+        hero_diff < 0.5 && is_monster_settled && feedback_query.is_empty();
+}
+
+pub fn select_target(
+    button_query: Query<(&Interaction, &BattleMonster), Changed<Interaction>>,
+    mut battle_query: Query<&mut Combat>,
+) {
+    for (interaction, stand) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Ok(mut battle) = battle_query.get_single_mut() else {
+            continue;
+        };
+
+        if battle.set_target(stand.monster) {
+            info!("Targeting {:?}", stand.monster);
+        }
+    }
+}
+
+pub fn update_target_indicator(
+    battle_query: Query<&Combat, Changed<Combat>>,
+    mut stand_query: Query<(&mut BorderColor, &BattleMonster)>,
+) {
+    let Ok(battle) = battle_query.get_single() else {
+        return;
+    };
+
+    let target = battle.target_entity();
+    for (mut border_color, stand) in stand_query.iter_mut() {
+        // no point aiming when there is only one
+        *border_color = if battle.foes.len() > 1 && target == Some(stand.monster) {
+            Color::srgb(1., 0.85, 0.2).into()
+        } else {
+            Color::NONE.into()
+        };
+    }
+}
+
+pub fn interact_with_speed_control(
+    button_query: Query<(&Interaction, &SpeedControl), Changed<Interaction>>,
+    mut speed: ResMut<BattleSpeed>,
+) {
+    for (interaction, control) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match control {
+            SpeedControl::Auto => speed.is_auto = !speed.is_auto,
+            SpeedControl::Speed => speed.speed = speed.speed.next(),
+            SpeedControl::Skip => speed.is_skip = !speed.is_skip,
+        }
+        info!("Battle speed: {:?}", *speed);
+    }
+}
+
+pub fn update_speed_control_text(
+    speed: Res<BattleSpeed>,
+    control_query: Query<(&SpeedControl, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !speed.is_changed() {
+        return;
+    }
+
+    for (control, children) in control_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = control.label(&speed);
+            }
+        }
+    }
+}
+
+pub fn spawn_hit_feedback(
+    mut commands: Commands,
+    mut event: EventReader<AttackEvent>,
+    speed: Res<BattleSpeed>,
+    monster_sprite_query: Query<(Entity, &Parent), With<BattleMonsterSprite>>,
+    hero_sprite_query: Query<(Entity, &Parent), With<BattleHeroSprite>>,
+    stand_query: Query<&BattleMonster>,
+) {
+    for ev in event.read() {
+        // a skipped fight only shows the summary
+        if speed.is_skip {
+            continue;
+        }
+
+        let record = &ev.record;
+        let monster = monster_sprite_query.iter().find(|(_, parent)| {
+            stand_query
+                .get(parent.get())
+                .is_ok_and(|stand| stand.monster == record.monster)
+        });
+        let hero = hero_sprite_query.get_single().ok();
+
+        let (attacker, defender) = if record.is_player_turn {
+            (hero, monster)
+        } else {
+            (monster, hero)
+        };
+
+        if let Some((sprite, stand)) = defender.filter(|_| !record.is_skipped) {
+            let number = match record.damage_out {
+                DamageOut::CriticalHit(damage) => DamageNumber::critical(&mut commands, damage),
+                DamageOut::NormalHit(damage) => DamageNumber::normal(&mut commands, damage),
+            };
+            commands.entity(stand.get()).add_child(number);
+            commands.entity(sprite).insert(HitFlash::default());
+        }
+
+        if let Some((_, stand)) = attacker.filter(|_| record.status_damage > 0.) {
+            let number = DamageNumber::status(&mut commands, record.status_damage);
+            commands.entity(stand.get()).add_child(number);
+        }
+    }
+}
+
+pub fn update_damage_number(
+    mut commands: Commands,
+    mut number_query: Query<(Entity, &mut DamageNumber, &mut Style, &mut Text)>,
+    time: Res<Time>,
+    speed: Res<BattleSpeed>,
+) {
+    let delta = speed.scale(time.delta());
+    for (entity, mut number, mut style, mut text) in number_query.iter_mut() {
+        if number.0.tick(delta).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        if let Val::Px(top) = style.top {
+            style.top = Val::Px(top - 60. * delta.as_secs_f32());
+        }
+        let alpha = 1. - number.0.fraction();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_alpha(alpha);
+        }
+    }
+}
+
+pub fn update_hit_flash(
+    mut commands: Commands,
+    mut sprite_query: Query<(Entity, &mut HitFlash, &mut UiImage, Option<&HeroClass>)>,
+    time: Res<Time>,
+    speed: Res<BattleSpeed>,
+) {
+    for (entity, mut flash, mut image, class) in sprite_query.iter_mut() {
+        if flash.0.tick(speed.scale(time.delta())).finished() {
+            image.color = class.map_or(Color::WHITE, HeroClass::tint);
+            commands.entity(entity).remove::<HitFlash>();
+        } else {
+            let tint = flash.0.fraction();
+            image.color = Color::srgb(1., tint, tint);
+        }
     }
 }
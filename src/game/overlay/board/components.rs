@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::model::sub::DropEquipment;
+use crate::{game::character::component::Allocation, model::sub::DropEquipment};
 
 #[derive(Component)]
 pub struct StatusBoard {}
@@ -79,9 +79,27 @@ pub struct ResetButton;
 #[derive(Component)]
 pub struct RestartButton;
 
+#[derive(Component)]
+pub struct RespecButton;
+
 #[derive(Component)]
 pub struct BagGrid;
 
+#[derive(Component)]
+pub struct BattleLogButton;
+
+#[derive(Component)]
+pub struct ExportLogButton;
+
+// Hidden until the battle log button swaps it with the bag grid
+#[derive(Component)]
+pub struct BattleLogPanel;
+
+#[derive(Component, Default)]
+pub struct BattleLogList {
+    pub position: f32,
+}
+
 #[derive(Component)]
 pub struct SlotContainers(pub Vec<InteractiveSlot>);
 impl SlotContainers {
@@ -97,7 +115,7 @@ pub enum InteractiveSlot {
 }
 
 impl InteractiveSlot {
-    pub fn to_confirmed(&mut self) -> Self {
+    pub fn to_confirmed(&self) -> Self {
         Self::Confirmed(self.get_value())
     }
 
@@ -144,11 +162,7 @@ pub struct DropBackButton;
 pub struct PickingBag();
 
 #[derive(Component)]
-pub struct NotSelectedEq {
-    pub eq: DropEquipment,
-    pub image: Handle<Image>,
-    pub empty: bool,
-}
+pub struct NotSelectedEq;
 
 #[derive(Component, Default)]
 pub struct UpgradeBuffer {
@@ -161,6 +175,13 @@ impl UpgradeBuffer {
     pub fn get_total_point(&self) -> u32 {
         self.strength + self.agi + self.defense
     }
+    pub fn allocation(&self) -> Allocation {
+        Allocation {
+            str: self.strength,
+            def: self.defense,
+            agi: self.agi,
+        }
+    }
     pub fn reset(&mut self) {
         self.strength = 0;
         self.agi = 0;
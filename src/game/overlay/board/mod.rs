@@ -1,8 +1,3 @@
-use crate::game::{
-    character::component::{Critical, Defense, Strength},
-    Money,
-};
-
 use bevy::prelude::*;
 
 pub mod components;
@@ -10,7 +5,7 @@ mod picking_board;
 mod pop_up;
 mod score_board;
 pub mod status_board;
-mod styles;
+pub mod styles;
 
 use picking_board::*;
 use pop_up::*;
@@ -33,26 +28,3 @@ impl Plugin for UIBoardPlugin {
             ));
     }
 }
-
-struct StatusPack<'a> {
-    pub strength: &'a Strength,
-    pub defense: &'a Defense,
-    pub critical: &'a Critical,
-    pub money: &'a Money,
-}
-
-impl<'a> StatusPack<'a> {
-    fn new(
-        strength: &'a Strength,
-        defense: &'a Defense,
-        critical: &'a Critical,
-        money: &'a Money,
-    ) -> Self {
-        StatusPack {
-            strength,
-            defense,
-            critical,
-            money,
-        }
-    }
-}
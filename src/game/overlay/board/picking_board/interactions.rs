@@ -141,17 +141,7 @@ pub fn interact_with_finish_button(
             let current = current_query.single_mut();
 
             for item in current.drop_list().iter() {
-                if let Some(item_list) = player_storage.items.get_mut(&item.item_type) {
-                    if let Some(eq) = item_list.iter_mut().find(|a| a.id == item.id) {
-                        eq.set_level(item.level);
-                    } else {
-                        item_list.push(item.clone());
-                    }
-                } else {
-                    player_storage
-                        .items
-                        .insert(item.item_type.clone(), vec![item.clone()]);
-                }
+                player_storage.add_item(item);
             }
 
             info!("Naving to Score Board");
@@ -45,7 +45,7 @@ pub fn handle_selecting_grid(
                 info!("Selecting : {:?}", cell_entity);
                 commands.entity(cell_entity).insert(SelectedGrid {});
 
-                border_color.0 = Color::srgb(0.4, 0.4, 0.2).into();
+                border_color.0 = Color::srgb(0.4, 0.4, 0.2);
                 container.0.push(Selected(eq.clone()));
             }
 
@@ -108,7 +108,7 @@ pub fn handle_confirming_slot(
 
         container.0 = container
             .0
-            .iter_mut()
+            .iter()
             .map(|slot| slot.to_confirmed())
             .collect::<Vec<InteractiveSlot>>();
 
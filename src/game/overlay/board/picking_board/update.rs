@@ -9,6 +9,7 @@ use crate::{
             DropNextButtonHolder, InteractiveSlot, NotSelectedEq, SelectedSlotID, SingleDropEqGrid,
             SlotContainers,
         },
+        player::{EquipmentAssets, EquipmentBelt, Player},
     },
     model::{sub::DropEquipment, ItemType},
     preload::images::EquipmentHandles,
@@ -40,11 +41,7 @@ pub fn update_drop_bag_grid(
 
             let parent = commands
                 .spawn((
-                    NotSelectedEq {
-                        image: source.clone(),
-                        eq: equipment.clone(),
-                        empty: false,
-                    },
+                    NotSelectedEq,
                     NodeBundle {
                         style: Style {
                             display: Display::Grid,
@@ -53,7 +50,8 @@ pub fn update_drop_bag_grid(
                             padding: UiRect::all(Val::Px(3.0)),
                             ..default()
                         },
-                        background_color: BackgroundColor(Color::BLACK),
+                        // the frame shows the rarity
+                        background_color: BackgroundColor(equipment.rarity.color()),
                         ..default()
                     },
                 ))
@@ -291,6 +289,8 @@ pub fn update_slots_with_containers(
     container_query: Query<&mut SlotContainers, Changed<SlotContainers>>,
     slots_query: Query<(&SelectedSlotID, Entity)>,
     eq_pic_assets: Res<EquipmentHandles>,
+    player_query: Query<&EquipmentBelt, With<Player>>,
+    assets: EquipmentAssets,
 ) {
     if let Ok(container) = container_query.get_single() {
         info!("{:?} in continer", container.0);
@@ -323,9 +323,9 @@ pub fn update_slots_with_containers(
                     .spawn(TextBundle {
                         text: Text {
                             sections: vec![TextSection {
-                                value: format!("{}", item.name).to_string(),
+                                value: item.name.clone(),
                                 style: TextStyle {
-                                    color: Color::WHITE,
+                                    color: item.rarity.color(),
                                     font_size: 20.,
                                     ..default()
                                 },
@@ -348,7 +348,12 @@ pub fn update_slots_with_containers(
                     .spawn(TextBundle {
                         text: Text {
                             sections: vec![TextSection {
-                                value: format!("Lv {}", item.level).to_string(),
+                                value: item
+                                    .affixes
+                                    .iter()
+                                    .fold(format!("Lv {}", item.level), |text, affix| {
+                                        format!("{}\n{}", text, affix)
+                                    }),
                                 style: TextStyle {
                                     font_size: 15.,
                                     color: Color::WHITE,
@@ -362,9 +367,21 @@ pub fn update_slots_with_containers(
                     })
                     .id();
 
-                commands
-                    .entity(slot_entity)
-                    .push_children(&[name, image, lv]);
+                // against what is worn now in the same slot
+                let mut children = vec![name, image, lv];
+                if let Ok(belt) = player_query.get_single() {
+                    let sections = belt.comparison_sections(
+                        &belt.with_item(&assets, item),
+                        &TextStyle {
+                            font_size: 12.,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    );
+                    children.push(commands.spawn(TextBundle::from_sections(sections)).id());
+                }
+
+                commands.entity(slot_entity).push_children(&children);
                 commands.entity(slot_entity).insert(item.clone());
             }
         }
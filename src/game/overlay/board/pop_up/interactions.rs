@@ -15,14 +15,11 @@ pub fn interact_with_confirm_restart_button(
     mut spawn_score_board: EventWriter<SpawnScoreBoardEvent>,
 ) {
     for interaction in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                app_state.set(Screen::GameOver);
-                spawn_score_board.send(SpawnScoreBoardEvent(vec![]));
-                status_board_state.set(OverlayStatusBoardState::Closed);
-                next_score_board_state.set(OverlayScoreBoardState::Opened);
-            }
-            _ => (),
+        if *interaction == Interaction::Pressed {
+            app_state.set(Screen::GameOver);
+            spawn_score_board.send(SpawnScoreBoardEvent(vec![]));
+            status_board_state.set(OverlayStatusBoardState::Closed);
+            next_score_board_state.set(OverlayScoreBoardState::Opened);
         }
     }
 }
@@ -32,12 +29,9 @@ pub fn interact_with_cancel_buy_button(
     mut status_board_state: ResMut<NextState<OverlayStatusBoardState>>,
 ) {
     for interaction in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                status_board_state.set(OverlayStatusBoardState::Opened);
-                info!("Close Window");
-            }
-            _ => (),
+        if *interaction == Interaction::Pressed {
+            status_board_state.set(OverlayStatusBoardState::Opened);
+            info!("Close Window");
         }
     }
 }
@@ -111,9 +111,8 @@ pub fn spawn_confirm_pop_up(
                 TextBundle {
                     text: Text {
                         sections: vec![TextSection {
-                            value: format!(
-                                "You are going to lose your current progress.\n\nAre you sure?"
-                            ),
+                            value: "You are going to lose your current progress.\n\nAre you sure?"
+                                .to_string(),
                             style: TextStyle {
                                 font_size: 30.,
                                 font: asset_server.load("fonts/FiraMono-Medium.ttf"),
@@ -17,9 +17,10 @@ pub fn interact_with_score_button(
             Interaction::Pressed => {
                 *background_color = PRESSED_BUTTON_COLOR.into();
 
-                next_app_state.set(Screen::Title);
+                // essence from the run is spent before the next one
+                next_app_state.set(Screen::Upgrades);
                 next_score_board_state.set(OverlayScoreBoardState::Closed);
-                info!("Back To Menu");
+                info!("To Upgrades");
             }
 
             Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
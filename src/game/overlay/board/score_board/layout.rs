@@ -4,6 +4,8 @@ use crate::audio_effects::ButtonClickEffect;
 use crate::game::overlay::board::components::*;
 use crate::game::overlay::board::styles::*;
 
+use crate::game::character::component::HeroClass;
+use crate::game::character::hero::SelectedClass;
 use crate::game::GameUIFrame;
 use crate::model::sub::DropEquipment;
 use crate::model::ItemType;
@@ -23,6 +25,7 @@ pub fn spawn_score_board(
     hero_sprite: Res<HeroActionHandles>,
     eq_pic_assets: Res<EquipmentHandles>,
     hero_textureatlas: Res<HeroActionTextureAtLasHandles>,
+    selected_class: Res<SelectedClass>,
 ) {
     for ev in spawn_event.read() {
         let layout_entity = layout_query.get_single().expect("No Layout in Game");
@@ -44,6 +47,7 @@ pub fn spawn_score_board(
             &ev.0,
             &eq_pic_assets,
             &hero_textureatlas,
+            selected_class.0,
         );
         // }
     }
@@ -68,9 +72,10 @@ fn build_score_board(
     asset_server: &Res<AssetServer>,
     layout_entity: Entity,
     hero_sprite: &Res<HeroActionHandles>,
-    drops: &Vec<DropEquipment>,
+    drops: &[DropEquipment],
     eq_pic_assets: &Res<EquipmentHandles>,
     hero_textureatlas: &Res<HeroActionTextureAtLasHandles>,
+    hero_class: HeroClass,
 ) {
     let board = commands
         .spawn((
@@ -126,7 +131,7 @@ fn build_score_board(
                 },
                 text: Text {
                     sections: vec![TextSection::new(
-                        format!("Round Summary"),
+                        "Round Summary".to_string(),
                         TextStyle {
                             font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                             font_size: 30.0,
@@ -381,12 +386,8 @@ fn build_score_board(
                     justify_content: JustifyContent::SpaceBetween,
                     ..Style::DEFAULT
                 },
-                image: hero_sprite
-                    .0
-                    .get(&HeroAction::AttackNoMovement)
-                    .unwrap()
-                    .clone()
-                    .into(),
+                image: UiImage::new(hero_sprite[&HeroAction::AttackNoMovement].clone())
+                    .with_color(hero_class.tint()),
                 ..default()
             },
             TextureAtlas {
@@ -721,7 +722,7 @@ fn build_score_board(
             .spawn(TextBundle {
                 text: Text {
                     sections: vec![TextSection {
-                        value: format!("{}", equipment.name).to_string(),
+                        value: equipment.name.clone(),
                         style: TextStyle {
                             color: Color::WHITE,
                             font_size: 20.,
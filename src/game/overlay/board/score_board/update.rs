@@ -15,7 +15,7 @@ pub fn update_lv_text(
             if level.0 < stat.lv_f32() {
                 level.0 = stat
                     .lv_f32()
-                    .min(level.0 as f32 + stat.lv_f32() * time.delta_seconds() / 3.);
+                    .min(level.0 + stat.lv_f32() * time.delta_seconds() / 3.);
             }
 
             text.sections[0].value = format!("{:.0}Lv", level.0);
@@ -33,7 +33,7 @@ pub fn update_money_text(
             if money.0 < stat.get_f32() {
                 money.0 = stat
                     .get_f32()
-                    .min(money.0 as f32 + stat.get() as f32 * time.delta_seconds() / 3.);
+                    .min(money.0 + stat.get() as f32 * time.delta_seconds() / 3.);
             }
 
             text.sections[0].value = format!("Money: {:.0}", money.0);
@@ -1,23 +1,30 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::log::info;
 use bevy::prelude::*;
 
+use crate::game::battle::BattleHistory;
+
 use crate::game::board::styles::NORMAL_BUTTON_COLOR;
 use crate::game::board::SelectGridEvent;
 use crate::game::overlay::board::SpawnRestartConfirmPopupEvent;
 use crate::game::overlay::header::styles::{HOVERED_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
+use crate::game::terminal::component::AutoTerminalText;
 use crate::game::{
     character::component::*, overlay::board::components::*,
     overlay::header::components::StatusBoardButton,
 };
+use crate::game::{Money, Player};
 use crate::state::{OverlayStatusBoardState, SimulationState};
 
+const BATTLE_HISTORY_EXPORT_PATH: &str = "battle_history.json";
+
 pub fn interact_with_str_half_button(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<StrButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -27,10 +34,10 @@ pub fn interact_with_str_half_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
-                match status_point.half() {
+                match hero.half_state_point() {
                     Ok(point) => {
                         buffer.strength += point;
                     }
@@ -49,7 +56,7 @@ pub fn interact_with_def_half_button(
         (Changed<Interaction>, With<DefButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -59,10 +66,10 @@ pub fn interact_with_def_half_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
-                match status_point.half() {
+                match hero.half_state_point() {
                     Ok(point) => {
                         buffer.defense += point;
                     }
@@ -81,7 +88,7 @@ pub fn interact_with_agi_half_button(
         (Changed<Interaction>, With<AgiButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -91,10 +98,10 @@ pub fn interact_with_agi_half_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
-                match status_point.half() {
+                match hero.half_state_point() {
                     Ok(point) => {
                         buffer.agi += point;
                     }
@@ -113,7 +120,7 @@ pub fn interact_with_str_all_button(
         (Changed<Interaction>, With<AllStrButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -123,10 +130,10 @@ pub fn interact_with_str_all_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
-                match status_point.all() {
+                match hero.all_state_point() {
                     Ok(point) => {
                         buffer.strength += point;
                     }
@@ -145,7 +152,7 @@ pub fn interact_with_def_all_button(
         (Changed<Interaction>, With<AllDefButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -155,10 +162,10 @@ pub fn interact_with_def_all_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
-                match status_point.all() {
+                match hero.all_state_point() {
                     Ok(point) => {
                         buffer.defense += point;
                     }
@@ -177,7 +184,7 @@ pub fn interact_with_agi_all_button(
         (Changed<Interaction>, With<AllAgiButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -187,10 +194,10 @@ pub fn interact_with_agi_all_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
-                match status_point.all() {
+                match hero.all_state_point() {
                     Ok(point) => {
                         buffer.agi += point;
                     }
@@ -209,7 +216,7 @@ pub fn interact_with_confirm_button(
         (Changed<Interaction>, With<ConfirmButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<(&mut Strength, &mut Defense, &mut Critical), With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -219,14 +226,11 @@ pub fn interact_with_confirm_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok((mut strength, mut defense, mut critical)) = hero_query.get_single_mut()
-                else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
 
-                strength.upgrade(buffer.strength);
-                defense.upgrade(buffer.defense);
-                critical.upgrade(buffer.agi);
+                hero.allocate(buffer.allocation());
 
                 buffer.reset();
             }
@@ -242,7 +246,7 @@ pub fn interact_with_reset_button(
         (Changed<Interaction>, With<ResetButton>),
     >,
     mut buffer_query: Query<&mut UpgradeBuffer>,
-    mut hero_query: Query<&mut StatePoint, With<Hero>>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
 ) {
     for (interaction, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -252,11 +256,11 @@ pub fn interact_with_reset_button(
                 let Ok(mut buffer) = buffer_query.get_single_mut() else {
                     continue;
                 };
-                let Ok(mut status_point) = hero_query.get_single_mut() else {
+                let Ok(mut hero) = hero_query.get_single_mut() else {
                     continue;
                 };
 
-                status_point.plus(buffer.get_total_point());
+                hero.plus_state_point(buffer.get_total_point());
 
                 buffer.reset();
             }
@@ -266,18 +270,54 @@ pub fn interact_with_reset_button(
     }
 }
 
+// Refunds every allocated point for gold, pending buffer points are left alone
+pub fn interact_with_respec_button(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<RespecButton>)>,
+    mut hero_query: Query<&mut BaseStates, With<Hero>>,
+    mut money_query: Query<&mut Money, With<Player>>,
+    mut terminal_query: Query<&mut AutoTerminalText>,
+) {
+    for interaction in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let (Ok(mut hero), Ok(mut money)) =
+            (hero_query.get_single_mut(), money_query.get_single_mut())
+        else {
+            continue;
+        };
+
+        let cost = hero.respec_cost();
+        let message = if hero.allocated().total() == 0 {
+            "No points to respec".to_string()
+        } else if money.0 < cost {
+            format!("Respec costs ${}", cost)
+        } else {
+            match hero.respec() {
+                Ok(point) => {
+                    money.0 -= cost;
+                    format!("Refunded {} points for ${}", point, cost)
+                }
+                Err(err) => format!("Error: {:?}", err),
+            }
+        };
+
+        if let Ok(mut auto_text) = terminal_query.get_single_mut() {
+            auto_text.text = vec![message];
+        }
+    }
+}
+
 pub fn interact_with_restart_button(
     mut button_query: Query<&Interaction, (Changed<Interaction>, With<RestartButton>)>,
     mut spawn_confirm_event: EventWriter<SpawnRestartConfirmPopupEvent>,
     mut status_board_state: ResMut<NextState<OverlayStatusBoardState>>,
 ) {
     for interaction in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                status_board_state.set(OverlayStatusBoardState::Popup);
-                spawn_confirm_event.send(SpawnRestartConfirmPopupEvent);
-            }
-            _ => (),
+        if *interaction == Interaction::Pressed {
+            status_board_state.set(OverlayStatusBoardState::Popup);
+            spawn_confirm_event.send(SpawnRestartConfirmPopupEvent);
         }
     }
 }
@@ -319,31 +359,140 @@ pub fn click_to_close(
         info!("Exited status board with mouse")
     }
 
-    if buttons.any_just_released([MouseButton::Left]) {
-        if button_query
+    if buttons.any_just_released([MouseButton::Left])
+        && button_query
             .iter_mut()
-            .map(|interaction| *interaction == Interaction::None)
-            .all(|x| x)
-        {
-            next_board_state.set(OverlayStatusBoardState::Closed);
-            next_simulation_state.set(SimulationState::Running);
-            info!("Exited status board with mouse")
-        }
+            .all(|interaction| *interaction == Interaction::None)
+    {
+        next_board_state.set(OverlayStatusBoardState::Closed);
+        next_simulation_state.set(SimulationState::Running);
+        info!("Exited status board with mouse")
     }
 
-    if touches.any_just_pressed() {
-        if button_query
+    if touches.any_just_pressed()
+        && button_query
+            .iter_mut()
+            .all(|interaction| *interaction == Interaction::None)
+        && status_button_query
             .iter_mut()
-            .map(|interaction| *interaction == Interaction::None)
-            .all(|x| x)
-            && status_button_query
-                .iter_mut()
-                .map(|interaction| *interaction == Interaction::None)
-                .all(|x| x)
-        {
-            next_board_state.set(OverlayStatusBoardState::Closed);
-            next_simulation_state.set(SimulationState::Running);
-            info!("Exited status board with touch")
+            .all(|interaction| *interaction == Interaction::None)
+    {
+        next_board_state.set(OverlayStatusBoardState::Closed);
+        next_simulation_state.set(SimulationState::Running);
+        info!("Exited status board with touch")
+    }
+}
+
+pub fn interact_with_battle_log_button(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<BattleLogButton>)>,
+    mut panel_query: Query<&mut Style, (With<BattleLogPanel>, Without<BagGrid>)>,
+    mut grid_query: Query<&mut Style, (With<BagGrid>, Without<BattleLogPanel>)>,
+) {
+    for interaction in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let (Ok(mut panel), Ok(mut grid)) =
+            (panel_query.get_single_mut(), grid_query.get_single_mut())
+        else {
+            warn!("battle log panel not found");
+            continue;
+        };
+
+        // the log takes the place of the bag while opened
+        if panel.display == Display::None {
+            panel.display = Display::Flex;
+            grid.display = Display::None;
+        } else {
+            panel.display = Display::None;
+            grid.display = Display::Grid;
+        }
+    }
+}
+
+pub fn interact_with_export_log_button(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<ExportLogButton>)>,
+    mut terminal_query: Query<&mut AutoTerminalText>,
+    history: Res<BattleHistory>,
+) {
+    for interaction in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let message = match history.to_json() {
+            Ok(json) => export_battle_history(json),
+            Err(err) => format!("Failed to export battle log: {}", err),
+        };
+
+        info!("{}", message);
+        if let Ok(mut auto_text) = terminal_query.get_single_mut() {
+            auto_text.text = vec![message];
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export_battle_history(json: String) -> String {
+    match std::fs::write(BATTLE_HISTORY_EXPORT_PATH, json) {
+        Ok(_) => format!("Battle log exported to {}", BATTLE_HISTORY_EXPORT_PATH),
+        Err(err) => format!("Failed to export battle log: {}", err),
+    }
+}
+
+// no file system on the web, the log goes to the browser console instead
+#[cfg(target_arch = "wasm32")]
+fn export_battle_history(json: String) -> String {
+    info!("{}", json);
+    "Battle log printed to the console".to_string()
+}
+
+pub fn scroll_battle_log(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut list_query: Query<(&mut BattleLogList, &mut Style, &Parent, &Node)>,
+    node_query: Query<&Node>,
+) {
+    for mouse_wheel_event in mouse_wheel_events.read() {
+        for (mut list, mut style, parent, list_node) in list_query.iter_mut() {
+            let Ok(container) = node_query.get(parent.get()) else {
+                continue;
+            };
+            let max_scroll = (list_node.size().y - container.size().y).max(0.);
+
+            let dy = match mouse_wheel_event.unit {
+                MouseScrollUnit::Line => mouse_wheel_event.y * 20.,
+                MouseScrollUnit::Pixel => mouse_wheel_event.y,
+            };
+
+            list.position = (list.position + dy).clamp(-max_scroll, 0.);
+            style.top = Val::Px(list.position);
+        }
+    }
+}
+
+// dragging a finger over the log panel scrolls it like the shop lists
+pub fn touch_scroll_battle_log(
+    touches: Res<Touches>,
+    mut list_query: Query<(&mut BattleLogList, &mut Style, &Parent, &Node)>,
+    panel_query: Query<(&Interaction, &Node)>,
+) {
+    for touch in touches.iter() {
+        let Some(touch) = touches.get_pressed(touch.id()) else {
+            continue;
+        };
+
+        for (mut list, mut style, parent, list_node) in list_query.iter_mut() {
+            let Ok((interaction, container)) = panel_query.get(parent.get()) else {
+                continue;
+            };
+            if *interaction != Interaction::Pressed {
+                continue;
+            }
+            let max_scroll = (list_node.size().y - container.size().y).max(0.);
+
+            list.position = (list.position + touch.delta().y).clamp(-max_scroll, 0.);
+            style.top = Val::Px(list.position);
         }
     }
 }
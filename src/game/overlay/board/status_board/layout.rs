@@ -1,9 +1,11 @@
 use crate::audio_effects::ButtonClickEffect;
+use crate::game::character::component::{BaseStates, Hero, HeroClass};
 use crate::game::overlay::board::components::*;
 use crate::game::overlay::board::styles::*;
 use crate::state::SimulationState;
 use crate::state::TerminalState;
 
+use crate::game::player::ConsumableBar;
 use crate::game::TerminalBundle;
 use crate::IPHONE_BAND_SPACER_HEIGHT;
 use bevy::prelude::*;
@@ -41,6 +43,7 @@ pub fn spawn_status_board(
     mut next_input_state: ResMut<NextState<SimulationState>>,
     mut next_terminal_state: ResMut<NextState<TerminalState>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
+    hero_query: Query<(&BaseStates, &HeroClass), With<Hero>>,
 ) {
     info!("Status Board Spawned");
     // let status_pack = StatusPack::new(strength, defense, critical, state_point, money);
@@ -53,7 +56,14 @@ pub fn spawn_status_board(
 
     let window_height = window_query.single().height();
 
-    build_status_board(&mut commands, &asset_server, window_height);
+    let hero = hero_query.get_single().ok();
+    build_status_board(
+        &mut commands,
+        &asset_server,
+        window_height,
+        hero.map(|(base, _)| base),
+        hero.map(|(_, class)| *class),
+    );
     next_terminal_state.set(TerminalState::Auto);
     next_input_state.set(SimulationState::Pause);
 }
@@ -75,6 +85,8 @@ fn build_status_board(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     window_height: f32,
+    hero: Option<&BaseStates>,
+    hero_class: Option<HeroClass>,
 ) {
     let layout_entity = commands
         .spawn((
@@ -169,42 +181,9 @@ fn build_status_board(
         ))
         .id();
 
-    let restart_button = commands
-        .spawn((
-            ButtonBundle {
-                background_color: NORMAL_BUTTON_COLOR.into(),
-                style: Style {
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    width: Val::Px(100.0),
-                    height: Val::Px(35.0),
-                    margin: UiRect::all(Val::Px(10.)),
-                    ..Style::DEFAULT
-                },
-                ..Default::default()
-            },
-            RestartButton {},
-            ButtonClickEffect,
-            StatusBoardZone {},
-        ))
-        .with_children(|parent| {
-            parent.spawn((TextBundle {
-                text: Text {
-                    sections: vec![TextSection::new(
-                        "Restart".to_string(),
-                        TextStyle {
-                            font_size: 15.0,
-                            color: Color::WHITE,
-                            ..default()
-                        },
-                    )],
-                    justify: JustifyText::Right,
-                    ..default()
-                },
-                ..default()
-            },));
-        })
-        .id();
+    let restart_button = build_feature_button(commands, "Restart", RestartButton {});
+    let battle_log_button = build_feature_button(commands, "Battle Log", BattleLogButton);
+    let export_log_button = build_feature_button(commands, "Export Log", ExportLogButton);
 
     let top_assets = commands
         .spawn((
@@ -271,7 +250,7 @@ fn build_status_board(
         .spawn((TextBundle {
             text: Text {
                 sections: vec![TextSection::new(
-                    format!("Exp until next lv"),
+                    "Exp until next lv".to_string(),
                     TextStyle {
                         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                         font_size: 10.0,
@@ -360,7 +339,7 @@ fn build_status_board(
                 },
                 text: Text {
                     sections: vec![TextSection::new(
-                        format!("$"),
+                        "$".to_string(),
                         TextStyle {
                             font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                             font_size: 30.0,
@@ -376,6 +355,27 @@ fn build_status_board(
         ))
         .id();
 
+    let class_text = commands
+        .spawn((
+            Name::new("Hero Class"),
+            TextBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Px(20.)),
+                    ..default()
+                },
+                text: Text::from_section(
+                    hero_class.map_or(String::new(), |class| class.to_string()),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                        font_size: 20.0,
+                        color: hero_class.map_or(Color::WHITE, |class| class.tint()),
+                    },
+                ),
+                ..default()
+            },
+        ))
+        .id();
+
     let terminal_node = commands
         .spawn((
             Name::new("Terminal Spacer"),
@@ -883,14 +883,53 @@ fn build_status_board(
         ))
         .id();
 
+    let battle_log_panel = commands
+        .spawn((
+            Name::new("Battle Log Panel"),
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    flex_direction: FlexDirection::Column,
+                    height: Val::Px(window_height - 300.),
+                    margin: UiRect::horizontal(Val::Px(8.)),
+                    padding: UiRect::all(Val::Px(5.)),
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            Interaction::default(),
+            BattleLogPanel,
+            StatusBoardZone {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Battle Log List"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+                BattleLogList::default(),
+            ));
+        })
+        .id();
+
     commands
         .entity(layout_entity)
         .push_children(&[header_spacer, spacer]);
     commands
         .entity(spacer)
-        .push_children(&[top_container, mid_container, board]);
+        .push_children(&[top_container, mid_container, battle_log_panel, board]);
 
-    commands.entity(board).push_children(&[confirm_row, row]);
+    let stat_panel = build_stat_panel(commands, asset_server, hero);
+
+    commands
+        .entity(board)
+        .push_children(&[stat_panel, confirm_row, row]);
 
     commands
         .entity(top_container)
@@ -900,11 +939,32 @@ fn build_status_board(
         .entity(features_buttons_container)
         .add_child(features_buttons_list);
 
-    commands
-        .entity(features_buttons_list)
-        .add_child(restart_button);
+    let consumable_bar = commands
+        .spawn((
+            Name::new("Consumable Bar"),
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    margin: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                ..default()
+            },
+            ConsumableBar { in_battle: false },
+        ))
+        .id();
 
-    commands.entity(top_assets).push_children(&[money]);
+    commands.entity(features_buttons_list).push_children(&[
+        restart_button,
+        battle_log_button,
+        export_log_button,
+        consumable_bar,
+    ]);
+
+    commands
+        .entity(top_assets)
+        .push_children(&[money, class_text]);
 
     commands.entity(confirm_row).push_children(&[exp_container]);
 
@@ -928,3 +988,176 @@ fn build_status_board(
     // commands.entity(def_stat).push_children(&[def_display]);
     // commands.entity(agi_stat).push_children(&[agi_display]);
 }
+
+// Str/Def/Agi with the half/all buttons feeding the UpgradeBuffer, then confirm/reset/respec
+fn build_stat_panel(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    hero: Option<&BaseStates>,
+) -> Entity {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    let (strength, defense, agi, point) = hero.map_or((0., 0., 0., 0), |hero| {
+        (hero.str(), hero.dex(), hero.agi(), hero.state_point())
+    });
+
+    let stat_panel = commands
+        .spawn((
+            Name::new("Stat Panel"),
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    width: Val::Percent(100.),
+                    ..default()
+                },
+                ..default()
+            },
+            StatusBoardZone {},
+        ))
+        .id();
+
+    let point_text = commands
+        .spawn((
+            TextBundle {
+                text: Text::from_section(
+                    format!("Point: {}", point),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..default()
+            },
+            AvailablePointText,
+        ))
+        .id();
+    let respec_button = build_feature_button(commands, "Respec", RespecButton);
+    let point_row = build_stat_row(commands, &[point_text, respec_button]);
+
+    let str_text = build_stat_text(commands, &font, format!("Str: {:.1}", strength), StrText);
+    let str_half = build_feature_button(commands, "Half", StrButton);
+    let str_all = build_feature_button(commands, "All", AllStrButton);
+    let str_row = build_stat_row(commands, &[str_text, str_half, str_all]);
+
+    let def_text = build_stat_text(commands, &font, format!("Def: {:.1}", defense), DefText);
+    let def_half = build_feature_button(commands, "Half", DefButton);
+    let def_all = build_feature_button(commands, "All", AllDefButton);
+    let def_row = build_stat_row(commands, &[def_text, def_half, def_all]);
+
+    let agi_text = build_stat_text(commands, &font, format!("Agi: {:.1}", agi), AgiText);
+    let agi_half = build_feature_button(commands, "Half", AgiButton);
+    let agi_all = build_feature_button(commands, "All", AllAgiButton);
+    let agi_row = build_stat_row(commands, &[agi_text, agi_half, agi_all]);
+
+    let reset_button = build_feature_button(commands, "Reset", ResetButton);
+    let confirm_button = build_feature_button(commands, "Confirm", ConfirmButton);
+    let confirm_button_group = build_stat_row(commands, &[reset_button, confirm_button]);
+
+    commands.entity(stat_panel).push_children(&[
+        point_row,
+        str_row,
+        def_row,
+        agi_row,
+        confirm_button_group,
+    ]);
+
+    stat_panel
+}
+
+fn build_stat_row(commands: &mut Commands, children: &[Entity]) -> Entity {
+    let row = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                ..default()
+            },
+            StatusBoardRow,
+        ))
+        .id();
+
+    commands.entity(row).push_children(children);
+    row
+}
+
+// the second section holds the pending buffer, e.g. "+ (3)"
+fn build_stat_text(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    value: String,
+    marker: impl Component,
+) -> Entity {
+    commands
+        .spawn((
+            TextBundle {
+                style: Style {
+                    width: Val::Px(130.),
+                    ..default()
+                },
+                text: Text::from_sections([
+                    TextSection::new(
+                        value,
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 20.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    TextSection::new(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 15.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                ]),
+                ..default()
+            },
+            marker,
+        ))
+        .id()
+}
+
+fn build_feature_button(commands: &mut Commands, label: &str, marker: impl Component) -> Entity {
+    commands
+        .spawn((
+            ButtonBundle {
+                background_color: NORMAL_BUTTON_COLOR.into(),
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    width: Val::Px(100.0),
+                    height: Val::Px(35.0),
+                    margin: UiRect::all(Val::Px(10.)),
+                    ..Style::DEFAULT
+                },
+                ..Default::default()
+            },
+            marker,
+            ButtonClickEffect,
+            StatusBoardZone {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        label.to_string(),
+                        TextStyle {
+                            font_size: 15.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    )],
+                    justify: JustifyText::Right,
+                    ..default()
+                },
+                ..default()
+            },));
+        })
+        .id()
+}
@@ -30,7 +30,12 @@ impl Plugin for StatusBoardPlugin {
                     interact_with_def_all_button,
                     interact_with_confirm_button,
                     interact_with_reset_button,
+                    interact_with_respec_button,
                     interact_with_restart_button,
+                    interact_with_battle_log_button,
+                    interact_with_export_log_button,
+                    scroll_battle_log,
+                    touch_scroll_battle_log,
                 )
                     .run_if(in_state(SimulationState::Pause))
                     .run_if(in_state(OverlayStatusBoardState::Opened)),
@@ -49,6 +54,7 @@ impl Plugin for StatusBoardPlugin {
                     update_bag_grid,
                     handle_selecting_grid,
                     interact_with_grid_button,
+                    update_battle_log_list,
                 )
                     .run_if(in_state(SimulationState::Pause))
                     .run_if(in_state(OverlayStatusBoardState::Opened)),
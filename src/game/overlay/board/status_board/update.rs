@@ -3,20 +3,20 @@ use bevy::prelude::*;
 use crate::{
     audio_effects::ButtonClickEffect,
     game::{
-        board::SelectGridEvent, character::component::*, overlay::board::components::*,
-        terminal::component::AutoTerminalText, Money, Player,
+        battle::BattleHistory, board::SelectGridEvent, character::component::*,
+        overlay::board::components::*, terminal::component::AutoTerminalText, Money, Player,
     },
-    model::{sub::DropEquipment, ItemType},
+    model::{progression::Progression, sub::DropEquipment, ItemType},
     preload::images::EquipmentHandles,
 };
 
 pub fn update_str_text(
     mut str_text_query: Query<&mut Text, With<StrText>>,
-    player_query: Query<&Strength, (Changed<Strength>, With<Hero>)>,
+    player_query: Query<&BaseStates, (Changed<BaseStates>, With<Hero>)>,
 ) {
     if let Ok(mut str_text) = str_text_query.get_single_mut() {
         if let Ok(stat) = player_query.get_single() {
-            str_text.sections[0].value = format!("Str: {0}", stat.get());
+            str_text.sections[0].value = format!("Str: {:.1}", stat.str());
         }
     }
 }
@@ -38,9 +38,9 @@ pub fn update_str_buffer_text(
                 def_text.sections[1].value = format!("+ ({})", buffer.defense);
                 agi_text.sections[1].value = format!("+ ({})", buffer.agi);
             } else {
-                str_text.sections[1].value = format!("");
-                def_text.sections[1].value = format!("");
-                agi_text.sections[1].value = format!("");
+                str_text.sections[1].value = String::new();
+                def_text.sections[1].value = String::new();
+                agi_text.sections[1].value = String::new();
             }
         }
     }
@@ -48,33 +48,33 @@ pub fn update_str_buffer_text(
 
 pub fn update_def_text(
     mut text_query: Query<&mut Text, With<DefText>>,
-    player_query: Query<&Defense, (Changed<Defense>, With<Hero>)>,
+    player_query: Query<&BaseStates, (Changed<BaseStates>, With<Hero>)>,
 ) {
     if let Ok(mut text) = text_query.get_single_mut() {
         if let Ok(stat) = player_query.get_single() {
-            text.sections[0].value = format!("Def: {0}", stat.get());
+            text.sections[0].value = format!("Def: {:.1}", stat.dex());
         }
     }
 }
 
 pub fn update_agi_text(
     mut text_query: Query<&mut Text, With<AgiText>>,
-    player_query: Query<&Critical, (Changed<Critical>, With<Hero>)>,
+    player_query: Query<&BaseStates, (Changed<BaseStates>, With<Hero>)>,
 ) {
     if let Ok(mut text) = text_query.get_single_mut() {
         if let Ok(stat) = player_query.get_single() {
-            text.sections[0].value = format!("Agi: {0}", stat.get());
+            text.sections[0].value = format!("Agi: {:.1}", stat.agi());
         }
     }
 }
 
 pub fn update_state_point_text(
     mut text_query: Query<&mut Text, With<AvailablePointText>>,
-    player_query: Query<&StatePoint, (Changed<StatePoint>, With<Hero>)>,
+    player_query: Query<&BaseStates, (Changed<BaseStates>, With<Hero>)>,
 ) {
     if let Ok(mut text) = text_query.get_single_mut() {
         if let Ok(stat) = player_query.get_single() {
-            text.sections[0].value = format!("Point: {}", stat.get());
+            text.sections[0].value = format!("Point: {}", stat.state_point());
         }
     }
 }
@@ -92,12 +92,12 @@ pub fn update_money_text(
 
 pub fn return_state_points(
     mut buffer_query: Query<&mut UpgradeBuffer, Changed<UpgradeBuffer>>,
-    mut player_query: Query<&mut StatePoint, With<Hero>>,
+    mut player_query: Query<&mut BaseStates, With<Hero>>,
 ) {
-    if let (Ok(mut buffer), Ok(mut state_point)) =
+    if let (Ok(mut buffer), Ok(mut hero)) =
         (buffer_query.get_single_mut(), player_query.get_single_mut())
     {
-        state_point.plus(buffer.get_total_point());
+        hero.plus_state_point(buffer.get_total_point());
         buffer.reset();
     }
 }
@@ -106,21 +106,26 @@ pub fn update_experience_gauge_ui(
     mut commands: Commands,
     ui_query: Query<Entity, Added<ExperienceUI>>,
     hero_query: Query<&BaseStates, With<Hero>>,
+    progression: Res<Progression>,
 ) {
     if let Ok(ui) = ui_query.get_single() {
         let hero = hero_query.get_single().expect("No Hero Spawned");
 
+        let exp = hero.exp_after_lv_up(&progression);
+        // the bar stays full once the level cap is reached
+        let require_exp = if hero.lv() >= progression.level_cap {
+            exp.max(1.)
+        } else {
+            hero.exp_req_for_next_lv(&progression)
+        };
+
         info!(
             "exp: {}, exp for next: {}, percentage: {}",
-            hero.exp_after_lv_up(),
-            hero.exp_req_for_next_lv(),
-            (hero.exp_after_lv_up() - 100.) / (hero.exp_req_for_next_lv() - 100.)
-        );
-        let child = ExperienceUI::build(
-            &mut commands,
-            hero.exp_after_lv_up(),
-            hero.exp_req_for_next_lv(),
+            exp,
+            require_exp,
+            exp / require_exp
         );
+        let child = ExperienceUI::build(&mut commands, exp, require_exp);
 
         commands.entity(ui).add_child(child);
     }
@@ -267,3 +272,36 @@ pub fn handle_selecting_grid(
 //         );
 //     }
 // }
+
+// refilled whenever a battle was added since the board was last open
+pub fn update_battle_log_list(
+    mut commands: Commands,
+    list_query: Query<(Entity, Ref<BattleLogList>)>,
+    history: Res<BattleHistory>,
+) {
+    let Ok((list, list_ref)) = list_query.get_single() else {
+        return;
+    };
+    if !list_ref.is_added() && !history.is_changed() {
+        return;
+    }
+    commands.entity(list).despawn_descendants();
+
+    let mut lines = history.to_lines();
+    if lines.is_empty() {
+        lines.push("No battle fought yet".to_string());
+    }
+
+    commands.entity(list).with_children(|parent| {
+        for line in lines {
+            parent.spawn(TextBundle::from_section(
+                line,
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        }
+    });
+}
@@ -116,7 +116,7 @@ fn spawn_credit_scene(mut commands: Commands) {
                     height: Val::Percent(100.),
                     ..default()
                 },
-                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
                 z_index: ZIndex::Global(99),
                 ..default()
             },
@@ -9,9 +9,6 @@ pub struct StatusBoardButton {}
 #[derive(Component)]
 pub struct StatusBoardButtonText {}
 
-#[derive(Component)]
-pub struct SignText {}
-
 #[derive(Component)]
 pub struct TurnsText {}
 
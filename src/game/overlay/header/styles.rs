@@ -1,8 +1,8 @@
 use bevy::prelude::*;
 
-pub const NORMAL_BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
-pub const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.50, 0.50, 0.50);
-pub const PRESSED_BUTTON_COLOR: Color = Color::rgb(0.0, 0.0, 0.0);
+pub const NORMAL_BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON_COLOR: Color = Color::srgb(0.50, 0.50, 0.50);
+pub const PRESSED_BUTTON_COLOR: Color = Color::srgb(0.0, 0.0, 0.0);
 
 pub fn get_main_menu_style() -> Style {
     Style {
@@ -114,7 +114,7 @@ pub fn update_in_danger_display(
                 *header = Color::srgba(1., 0.3, 0.3, 0.7).into();
             } else {
                 turns_text.sections = vec![TextSection::new(
-                    format!("Safe"),
+                    "Safe".to_string(),
                     TextStyle {
                         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                         font_size: 30.0,
@@ -18,7 +18,7 @@ pub fn next_sentance_in_auto(
     >,
     mut write_terminal_event: EventWriter<WriteTerminalEvent>,
 ) {
-    for (input_entity, auto, queue, _queue_timer, style, mut pause_timer) in
+    for (_input_entity, auto, queue, _queue_timer, _style, mut pause_timer) in
         text_input_query.iter_mut()
     {
         if queue.0.is_empty() {
@@ -103,7 +103,7 @@ pub fn handle_sentance_pause_timer(
     mut inner_text: InnerText,
     time: Res<Time>,
 ) {
-    for (input_entity, auto, mut queue, _queue_timer, style, mut pause_timer) in
+    for (input_entity, _auto, mut queue, _queue_timer, style, mut pause_timer) in
         text_input_query.iter_mut()
     {
         // info!(
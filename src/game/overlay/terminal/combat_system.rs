@@ -1,4 +1,4 @@
-use crate::game::TurnFlag;
+use crate::game::{battle::BattleSpeed, TurnFlag};
 
 use super::*;
 
@@ -18,7 +18,7 @@ pub fn handle_click_in_combat(
     touches: Res<Touches>,
     mut battle_query: Query<&mut TurnFlag, Without<TerminalQueue>>,
 ) {
-    for (input_entity, terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut() {
+    for (input_entity, _terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut() {
         let Some(mut text) = inner_text.get_mut(input_entity) else {
             continue;
         };
@@ -32,12 +32,12 @@ pub fn handle_click_in_combat(
             continue;
         };
 
-        if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
-            || touches.any_just_pressed()
+        if (keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
+            || touches.any_just_pressed())
+            && !first_item.is_empty()
+            && !turn_flag.is_all_ready()
         {
-            if !first_item.is_empty() && !turn_flag.is_all_ready() {
-                clear_queue_container(&mut text, first_item, style);
-            }
+            clear_queue_container(&mut text, first_item, style);
         }
     }
 }
@@ -56,9 +56,10 @@ pub fn next_sentance_combat(
     mut inner_text: InnerText,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     touches: Res<Touches>,
+    speed: Res<BattleSpeed>,
     mut battle_query: Query<&mut TurnFlag, Without<TerminalQueue>>,
 ) {
-    for (input_entity, terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut() {
+    for (input_entity, _terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut() {
         let Some(mut text) = inner_text.get_mut(input_entity) else {
             continue;
         };
@@ -72,9 +73,7 @@ pub fn next_sentance_combat(
             continue;
         };
 
-        if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
-            || touches.any_just_pressed()
-        {
+        if speed.is_pressed(&keyboard_input, &touches) {
             info!(
                 "first item: {:?}, turn_flag: {}",
                 first_item.is_empty(),
@@ -95,7 +94,7 @@ pub fn set_ready_for_next_turn(
     mut text_input_query: Query<(&Terminal, &mut TerminalQueue), Changed<TerminalQueue>>,
     mut battle_query: Query<&mut TurnFlag, Without<TerminalQueue>>,
 ) {
-    for (terminal, mut queue) in text_input_query.iter_mut() {
+    for (_terminal, mut queue) in text_input_query.iter_mut() {
         let Some(first_item) = queue.0.front_mut() else {
             continue;
         };
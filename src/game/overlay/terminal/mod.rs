@@ -13,6 +13,7 @@ use bevy::{
 use component::AutoTerminalText;
 
 use crate::{
+    game::battle::BattleSpeed,
     helper::string_to_char,
     state::{OverlayCombatState, OverlayStatusBoardState, TerminalState},
 };
@@ -98,13 +99,11 @@ const CURSOR_HANDLE: Handle<Font> = Handle::weak_from_u128(10482756907980398621)
 /// Add this to a `NodeBundle`.
 ///
 /// Examples:
-/// ```rust
-/// # use bevy::prelude::*;
-// / use bevy_simple_text_input::TerminalBundle;
-// / fn setup(mut commands: Commands) {
-// /     commands.spawn((NodeBundle::default(), TerminalBundle::default()));
-// / }
-// / ```
+/// ```ignore
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((NodeBundle::default(), TerminalBundle::new(22.)));
+/// }
+/// ```
 #[derive(Bundle)]
 pub struct TerminalBundle {
     text_style: TerminalTextStyle,
@@ -121,7 +120,7 @@ impl TerminalBundle {
         Self {
             text_style: TerminalTextStyle(TextStyle {
                 font_size,
-                color: Color::rgb(0.3, 0.3, 0.3),
+                color: Color::srgb(0.3, 0.3, 0.3),
                 ..default()
             }),
             cursor_timer: TerminalCursorTimer::default(),
@@ -137,7 +136,7 @@ impl TerminalBundle {
             Self {
                 text_style: TerminalTextStyle(TextStyle {
                     font_size,
-                    color: Color::rgb(0.3, 0.3, 0.3),
+                    color: Color::srgb(0.3, 0.3, 0.3),
                     ..default()
                 }),
                 cursor_timer: TerminalCursorTimer::default(),
@@ -212,19 +211,18 @@ pub struct Terminal;
 #[derive(Component)]
 struct TerminalInner;
 
-#[derive(Component)]
+#[derive(Component, Default)]
 pub struct TerminalQueue(VecDeque<VecDeque<char>>);
 
-impl Default for TerminalQueue {
-    fn default() -> Self {
-        TerminalQueue(VecDeque::from(VecDeque::new()))
-        // TerminalQueue(VecDeque::from([string_to_char("{".to_string())]))
-    }
-}
 impl TerminalQueue {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    // the current sentence is fully typed and waits for a tap
+    pub fn is_sentence_done(&self) -> bool {
+        self.0.front().is_some_and(|sentence| sentence.is_empty())
+    }
 }
 
 /// A convenience parameter for dealing with a `Terminal`'s inner `Text` entity.
@@ -357,9 +355,17 @@ fn push_queue_to_inner(
     >,
     mut inner_text: InnerText,
     time: Res<Time>,
+    speed: Res<BattleSpeed>,
+    combat_state: Res<State<OverlayCombatState>>,
 ) {
+    // battle speed only applies to the battle terminal
+    let delta = match combat_state.get() {
+        OverlayCombatState::Opened => speed.scale(time.delta()),
+        _ => time.delta(),
+    };
+
     for (input_entity, mut queue, mut queue_timer, style) in text_input_query.iter_mut() {
-        if queue_timer.timer.tick(time.delta()).finished() {
+        if queue_timer.timer.tick(delta).finished() {
             let Some(first_item) = queue.0.front_mut() else {
                 continue;
             };
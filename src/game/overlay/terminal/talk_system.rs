@@ -1,4 +1,4 @@
-use crate::game::TalkFlag;
+use crate::game::{battle::BattleSpeed, TalkFlag};
 
 use super::*;
 
@@ -39,7 +39,7 @@ pub fn handle_click_in_talking(
     if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
         || touches.any_just_pressed()
     {
-        for (input_entity, terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut()
+        for (input_entity, _terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut()
         {
             let Some(mut text) = inner_text.get_mut(input_entity) else {
                 continue;
@@ -75,12 +75,11 @@ pub fn next_sentance_in_talking(
     mut inner_text: InnerText,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     touches: Res<Touches>,
+    speed: Res<BattleSpeed>,
     mut battle_query: Query<&TalkFlag, Without<TerminalQueue>>,
 ) {
-    if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::ArrowLeft])
-        || touches.any_just_pressed()
-    {
-        for (input_entity, terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut()
+    if speed.is_pressed(&keyboard_input, &touches) {
+        for (input_entity, _terminal, mut queue, _queue_timer, style) in text_input_query.iter_mut()
         {
             let Some(mut text) = inner_text.get_mut(input_entity) else {
                 continue;
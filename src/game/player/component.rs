@@ -1,6 +1,19 @@
+use bevy::ecs::system::SystemParam;
+
 use crate::{
+    error::UserInputError,
     game::preload::Weather,
-    model::{helmet::HelmetData, shoes::ShoesData, ItemType},
+    model::{
+        affix::AffixKind,
+        gem::{GemBonus, GemBook},
+        helmet::HelmetData,
+        recipe::{RecipeBook, RecipeData},
+        set::{ActiveSet, SetBook},
+        shoes::ShoesData,
+        sub::Element,
+        ItemType,
+    },
+    preload::data::{GemPreloadHandler, RecipePreloadHandler, SetPreloadHandler},
 };
 
 use super::*;
@@ -15,6 +28,9 @@ pub struct DecendingHero;
 #[derive(Component, Reflect)]
 pub struct Storage {
     pub items: std::collections::HashMap<ItemType, Vec<DropEquipment>>,
+    pub consumables: std::collections::HashMap<u32, u32>,
+    pub gems: std::collections::HashMap<u32, u32>,
+    pub materials: u32,
 }
 
 #[derive(Component, Default, Reflect, PartialEq)]
@@ -24,14 +40,191 @@ impl Storage {
     pub fn empty() -> Self {
         Self {
             items: std::collections::HashMap::new(),
+            consumables: std::collections::HashMap::new(),
+            gems: std::collections::HashMap::new(),
+            materials: 0,
         }
     }
     pub fn new(player_data: &PlayerData) -> Self {
         Self {
             items: player_data.owned.clone(),
+            consumables: player_data.consumables.clone(),
+            gems: player_data.gems.clone(),
+            materials: player_data.materials,
+        }
+    }
+
+    pub fn consumable_count(&self, id: u32) -> u32 {
+        self.consumables.get(&id).copied().unwrap_or(0)
+    }
+
+    pub fn add_consumable(&mut self, id: u32, n: u32) {
+        let count = self.consumables.entry(id).or_insert(0);
+        *count = count.saturating_add(n);
+    }
+
+    // return false when there is nothing left to take
+    pub fn take_consumable(&mut self, id: u32) -> bool {
+        match self.consumables.get_mut(&id) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                if *count == 0 {
+                    self.consumables.remove(&id);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn gem_count(&self, id: u32) -> u32 {
+        self.gems.get(&id).copied().unwrap_or(0)
+    }
+
+    pub fn add_gem(&mut self, id: u32) {
+        *self.gems.entry(id).or_insert(0) += 1;
+    }
+
+    pub fn take_gem(&mut self, id: u32) -> bool {
+        match self.gems.get_mut(&id) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                if *count == 0 {
+                    self.gems.remove(&id);
+                }
+                true
+            }
+            _ => false,
         }
     }
 
+    // a duplicate is merged into the owned copy
+    pub fn add_item(&mut self, item: &DropEquipment) {
+        let list = self.items.entry(item.item_type.clone()).or_default();
+        if let Some(owned) = list.iter_mut().find(|owned| owned.id == item.id) {
+            owned.merge(item);
+        } else {
+            list.push(item.clone());
+        }
+    }
+
+    pub fn remove_item(&mut self, item: &DropEquipment) -> Option<DropEquipment> {
+        let list = self.items.get_mut(&item.item_type)?;
+        let index = list.iter().position(|owned| owned.id == item.id)?;
+        Some(list.remove(index))
+    }
+
+    // everything of that type under the level, equipped items are never included
+    pub fn sellable_below(
+        &self,
+        item_type: &ItemType,
+        lv: u32,
+        belt: &EquipmentBelt,
+    ) -> Vec<DropEquipment> {
+        let equipped = belt.equipped();
+        self.items
+            .get(item_type)
+            .into_iter()
+            .flatten()
+            .filter(|item| item.level < lv)
+            .filter(|item| !equipped.contains(&(item.item_type.clone(), item.id)))
+            .cloned()
+            .collect()
+    }
+
+    // money and materials the next upgrade takes, materials stand in for a spare copy
+    pub fn upgrade_cost(&self, item: &DropEquipment, tier: u32) -> (u64, u32) {
+        let materials = DropEquipment::upgrade_materials(tier);
+        if item.copies == 0 && self.materials >= materials {
+            (item.upgrade_cost(tier) / 2, materials)
+        } else {
+            (item.upgrade_cost(tier), 0)
+        }
+    }
+
+    // recipe inputs on hand, a stack counts with its spare copies and equipped items stay out
+    pub fn craft_ingredients(
+        &self,
+        recipe: &RecipeData,
+        catalog: &[(ItemType, u32, u32)],
+        belt: &EquipmentBelt,
+    ) -> Vec<DropEquipment> {
+        let equipped = belt.equipped();
+        let mut ingredients: Vec<DropEquipment> = self
+            .items
+            .get(&recipe.item_type)
+            .into_iter()
+            .flatten()
+            .filter(|item| {
+                catalog.iter().any(|(item_type, id, tier)| {
+                    *item_type == item.item_type && *id == item.id && *tier == recipe.tier
+                })
+            })
+            .filter(|item| !equipped.contains(&(item.item_type.clone(), item.id)))
+            .cloned()
+            .collect();
+        // the weakest go first
+        ingredients.sort_by_key(|item| item.level);
+        ingredients
+    }
+
+    pub fn ingredient_count(
+        &self,
+        recipe: &RecipeData,
+        catalog: &[(ItemType, u32, u32)],
+        belt: &EquipmentBelt,
+    ) -> u32 {
+        self.craft_ingredients(recipe, catalog, belt)
+            .iter()
+            .map(|item| item.copies + 1)
+            .sum()
+    }
+
+    pub fn can_craft(
+        &self,
+        recipe: &RecipeData,
+        catalog: &[(ItemType, u32, u32)],
+        belt: &EquipmentBelt,
+    ) -> bool {
+        self.ingredient_count(recipe, catalog, belt) >= recipe.count
+            && self.materials >= recipe.materials
+    }
+
+    // takes the inputs out of storage, the outcome is rolled by the caller
+    pub fn craft(
+        &mut self,
+        recipe: &RecipeData,
+        catalog: &[(ItemType, u32, u32)],
+        belt: &EquipmentBelt,
+    ) -> Result<(), UserInputError> {
+        if !self.can_craft(recipe, catalog, belt) {
+            return Err(UserInputError());
+        }
+
+        let mut needed = recipe.count;
+        for item in self.craft_ingredients(recipe, catalog, belt) {
+            if needed == 0 {
+                break;
+            }
+            if item.copies < needed {
+                needed -= item.copies + 1;
+                self.remove_item(&item);
+            } else if let Some(owned) = self.owned_mut(&item) {
+                owned.copies -= needed;
+                needed = 0;
+            }
+        }
+        self.materials -= recipe.materials;
+        Ok(())
+    }
+
+    // the stored copy is the one that gets saved, the belt only holds a clone
+    pub fn owned_mut(&mut self, item: &DropEquipment) -> Option<&mut DropEquipment> {
+        self.items
+            .get_mut(&item.item_type)
+            .and_then(|list| list.iter_mut().find(|owned| owned.id == item.id))
+    }
+
     pub fn is_owned(&self, data: &dyn EquipmentData) -> Option<DropEquipment> {
         if let Some(item_list) = self.items.get(&data.item_type()) {
             item_list.iter().find(|item| item.id == data.id()).cloned()
@@ -47,6 +240,10 @@ pub struct EquipmentBelt {
     pub armor: Option<Armor>,
     pub helmet: Option<Helmet>,
     pub shoes: Option<Shoes>,
+    // recomputed from the set book whenever the belt changes
+    pub sets: Vec<ActiveSet>,
+    // from the gems socketed into the weapon, kept in sync like the sets
+    pub gem_bonus: GemBonus,
 }
 
 impl EquipmentBelt {
@@ -56,10 +253,126 @@ impl EquipmentBelt {
             armor: None,
             helmet: None,
             shoes: None,
+            sets: Vec::new(),
+            gem_bonus: GemBonus::default(),
         }
     }
 
-    pub fn save() {}
+    pub fn equipped(&self) -> Vec<(ItemType, u32)> {
+        let mut equipped = Vec::new();
+        if let Some(item) = &self.weapon {
+            equipped.push((ItemType::Weapon, item.id()));
+        }
+        if let Some(item) = &self.armor {
+            equipped.push((ItemType::Armor, item.id()));
+        }
+        if let Some(item) = &self.helmet {
+            equipped.push((ItemType::Helmet, item.id()));
+        }
+        if let Some(item) = &self.shoes {
+            equipped.push((ItemType::Shoes, item.id()));
+        }
+        equipped
+    }
+
+    pub fn active_sets(&self, book: &SetBook) -> Vec<ActiveSet> {
+        book.active(&self.equipped())
+    }
+
+    pub fn socket_bonus(&self, book: &GemBook) -> GemBonus {
+        self.weapon
+            .as_ref()
+            .map(|weapon| book.bonus(&weapon.feature.sockets))
+            .unwrap_or_default()
+    }
+
+    // the belt as it would be with the item equipped, bonuses included
+    pub fn with_item(&self, assets: &EquipmentAssets, item: &DropEquipment) -> EquipmentBelt {
+        let mut belt = self.clone();
+        match item.item_type {
+            ItemType::Weapon => belt.weapon = Weapon::from(&assets.weapon, item.clone()),
+            ItemType::Armor => belt.armor = Armor::from(&assets.armor, item.clone()),
+            ItemType::Helmet => belt.helmet = Helmet::from(&assets.helmet, item.clone()),
+            ItemType::Shoes => belt.shoes = Shoes::from(&assets.shoes, item.clone()),
+            ItemType::Others => (),
+        }
+        if let Some(book) = assets.sets() {
+            belt.sets = belt.active_sets(book);
+        }
+        if let Some(book) = assets.gems() {
+            belt.gem_bonus = belt.socket_bonus(book);
+        }
+        belt
+    }
+
+    // every stat the combat and field systems read from the belt
+    pub fn compare(&self, other: &EquipmentBelt) -> Vec<StatDiff> {
+        let stats = |belt: &EquipmentBelt| {
+            [
+                ("Attack", "", belt.attack(), false),
+                ("Defend", "", belt.defense(), false),
+                ("Crit", "%", belt.crit_bonus(), false),
+                ("Crit Resist", "%", belt.crit_resist(), false),
+                (
+                    "Elemental",
+                    "%",
+                    (belt.elemental_multiplier() - 1.) * 100.,
+                    false,
+                ),
+                ("Speed", "%", (belt.walk_speed() - 1.) * 100., false),
+                ("Encounter", "%", (belt.encounter_rate() - 1.) * 100., true),
+                ("Exp", "%", belt.affix(&AffixKind::ExpGain) as f32, false),
+            ]
+        };
+
+        stats(self)
+            .into_iter()
+            .zip(stats(other))
+            .map(
+                |((name, unit, current, lower_is_better), (_, _, candidate, _))| StatDiff {
+                    name,
+                    unit,
+                    current,
+                    candidate,
+                    lower_is_better,
+                },
+            )
+            // nothing to say about stats neither belt has
+            .filter(|diff| diff.current != 0. || diff.candidate != 0.)
+            .collect()
+    }
+
+    // the element line plus one line per stat, green when better and red when worse
+    pub fn comparison_sections(
+        &self,
+        other: &EquipmentBelt,
+        style: &TextStyle,
+    ) -> Vec<TextSection> {
+        let (current, candidate) = (self.weapon_element(), other.weapon_element());
+        let mut sections = vec![TextSection::new(
+            format!("\nElement: {:?} -> {:?}", current, candidate),
+            style.clone(),
+        )];
+
+        for diff in self.compare(other) {
+            sections.push(TextSection::new(
+                format!("\n{}", diff),
+                TextStyle {
+                    color: diff.color(),
+                    ..style.clone()
+                },
+            ));
+        }
+        sections
+    }
+
+    pub fn set_attack(&self) -> f32 {
+        self.sets.iter().map(|set| set.attack).sum()
+    }
+
+    pub fn set_defense(&self) -> f32 {
+        self.sets.iter().map(|set| set.defense).sum()
+    }
 
     pub fn is_equipped(&self, data: &dyn EquipmentData) -> Option<DropEquipment> {
         match data.item_type() {
@@ -105,20 +418,244 @@ impl EquipmentBelt {
     }
 
     pub fn attack(&self) -> f32 {
-        if let Some(weapon) = &self.weapon {
+        let weapon = if let Some(weapon) = &self.weapon {
             weapon.attack()
         } else {
             0.
+        };
+        weapon + self.set_attack() + self.gem_bonus.attack + self.affix(&AffixKind::Attack) as f32
+    }
+
+    pub fn crit_bonus(&self) -> f32 {
+        self.gem_bonus.crit_rate + self.affix(&AffixKind::CritRate) as f32
+    }
+
+    // summed over every equipped item
+    pub fn affix(&self, kind: &AffixKind) -> u32 {
+        [
+            self.weapon.as_ref().map(|item| &item.feature),
+            self.armor.as_ref().map(|item| &item.feature),
+            self.helmet.as_ref().map(|item| &item.feature),
+            self.shoes.as_ref().map(|item| &item.feature),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|feature| feature.affix(kind))
+        .sum()
+    }
+
+    // only hits with an element get the elemental damage affix
+    pub fn elemental_multiplier(&self) -> f32 {
+        if self.weapon_element() == Element::Neutral {
+            1.
+        } else {
+            1. + self.affix(&AffixKind::ElementalDamage) as f32 / 100.
         }
     }
 
+    pub fn crit_resist(&self) -> f32 {
+        self.helmet
+            .as_ref()
+            .map_or(0., |helmet| helmet.crit_resist())
+    }
+
+    pub fn walk_speed(&self) -> f32 {
+        let shoes = self.shoes.as_ref().map_or(1., |shoes| shoes.walk_speed());
+        shoes * (1. + self.affix(&AffixKind::WalkSpeed) as f32 / 100.)
+    }
+
+    pub fn encounter_rate(&self) -> f32 {
+        self.shoes
+            .as_ref()
+            .map_or(1., |shoes| shoes.encounter_rate())
+    }
+
     pub fn defense(&self) -> f32 {
-        if let Some(armor) = &self.armor {
+        let armor = if let Some(armor) = &self.armor {
             armor.defense()
         } else {
             0.
+        };
+        armor + self.set_defense() + self.affix(&AffixKind::Defense) as f32
+    }
+
+    pub fn weapon_element(&self) -> Element {
+        if let Some(element) = &self.gem_bonus.element {
+            return element.clone();
+        }
+        self.weapon
+            .as_ref()
+            .map(|weapon| weapon.element())
+            .unwrap_or_default()
+    }
+}
+
+// The asset books needed to put an item on a belt, see with_item
+#[derive(SystemParam)]
+pub struct EquipmentAssets<'w> {
+    pub weapon: Res<'w, Assets<WeaponData>>,
+    pub armor: Res<'w, Assets<ArmorData>>,
+    pub helmet: Res<'w, Assets<HelmetData>>,
+    pub shoes: Res<'w, Assets<ShoesData>>,
+    set_handle: Option<Res<'w, SetPreloadHandler>>,
+    set_books: Res<'w, Assets<SetBook>>,
+    gem_handle: Option<Res<'w, GemPreloadHandler>>,
+    gem_books: Res<'w, Assets<GemBook>>,
+    recipe_handle: Option<Res<'w, RecipePreloadHandler>>,
+    recipe_books: Res<'w, Assets<RecipeBook>>,
+}
+
+impl<'w> EquipmentAssets<'w> {
+    // numeric tier of the item's base data, 0 when unknown
+    pub fn tier(&self, item: &DropEquipment) -> u32 {
+        let tier = match item.item_type {
+            ItemType::Weapon => self
+                .weapon
+                .iter()
+                .find(|(_, data)| data.id == item.id)
+                .map(|(_, data)| data.tier()),
+            ItemType::Armor => self
+                .armor
+                .iter()
+                .find(|(_, data)| data.id == item.id)
+                .map(|(_, data)| data.tier()),
+            ItemType::Helmet => self
+                .helmet
+                .iter()
+                .find(|(_, data)| data.id == item.id)
+                .map(|(_, data)| data.tier()),
+            ItemType::Shoes => self
+                .shoes
+                .iter()
+                .find(|(_, data)| data.id == item.id)
+                .map(|(_, data)| data.tier()),
+            ItemType::Others => None,
+        };
+        tier.map_or(0, |tier| tier.rank())
+    }
+
+    pub fn catalog(&self) -> Vec<(ItemType, u32, u32)> {
+        equipment_catalog(&self.weapon, &self.armor, &self.helmet, &self.shoes)
+    }
+
+    // a fresh lv 1 copy of the item's base data
+    pub fn drop_equipment(&self, item_type: &ItemType, id: u32) -> Option<DropEquipment> {
+        match item_type {
+            ItemType::Weapon => self
+                .weapon
+                .iter()
+                .find(|(_, data)| data.id == id)
+                .map(|(_, data)| data.to_drop_equipment(1)),
+            ItemType::Armor => self
+                .armor
+                .iter()
+                .find(|(_, data)| data.id == id)
+                .map(|(_, data)| data.to_drop_equipment(1)),
+            ItemType::Helmet => self
+                .helmet
+                .iter()
+                .find(|(_, data)| data.id == id)
+                .map(|(_, data)| data.to_drop_equipment(1)),
+            ItemType::Shoes => self
+                .shoes
+                .iter()
+                .find(|(_, data)| data.id == id)
+                .map(|(_, data)| data.to_drop_equipment(1)),
+            ItemType::Others => None,
         }
     }
+
+    pub fn sets(&self) -> Option<&SetBook> {
+        self.set_handle
+            .as_ref()
+            .and_then(|handle| self.set_books.get(handle.id()))
+    }
+
+    pub fn gems(&self) -> Option<&GemBook> {
+        self.gem_handle
+            .as_ref()
+            .and_then(|handle| self.gem_books.get(handle.id()))
+    }
+
+    pub fn recipes(&self) -> Option<&RecipeBook> {
+        self.recipe_handle
+            .as_ref()
+            .and_then(|handle| self.recipe_books.get(handle.id()))
+    }
+}
+
+// every equipment that exists as (type, id, tier)
+pub fn equipment_catalog(
+    weapons: &Assets<WeaponData>,
+    armors: &Assets<ArmorData>,
+    helmets: &Assets<HelmetData>,
+    shoes: &Assets<ShoesData>,
+) -> Vec<(ItemType, u32, u32)> {
+    let mut catalog = Vec::new();
+    for (_, data) in weapons.iter() {
+        catalog.push((data.item_type(), data.id(), data.tier().rank()));
+    }
+    for (_, data) in armors.iter() {
+        catalog.push((data.item_type(), data.id(), data.tier().rank()));
+    }
+    for (_, data) in helmets.iter() {
+        catalog.push((data.item_type(), data.id(), data.tier().rank()));
+    }
+    for (_, data) in shoes.iter() {
+        catalog.push((data.item_type(), data.id(), data.tier().rank()));
+    }
+    catalog
+}
+
+// One belt stat before and after swapping an item in
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatDiff {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub current: f32,
+    pub candidate: f32,
+    pub lower_is_better: bool,
+}
+
+impl StatDiff {
+    pub fn is_better(&self) -> bool {
+        if self.lower_is_better {
+            self.candidate < self.current
+        } else {
+            self.candidate > self.current
+        }
+    }
+
+    pub fn is_worse(&self) -> bool {
+        self.candidate != self.current && !self.is_better()
+    }
+
+    pub fn color(&self) -> Color {
+        if self.is_better() {
+            Color::srgb(0.3, 0.9, 0.3)
+        } else if self.is_worse() {
+            Color::srgb(0.9, 0.3, 0.3)
+        } else {
+            Color::WHITE
+        }
+    }
+}
+
+impl std::fmt::Display for StatDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let arrow = if self.is_better() {
+            " ↑"
+        } else if self.is_worse() {
+            " ↓"
+        } else {
+            ""
+        };
+        write!(
+            f,
+            "{}: {:.1}{} -> {:.1}{}{}",
+            self.name, self.current, self.unit, self.candidate, self.unit, arrow
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -151,6 +688,14 @@ impl Weapon {
     pub fn attack(&self) -> f32 {
         self.feature.level as f32
     }
+
+    pub fn element(&self) -> Element {
+        self.base.element.clone()
+    }
+
+    pub fn slot(&self) -> u32 {
+        self.base.slot
+    }
 }
 
 #[derive(Clone)]
@@ -160,10 +705,6 @@ pub struct Armor {
 }
 
 impl Armor {
-    fn new(base: ArmorData, feature: DropEquipment) -> Self {
-        Self { base, feature }
-    }
-
     pub fn from(assets: &Res<Assets<ArmorData>>, eq: DropEquipment) -> Option<Armor> {
         if let Some((_, data)) = assets.iter().find(|(_, record)| eq.id == record.id) {
             Some(Self {
@@ -215,6 +756,10 @@ impl Helmet {
     pub fn defense(&self) -> f32 {
         self.feature.level as f32
     }
+
+    pub fn crit_resist(&self) -> f32 {
+        self.feature.crit_resist(self.base.tier.rank())
+    }
 }
 
 #[derive(Clone)]
@@ -244,15 +789,20 @@ impl Shoes {
     pub fn defense(&self) -> f32 {
         self.feature.level as f32
     }
+
+    pub fn walk_speed(&self) -> f32 {
+        self.feature.walk_speed(self.base.tier.rank())
+    }
+
+    pub fn encounter_rate(&self) -> f32 {
+        self.feature.encounter_rate(self.base.tier.rank())
+    }
 }
 
 #[derive(Component)]
 pub struct Player;
 
-#[derive(Component)]
-pub struct Movable;
-
-#[derive(Component, Reflect)]
+#[derive(Component, Reflect, Default)]
 pub struct Money(pub u64);
 
 impl Money {
@@ -273,7 +823,33 @@ impl Money {
     }
 }
 
-#[derive(Component, Default, PartialEq, Eq)]
+// Long term record of the run, survives fainting
+#[derive(Component, Reflect, Default, Clone, Debug)]
+pub struct Progress {
+    pub cleared_bosses: Vec<u32>,
+    pub is_completed: bool,
+}
+
+impl Progress {
+    pub fn new(data: &PlayerData) -> Self {
+        Self {
+            cleared_bosses: data.cleared_bosses.clone(),
+            is_completed: data.is_completed,
+        }
+    }
+
+    pub fn clear_boss(&mut self, id: u32) {
+        if !self.is_boss_cleared(id) {
+            self.cleared_bosses.push(id);
+        }
+    }
+
+    pub fn is_boss_cleared(&self, id: u32) -> bool {
+        self.cleared_bosses.contains(&id)
+    }
+}
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FacingSide {
     #[default]
     Right,
@@ -304,6 +880,113 @@ impl Encounter {
 
     pub fn update(&mut self, id: u32) {
         self.field = Some(id);
-        self.percentage;
     }
 }
+
+#[test]
+fn test_belt_comparison() {
+    let current = EquipmentBelt::new();
+    let mut candidate = EquipmentBelt::new();
+    candidate.gem_bonus.attack = 2.;
+
+    let diffs = current.compare(&candidate);
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].name, "Attack");
+    assert!(diffs[0].is_better());
+    assert_eq!(diffs[0].to_string(), "Attack: 0.0 -> 2.0 ↑");
+
+    // going back is a downgrade
+    assert!(candidate.compare(&current)[0].is_worse());
+    assert!(current.compare(&current).is_empty());
+}
+
+#[test]
+fn test_sellable_below_skips_equipped() {
+    let mut storage = Storage::empty();
+    storage.add_item(&DropEquipment::new(ItemType::Armor, 1, 2));
+    storage.add_item(&DropEquipment::new(ItemType::Armor, 2, 3));
+    storage.add_item(&DropEquipment::new(ItemType::Armor, 3, 9));
+
+    let mut belt = EquipmentBelt::new();
+    assert_eq!(
+        storage
+            .sellable_below(&ItemType::Armor, 5, &belt)
+            .iter()
+            .map(|item| item.id)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    // never the equipped one
+    belt.armor = Some(Armor {
+        base: ArmorData {
+            id: 2,
+            name: String::new(),
+            tier: Default::default(),
+            weight: Default::default(),
+            element: Default::default(),
+            description: String::new(),
+        },
+        feature: DropEquipment::new(ItemType::Armor, 2, 3),
+    });
+    assert_eq!(storage.sellable_below(&ItemType::Armor, 5, &belt).len(), 1);
+
+    assert_eq!(
+        storage
+            .remove_item(&DropEquipment::new(ItemType::Armor, 1, 0))
+            .map(|item| item.level),
+        Some(2)
+    );
+    assert!(storage
+        .remove_item(&DropEquipment::new(ItemType::Armor, 1, 0))
+        .is_none());
+
+    storage.materials = DropEquipment::upgrade_materials(1);
+    assert_eq!(
+        storage.upgrade_cost(&DropEquipment::new(ItemType::Armor, 2, 3), 1),
+        (
+            DropEquipment::new(ItemType::Armor, 2, 3).upgrade_cost(1) / 2,
+            3 * 2
+        )
+    );
+}
+
+#[test]
+fn test_craft_consumes_ingredients() {
+    let recipe = RecipeData {
+        id: 1,
+        item_type: ItemType::Shoes,
+        tier: 1,
+        count: 3,
+        materials: 4,
+    };
+    let catalog = vec![
+        (ItemType::Shoes, 1, 1),
+        (ItemType::Shoes, 2, 1),
+        (ItemType::Shoes, 3, 2),
+    ];
+    let belt = EquipmentBelt::new();
+
+    let mut storage = Storage::empty();
+    storage.add_item(&DropEquipment::new(ItemType::Shoes, 1, 1));
+    storage.add_item(&DropEquipment::new(ItemType::Shoes, 1, 1));
+    // wrong tier, never an input
+    storage.add_item(&DropEquipment::new(ItemType::Shoes, 3, 1));
+    storage.materials = 4;
+    assert!(!storage.can_craft(&recipe, &catalog, &belt));
+
+    storage.add_item(&DropEquipment::new(ItemType::Shoes, 2, 5));
+    storage.add_item(&DropEquipment::new(ItemType::Shoes, 2, 5));
+    storage.craft(&recipe, &catalog, &belt).unwrap();
+
+    // the lower level stack goes first, then a spare copy of the other
+    let left = &storage.items[&ItemType::Shoes];
+    assert_eq!(
+        left.iter()
+            .map(|item| (item.id, item.copies))
+            .collect::<Vec<_>>(),
+        vec![(3, 0), (2, 0)]
+    );
+    assert_eq!(storage.materials, 0);
+    assert!(storage.craft(&recipe, &catalog, &belt).is_err());
+}
@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    audio_effects::ButtonClickEffect,
+    game::{
+        battle_scene::component::{HealthBar, HeroHealthBar},
+        board::{components::StatusBoardZone, styles::NORMAL_BUTTON_COLOR},
+        character::component::{BaseStates, Hero, Turns},
+        terminal::component::AutoTerminalText,
+        Combat, WriteTerminalEvent,
+    },
+    model::{
+        consumable::{ConsumableData, ConsumableEffect},
+        progression::Progression,
+    },
+    state::OverlayCombatState,
+};
+
+use super::{Player, Storage};
+
+#[derive(Event)]
+pub struct UseConsumableEvent(pub u32);
+
+// Any button carrying this will fire a UseConsumableEvent when pressed
+#[derive(Component)]
+pub struct ConsumableButton(pub u32);
+
+#[derive(Component)]
+pub struct ConsumableCountText(pub u32);
+
+// Row that gets filled with the consumables the player is holding
+#[derive(Component)]
+pub struct ConsumableBar {
+    pub in_battle: bool,
+}
+
+// No encounter until the timer runs out
+#[derive(Component)]
+pub struct Repellent(pub Timer);
+
+impl Repellent {
+    pub fn new(sec: f32) -> Self {
+        Self(Timer::from_seconds(sec, TimerMode::Once))
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.0.finished()
+    }
+}
+
+#[derive(Component, Reflect, Clone)]
+pub struct ExpBoost {
+    pub multiplier: f32,
+    pub battles: u32,
+}
+
+impl ExpBoost {
+    // use up one battle worth of boost and return the multiplier
+    pub fn consume(&mut self) -> f32 {
+        if self.battles > 0 {
+            self.battles -= 1;
+            self.multiplier
+        } else {
+            1.
+        }
+    }
+}
+
+pub fn interact_with_consumable_button(
+    button_query: Query<(&Interaction, &ConsumableButton), Changed<Interaction>>,
+    mut use_event: EventWriter<UseConsumableEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction == Interaction::Pressed {
+            use_event.send(UseConsumableEvent(button.0));
+        }
+    }
+}
+
+pub fn use_consumable(
+    mut commands: Commands,
+    mut use_event: EventReader<UseConsumableEvent>,
+    consumable_assets: Res<Assets<ConsumableData>>,
+    mut player_query: Query<(Entity, &mut Storage), With<Player>>,
+    mut hero_query: Query<(Entity, &BaseStates, &mut Turns), With<Hero>>,
+    mut battle_query: Query<&mut Combat>,
+    mut hero_bar_query: Query<&mut HealthBar, With<HeroHealthBar>>,
+    mut auto_text_query: Query<&mut AutoTerminalText>,
+    mut write_terminal_event: EventWriter<WriteTerminalEvent>,
+    combat_state: Res<State<OverlayCombatState>>,
+    progression: Res<Progression>,
+) {
+    for ev in use_event.read() {
+        let Some((_, data)) = consumable_assets.iter().find(|(_, data)| data.id == ev.0) else {
+            warn!("No consumable found with id {}", ev.0);
+            continue;
+        };
+
+        let Ok((player_entity, mut storage)) = player_query.get_single_mut() else {
+            warn!("player not found");
+            continue;
+        };
+
+        let Ok((hero_entity, hero_state, mut turns)) = hero_query.get_single_mut() else {
+            warn!("hero not found");
+            continue;
+        };
+
+        let in_battle = *combat_state.get() == OverlayCombatState::Opened;
+
+        // Some items only make sense on one side of the battle
+        let text = if data.battle && !in_battle {
+            format!("{} can only be used in battle", data.name)
+        } else if !data.battle && in_battle {
+            format!("{} cannot be used in battle", data.name)
+        } else if !storage.take_consumable(data.id) {
+            format!("You have no {} left", data.name)
+        } else {
+            match data.effect {
+                ConsumableEffect::Heal => {
+                    if let Ok(mut battle) = battle_query.get_single_mut() {
+                        battle.player_hp_remain = hero_state
+                            .hp(&progression)
+                            .min(battle.player_hp_remain + data.value);
+                    }
+                    for mut bar in hero_bar_query.iter_mut() {
+                        bar.heal(data.value);
+                    }
+                    format!("Drank {}, recovered {:.1} hp", data.name, data.value)
+                }
+                ConsumableEffect::RestoreTurns => {
+                    turns.plus(data.value as u32);
+                    format!("Drank {}, {} turns came back", data.name, data.value)
+                }
+                ConsumableEffect::Repel => {
+                    commands
+                        .entity(player_entity)
+                        .insert(Repellent::new(data.value));
+                    format!("Used {}, monsters keep away for a while", data.name)
+                }
+                ConsumableEffect::ExpBoost => {
+                    commands.entity(hero_entity).insert(ExpBoost {
+                        multiplier: data.value,
+                        battles: data.duration,
+                    });
+                    format!(
+                        "Drank {}, next {} battles give {}x exp",
+                        data.name, data.duration, data.value
+                    )
+                }
+            }
+        };
+
+        if in_battle {
+            write_terminal_event.send(WriteTerminalEvent::new(text));
+        } else if let Ok(mut auto_text) = auto_text_query.get_single_mut() {
+            auto_text.text = vec![text];
+        }
+    }
+}
+
+pub fn tick_repellent(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Repellent), With<Player>>,
+    time: Res<Time>,
+) {
+    for (entity, mut repellent) in player_query.iter_mut() {
+        if repellent.0.tick(time.delta()).just_finished() {
+            commands.entity(entity).remove::<Repellent>();
+        }
+    }
+}
+
+pub fn update_consumable_count_text(
+    mut text_query: Query<(&mut Text, &ConsumableCountText)>,
+    player_query: Query<&Storage, (With<Player>, Changed<Storage>)>,
+) {
+    let Ok(storage) = player_query.get_single() else {
+        return;
+    };
+
+    for (mut text, count) in text_query.iter_mut() {
+        text.sections[0].value = format!("x{}", storage.consumable_count(count.0));
+    }
+}
+
+// rebuilt whenever the holdings change so bought and used up items show up right away
+pub fn fill_consumable_bar(
+    mut commands: Commands,
+    bar_query: Query<(Entity, Ref<ConsumableBar>)>,
+    player_query: Query<Ref<Storage>, With<Player>>,
+    consumable_assets: Res<Assets<ConsumableData>>,
+) {
+    let Ok(storage) = player_query.get_single() else {
+        return;
+    };
+
+    for (bar, bar_setting) in bar_query.iter() {
+        if !bar_setting.is_added() && !storage.is_changed() {
+            continue;
+        }
+        commands.entity(bar).despawn_descendants();
+
+        let mut list = consumable_assets
+            .iter()
+            .map(|(_, data)| data)
+            .filter(|data| data.battle == bar_setting.in_battle)
+            .filter(|data| storage.consumable_count(data.id) > 0)
+            .collect::<Vec<_>>();
+        list.sort_by_key(|data| data.id);
+
+        for data in list {
+            let button = commands
+                .spawn((
+                    Name::new(data.name.clone()),
+                    ButtonBundle {
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::all(Val::Px(4.)),
+                            margin: UiRect::horizontal(Val::Px(4.)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ButtonClickEffect,
+                    StatusBoardZone {},
+                    ConsumableButton(data.id),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        data.name.clone(),
+                        TextStyle {
+                            font_size: 12.,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ));
+                    parent.spawn((
+                        TextBundle::from_section(
+                            format!("x{}", storage.consumable_count(data.id)),
+                            TextStyle {
+                                font_size: 12.,
+                                color: Color::WHITE,
+                                ..default()
+                            },
+                        ),
+                        ConsumableCountText(data.id),
+                    ));
+                })
+                .id();
+
+            commands.entity(bar).add_child(button);
+        }
+    }
+}
+
+// at most one consumable per battle, sorted so the pick does not depend on asset order
+pub fn consumable_possible_drop(
+    assets: &Assets<ConsumableData>,
+    drop_multiplier: f32,
+) -> Option<ConsumableData> {
+    let mut list = assets.iter().map(|(_, data)| data).collect::<Vec<_>>();
+    list.sort_by_key(|data| data.id);
+
+    pick_consumable_drop(&list, drop_multiplier, &mut rand::thread_rng()).cloned()
+}
+
+// one roll against the summed drop rates decides if anything drops, the rates then weight the pick
+pub fn pick_consumable_drop<'a>(
+    list: &[&'a ConsumableData],
+    drop_multiplier: f32,
+    rng: &mut impl Rng,
+) -> Option<&'a ConsumableData> {
+    let total: f32 = list.iter().map(|data| data.drop_rate.max(0.)).sum();
+    if total <= 0. || rng.gen::<f32>() >= (total * drop_multiplier).min(1.) {
+        return None;
+    }
+
+    list.choose_weighted(rng, |data| data.drop_rate.max(0.))
+        .ok()
+        .copied()
+}
+
+#[test]
+fn test_consumable_drop_and_use() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let consumable = |id: u32, drop_rate: f32| ConsumableData {
+        id,
+        name: format!("Potion {}", id),
+        tier: Default::default(),
+        effect: ConsumableEffect::Heal,
+        value: 5.,
+        duration: 0,
+        price: 10,
+        drop_rate,
+        battle: true,
+        description: String::new(),
+    };
+    let (common, rare) = (consumable(1, 0.3), consumable(2, 0.1));
+    let list = vec![&common, &rare];
+    // seeded so the counts are the same on every run
+    let mut rng = StdRng::seed_from_u64(27);
+
+    assert!(pick_consumable_drop(&list, 0., &mut rng).is_none());
+
+    // the first item listed gets no head start, drops follow the 3:1 rates
+    let mut counts = [0, 0, 0];
+    for _ in 0..4000 {
+        match pick_consumable_drop(&list, 1., &mut rng) {
+            Some(data) => counts[data.id as usize] += 1,
+            None => counts[0] += 1,
+        }
+    }
+    assert!((2200..2600).contains(&counts[0]), "{:?}", counts);
+    assert!((1050..1350).contains(&counts[1]), "{:?}", counts);
+    assert!((320..480).contains(&counts[2]), "{:?}", counts);
+
+    let mut storage = Storage::empty();
+    storage.add_consumable(common.id, 2);
+    assert!(storage.take_consumable(common.id));
+    assert!(storage.take_consumable(common.id));
+    assert!(!storage.take_consumable(common.id));
+    assert_eq!(storage.consumable_count(common.id), 0);
+    assert!(!storage.consumables.contains_key(&common.id));
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::error::UserInputError;
+
+use super::Money;
+
+// Gold for one defeated monster
+pub fn battle_gold(monster_lv: u32) -> u64 {
+    let lv = monster_lv as u64;
+    3 + lv + lv * lv / 10
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transaction {
+    Earned { source: String, amount: u64 },
+    Spent { item: String, amount: u64 },
+    // a purchase turned down for lack of money
+    Rejected { item: String, amount: u64 },
+}
+
+// Every change to the player's money, the only place that should touch Money for trades
+#[derive(Resource, Debug, Default)]
+pub struct Ledger(pub Vec<Transaction>);
+
+impl Ledger {
+    pub fn earn(&mut self, money: &mut Money, source: impl Into<String>, amount: u64) {
+        money.add(amount);
+        self.0.push(Transaction::Earned {
+            source: source.into(),
+            amount,
+        });
+    }
+
+    pub fn spend(
+        &mut self,
+        money: &mut Money,
+        item: impl Into<String>,
+        amount: u64,
+    ) -> Result<(), UserInputError> {
+        let item = item.into();
+        if money.0 < amount {
+            self.0.push(Transaction::Rejected { item, amount });
+            return Err(UserInputError());
+        }
+
+        money.0 -= amount;
+        self.0.push(Transaction::Spent { item, amount });
+        Ok(())
+    }
+
+    // gold earned by the entries from start on
+    pub fn earned_since(&self, start: usize) -> u64 {
+        self.0
+            .iter()
+            .skip(start)
+            .map(|transaction| match transaction {
+                Transaction::Earned { amount, .. } => *amount,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+pub fn reset_ledger(mut ledger: ResMut<Ledger>) {
+    ledger.0.clear();
+}
+
+#[test]
+fn test_ledger() {
+    let mut money = Money(0);
+    let mut ledger = Ledger::default();
+
+    ledger.earn(&mut money, "Slime", battle_gold(1));
+    assert_eq!(money.get(), 4);
+
+    assert!(ledger.spend(&mut money, "Red Potion", 10).is_err());
+    assert_eq!(money.get(), 4);
+
+    ledger.earn(&mut money, "Dragon", battle_gold(50));
+    ledger.spend(&mut money, "Red Potion", 10).unwrap();
+    assert_eq!(money.get(), 4 + 303 - 10);
+    assert_eq!(ledger.earned_since(0) - 10, money.get());
+    // spending in between does not count against what was earned
+    assert_eq!(ledger.earned_since(1), 303);
+
+    assert_eq!(
+        ledger.0[1],
+        Transaction::Rejected {
+            item: String::from("Red Potion"),
+            amount: 10
+        }
+    );
+}
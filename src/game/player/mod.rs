@@ -8,32 +8,47 @@ use bevy_rapier2d::{
 
 use super::preload::Location;
 use super::{
-    battle::event::BattleEvent, character::component::Level, monster::Monster, ChangeMapEvent,
+    character::hero::SelectedClass,
+    saving::{Profile, ProfileApplied},
+    ChangeMapEvent,
 };
 
 use crate::game::field::EncounterTimer;
 use crate::model::armor::ArmorData;
+use crate::model::gem::GemBook;
+use crate::model::helmet::HelmetData;
 use crate::model::player::PlayerData;
+use crate::model::set::SetBook;
+use crate::model::shoes::ShoesData;
 use crate::model::sub::DropEquipment;
 use crate::model::weapon::WeaponData;
 use crate::model::EquipmentData;
-use crate::preload::data::PlayerPreloadHandler;
+use crate::model::ItemType;
+use crate::preload::data::{GemPreloadHandler, SetPreloadHandler};
 use crate::state::{OverlayCombatState, Screen, SimulationState};
 
 pub mod component;
+pub mod consumable;
+pub mod economy;
 mod movement;
+pub mod stock;
 
 pub use component::*;
+pub use consumable::*;
+pub use economy::*;
 use movement::*;
+pub use stock::*;
 
 pub const PLAYER_Z_INDEX: f32 = 9.;
-pub const PLAYER_START_POSITION: Vec3 = Vec3::new(440., 370., PLAYER_Z_INDEX);
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spanw_player)
+        app.add_event::<UseConsumableEvent>()
+            .init_resource::<Ledger>()
+            .init_resource::<ShopStock>()
+            .add_systems(Startup, spanw_player)
             .add_systems(
                 Update,
                 (update_idle,)
@@ -51,10 +66,28 @@ impl Plugin for PlayerPlugin {
                     .run_if(in_state(SimulationState::Running))
                     .run_if(in_state(Screen::Playing)),
             )
-            .add_systems(OnEnter(Screen::Playing), init_player_position_w_map)
+            .add_systems(
+                OnEnter(Screen::Playing),
+                (equip_starting_weapon, reset_ledger),
+            )
+            // every data asset is loaded by then, so saved equipment resolves
+            .add_systems(OnExit(Screen::Loading), init_player_w_save)
+            .add_systems(
+                Update,
+                (spawn_player_position, update_set_bonus, update_gem_bonus),
+            )
             .add_systems(
                 Update,
-                (handle_battle, spawn_player_position, init_player_w_save),
+                (interact_with_consumable_button, use_consumable).run_if(in_state(Screen::Playing)),
+            )
+            .add_systems(Update, (fill_consumable_bar, update_consumable_count_text))
+            .add_systems(Update, (load_shop_stock, save_shop_stock))
+            .add_systems(Update, restock_shop.run_if(in_state(Screen::Playing)))
+            .add_systems(
+                Update,
+                tick_repellent
+                    .run_if(in_state(SimulationState::Running))
+                    .run_if(in_state(Screen::Playing)),
             )
             .add_systems(OnExit(Screen::Playing), despawn_joystick)
             .add_systems(OnExit(Screen::GameOver), reset_player)
@@ -63,38 +96,126 @@ impl Plugin for PlayerPlugin {
             .register_type::<Money>()
             .register_type::<Encounter>()
             .register_type::<Storage>()
+            .register_type::<Progress>()
             .register_type::<PlayerEnv>()
+            .register_type::<ExpBoost>()
             .register_type::<Velocity>();
     }
 }
 
+// runs once on leaving the loading screen, with the profile and the equipment data in
 pub fn init_player_w_save(
-    player_handle: Option<Res<PlayerPreloadHandler>>,
-    player_data: Res<Assets<PlayerData>>,
+    mut commands: Commands,
+    data: Res<Profile>,
+    mut player_query: Query<
+        (&mut EquipmentBelt, &mut Storage, &mut Progress, &mut Money),
+        With<Player>,
+    >,
+    weapon_assets: Res<Assets<WeaponData>>,
+    armor_assets: Res<Assets<ArmorData>>,
+    helmet_assets: Res<Assets<HelmetData>>,
+    shoes_assets: Res<Assets<ShoesData>>,
+) {
+    let Ok((mut player_equipment_belt, mut player_storage, mut player_progress, mut money)) =
+        player_query.get_single_mut()
+    else {
+        info!("no player when pulling player data");
+        return;
+    };
+
+    player_equipment_belt.equip_weapon(&weapon_assets, &data);
+    player_equipment_belt.armor = data
+        .eq_armor
+        .clone()
+        .and_then(|eq| Armor::from(&armor_assets, eq));
+    player_equipment_belt.helmet = data
+        .eq_helmet
+        .clone()
+        .and_then(|eq| Helmet::from(&helmet_assets, eq));
+    player_equipment_belt.shoes = data
+        .eq_shoes
+        .clone()
+        .and_then(|eq| Shoes::from(&shoes_assets, eq));
+
+    *player_storage = Storage::new(&data);
+    *player_progress = Progress::new(&data);
+    *money = Money(data.money);
+    commands.insert_resource(ProfileApplied);
+}
+
+// A profile without a weapon starts with the one the class is known for
+pub fn equip_starting_weapon(
     mut player_query: Query<(&mut EquipmentBelt, &mut Storage), With<Player>>,
     weapon_assets: Res<Assets<WeaponData>>,
+    selected_class: Res<SelectedClass>,
 ) {
-    if let Some(handle) = player_handle {
-        if handle.is_added() {
-            let Ok((mut player_equipment_belt, mut player_storage)) = player_query.get_single_mut()
-            else {
-                info!("no player when pulling player data");
-                return;
-            };
-
-            let Some(data) = player_data.get(handle.id()) else {
-                info!("no player data when pulling player data");
-                return;
-            };
-
-            player_equipment_belt.equip_weapon(&weapon_assets, &data);
-
-            *player_storage = Storage::new(data);
+    let Ok((mut belt, mut storage)) = player_query.get_single_mut() else {
+        return;
+    };
+    if belt.weapon.is_some() {
+        return;
+    }
+
+    let id = selected_class.starting_weapon();
+    let Some((_, data)) = weapon_assets.iter().find(|(_, data)| data.id == id) else {
+        info!("no starting weapon {} for {:?}", id, selected_class.0);
+        return;
+    };
+
+    let weapon = match storage.is_owned(data) {
+        Some(owned) => owned,
+        None => {
+            let weapon = data.to_drop_equipment(1);
+            storage
+                .items
+                .entry(ItemType::Weapon)
+                .or_default()
+                .push(weapon.clone());
+            weapon
         }
+    };
+
+    belt.weapon = Weapon::from(&weapon_assets, weapon);
+}
+
+// Only written back when the sets differ, so the belt is not marked changed every frame
+pub fn update_set_bonus(
+    mut player_query: Query<&mut EquipmentBelt, (With<Player>, Changed<EquipmentBelt>)>,
+    handle: Option<Res<SetPreloadHandler>>,
+    books: Res<Assets<SetBook>>,
+) {
+    let Ok(mut belt) = player_query.get_single_mut() else {
+        return;
+    };
+    let Some(book) = handle.and_then(|handle| books.get(handle.id())) else {
+        return;
+    };
+
+    let sets = belt.active_sets(book);
+    if belt.sets != sets {
+        belt.sets = sets;
+    }
+}
+
+pub fn update_gem_bonus(
+    mut player_query: Query<&mut EquipmentBelt, (With<Player>, Changed<EquipmentBelt>)>,
+    handle: Option<Res<GemPreloadHandler>>,
+    books: Res<Assets<GemBook>>,
+) {
+    let Ok(mut belt) = player_query.get_single_mut() else {
+        return;
+    };
+    let Some(book) = handle.and_then(|handle| books.get(handle.id())) else {
+        return;
+    };
+
+    let bonus = belt.socket_bonus(book);
+    if belt.gem_bonus != bonus {
+        belt.gem_bonus = bonus;
     }
 }
 
-pub fn spanw_player(mut commands: Commands, weapon_assets: Res<Assets<WeaponData>>) {
+pub fn spanw_player(mut commands: Commands, _weapon_assets: Res<Assets<WeaponData>>) {
     let rotation_constraints = LockedAxes::ROTATION_LOCKED;
     // let player_data = player_query
     //     .get(player_id.0.id())
@@ -104,8 +225,9 @@ pub fn spanw_player(mut commands: Commands, weapon_assets: Res<Assets<WeaponData
         .spawn((
             Name::new("Player"),
             Player {},
+            // moved onto the start level's spawn point once a run begins
             TransformBundle {
-                local: Transform::from_translation(PLAYER_START_POSITION),
+                local: Transform::from_xyz(0., 0., PLAYER_Z_INDEX),
                 ..default()
             },
             CurrentMovement::default(),
@@ -135,6 +257,8 @@ pub fn spanw_player(mut commands: Commands, weapon_assets: Res<Assets<WeaponData
     commands.entity(player).insert((
         belt,
         storage,
+        Money::default(),
+        Progress::default(),
         PlayerEnv::default(),
         Velocity::default(),
         Damping {
@@ -146,56 +270,37 @@ pub fn spanw_player(mut commands: Commands, weapon_assets: Res<Assets<WeaponData
     println!("Spawned Player")
 }
 
-pub fn handle_battle(
-    player_query: Query<&mut Money, With<Player>>,
-    monster_query: Query<&mut Level, With<Monster>>,
-    battle_event_reader: EventReader<BattleEvent>,
-) {
-    // for ev in battle_event_reader.read() {
-    //     if let Ok(mut money) = player_query.get_single_mut() {
-    //         if let Ok(monster_lv) = monster_query.get(ev.monster_entity) {
-    //             if ev.is_player_victory {
-    //                 money.add((monster_lv.get()).pow(2));
-    //             }
-    //         }
-    //     }
-    // }
-}
-
 pub fn spawn_player_position(
-    mut player_query: Query<&mut Transform, With<Player>>,
-    location_query: Query<(&Transform, &Location), (With<Location>, Without<Player>)>,
+    mut player_query: Query<(&mut Transform, &mut FacingSide, &mut Velocity), With<Player>>,
+    location_query: Query<(&Transform, &Location, &FacingSide), Without<Player>>,
     mut event_reader: EventReader<ChangeMapEvent>,
 ) {
     for event in event_reader.read() {
         info!("Transporting");
-        if let Some((destination, location)) = location_query
-            .iter()
-            .find(|(_, location)| location.equal(&event.location))
-        {
-            let Ok(mut player_transform) = player_query.get_single_mut() else {
-                warn!("Player is not found during map switching");
-                continue;
-            };
-            info!(
-                "New Location {:?}, is located at : {}",
-                location, destination.translation
-            );
-            player_transform.translation = destination.translation;
-            player_transform.translation.z = PLAYER_Z_INDEX;
-        }
+        let Ok((destination, location, facing)) = location_query.get(event.spawn_point) else {
+            error!("Spawn point {:?} is gone", event.location.name());
+            continue;
+        };
+        let Ok((mut player_transform, mut player_facing, mut velocity)) =
+            player_query.get_single_mut()
+        else {
+            warn!("Player is not found during map switching");
+            continue;
+        };
+        info!(
+            "New Location {:?}, is located at : {}",
+            location, destination.translation
+        );
+        player_transform.translation = destination.translation;
+        player_transform.translation.z = PLAYER_Z_INDEX;
+        player_facing.set_if_neq(*facing);
+        *velocity = Velocity::zero();
     }
 }
 
-pub fn init_player_position_w_map(mut player_query: Query<&mut Transform, With<Player>>) {
-    let mut player_transform = player_query.single_mut();
-
-    player_transform.translation = PLAYER_START_POSITION;
-}
-
-pub fn reset_player(mut player_query: Query<(&mut Transform, &mut Money), With<Player>>) {
-    for (mut transform, mut money) in player_query.iter_mut() {
-        transform.translation = PLAYER_START_POSITION;
+// the next run lands the hero on the start spawn point again
+pub fn reset_player(mut player_query: Query<&mut Money, With<Player>>) {
+    for mut money in player_query.iter_mut() {
         money.0 /= 10;
     }
 }
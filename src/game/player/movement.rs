@@ -7,7 +7,7 @@ use bevy_ecs_ldtk::{
 use super::*;
 use crate::game::{
     character::component::{BaseStates, Hero},
-    joystick::{JoystickFixed, NoAction, VirtualJoystickEvent, VirtualJoystickNode},
+    joystick::{VirtualJoystickEvent, VirtualJoystickNode},
 };
 
 // pub fn spawn_joystick(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -34,16 +34,26 @@ use crate::game::{
 //     );
 // }
 
+// switched off in the plugin while movement reads the keyboard and touches directly
+#[allow(dead_code)]
 pub fn update_joystick(
     mut joystick: EventReader<VirtualJoystickEvent>,
-    mut player_query: Query<(&mut Velocity, &mut CurrentMovement, &mut FacingSide), With<Player>>,
-    time_step: Res<Time>,
+    mut player_query: Query<
+        (
+            &mut Velocity,
+            &mut CurrentMovement,
+            &mut FacingSide,
+            &EquipmentBelt,
+        ),
+        With<Player>,
+    >,
+    _time_step: Res<Time>,
 ) {
     for j in joystick.read() {
-        let (mut velocity, mut current_movement, mut face_side) = player_query.single_mut();
+        let (mut velocity, mut current_movement, mut face_side, belt) = player_query.single_mut();
         // info!("joystick: {:?}", j);
         let Vec2 { x, y } = j.snap_axis(Some(0.3));
-        let speed: f32 = 170.;
+        let speed: f32 = 170. * belt.walk_speed();
 
         velocity.linvel.x = x * speed;
         velocity.linvel.y = y * speed;
@@ -64,16 +74,28 @@ pub fn update_joystick(
 
 pub fn movement_input(
     keys: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<(&mut Velocity, &mut CurrentMovement, &mut FacingSide), With<Player>>,
-    time: Res<Time>,
+    mut player_query: Query<
+        (
+            &mut Velocity,
+            &mut CurrentMovement,
+            &mut FacingSide,
+            &EquipmentBelt,
+        ),
+        With<Player>,
+    >,
+    _time: Res<Time>,
     hero_query: Query<&BaseStates, With<Hero>>,
 ) {
     // #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
-    if let Ok((mut velocity, mut current_movement, mut face_side)) = player_query.get_single_mut() {
+    if let Ok((mut velocity, mut current_movement, mut face_side, belt)) =
+        player_query.get_single_mut()
+    {
         let Ok(state) = hero_query.get_single() else {
             return;
         };
-        let speed: f32 = 30_f32.max(300_f32.min(200_f32 * state.lv_f32() / 100.));
+        // shoes go on top of the level based speed
+        let speed: f32 =
+            30_f32.max(300_f32.min(200_f32 * state.lv_f32() / 100.)) * belt.walk_speed();
         if keys.any_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
             velocity.linvel.y = speed;
             current_movement.set_if_neq(CurrentMovement(Movement::Run));
@@ -185,7 +207,7 @@ pub fn player_fit_inside_current_level(
     if let Ok(player_tranform) = player_query.get_single_mut() {
         let mut player_translation = player_tranform.translation;
         let window = window_query.get_single().unwrap();
-        let aspect_ratio = window.width() / window.height();
+        let _aspect_ratio = window.width() / window.height();
 
         for (level_transform, level_iid) in &level_query {
             let ldtk_project = ldtk_project_assets
@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::{
+    game::{
+        battle::event::BattleEvent,
+        character::component::{BaseStates, Hero},
+        saving::{write_profile, Profile},
+        ChangeMapEvent,
+    },
+    model::{
+        player::{ShopSave, StockOffer},
+        ItemType,
+    },
+};
+
+use super::{EquipmentAssets, Player, Progress};
+
+// victories before the shelves are refilled
+pub const RESTOCK_BATTLES: u32 = 5;
+const OFFERS_PER_TYPE: usize = 3;
+const DEAL_CHANCE: f64 = 0.2;
+const DEAL_DISCOUNT: u32 = 30;
+
+// Highest tier on sale, every 10 hero levels or every map opened by a boss raises it
+pub fn max_stock_tier(hero_lv: u32, maps_unlocked: u32) -> u32 {
+    1 + (hero_lv / 10).max(maps_unlocked)
+}
+
+// Shop shelves, mirrored into the profile whenever they change
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct ShopStock(pub ShopSave);
+
+impl ShopStock {
+    // only what is still on the shelf
+    pub fn offer(&self, item_type: &ItemType, id: u32) -> Option<&StockOffer> {
+        self.offers
+            .iter()
+            .find(|offer| offer.item_type == *item_type && offer.id == id && !offer.sold)
+    }
+
+    pub fn mark_sold(&mut self, item_type: &ItemType, id: u32) {
+        if let Some(offer) = self
+            .offers
+            .iter_mut()
+            .find(|offer| offer.item_type == *item_type && offer.id == id)
+        {
+            offer.sold = true;
+        }
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.restocks == 0 || self.battles >= RESTOCK_BATTLES
+    }
+
+    // catalog is (type, id, tier) of every equipment, anything above max_tier stays off the shelves
+    pub fn restock(&mut self, catalog: &[(ItemType, u32, u32)], max_tier: u32, rng: &mut impl Rng) {
+        let mut offers = Vec::new();
+        for item_type in [
+            ItemType::Weapon,
+            ItemType::Armor,
+            ItemType::Helmet,
+            ItemType::Shoes,
+        ] {
+            let picked = catalog
+                .iter()
+                .filter(|(kind, _, tier)| *kind == item_type && *tier <= max_tier)
+                .choose_multiple(rng, OFFERS_PER_TYPE);
+            for (_, id, _) in picked {
+                offers.push(StockOffer {
+                    item_type: item_type.clone(),
+                    id: *id,
+                    discount: if rng.gen_bool(DEAL_CHANCE) {
+                        DEAL_DISCOUNT
+                    } else {
+                        0
+                    },
+                    sold: false,
+                });
+            }
+        }
+
+        self.offers = offers;
+        self.battles = 0;
+        self.restocks += 1;
+    }
+}
+
+pub fn load_shop_stock(profile: Option<Res<Profile>>, mut stock: ResMut<ShopStock>) {
+    let Some(profile) = profile else {
+        return;
+    };
+    // untouched when it already matches, so loading does not count as a change to save
+    if profile.is_added() {
+        stock.set_if_neq(ShopStock(profile.shop.clone()));
+    }
+}
+
+pub fn save_shop_stock(stock: Res<ShopStock>, profile: Option<ResMut<Profile>>) {
+    if !stock.is_changed() || stock.is_added() {
+        return;
+    }
+    let Some(mut profile) = profile else {
+        return;
+    };
+    if profile.shop == stock.0 {
+        return;
+    }
+
+    profile.shop = stock.0.clone();
+    write_profile(&profile);
+}
+
+// refills after enough victories or whenever the hero travels to another map
+// only a trip to another map restocks, landing at the start of a run does not
+fn has_travelled<'a>(events: impl IntoIterator<Item = &'a ChangeMapEvent>) -> bool {
+    events.into_iter().filter(|ev| !ev.run_start).count() > 0
+}
+
+pub fn restock_shop(
+    mut battle_event_reader: EventReader<BattleEvent>,
+    mut change_map_event_reader: EventReader<ChangeMapEvent>,
+    mut stock: ResMut<ShopStock>,
+    hero_query: Query<&BaseStates, With<Hero>>,
+    progress_query: Query<&Progress, With<Player>>,
+    assets: EquipmentAssets,
+) {
+    let victories = battle_event_reader
+        .read()
+        .filter(|ev| ev.is_player_victory)
+        .count() as u32;
+    if victories > 0 {
+        stock.battles += victories;
+    }
+    let travelled = has_travelled(change_map_event_reader.read());
+    if !travelled && !stock.is_due() {
+        return;
+    }
+
+    let (Ok(base), Ok(progress)) = (hero_query.get_single(), progress_query.get_single()) else {
+        return;
+    };
+    let catalog = assets.catalog();
+    if catalog.is_empty() {
+        return;
+    }
+
+    let max_tier = max_stock_tier(base.lv(), progress.cleared_bosses.len() as u32);
+    stock.restock(&catalog, max_tier, &mut rand::thread_rng());
+    info!("Shop restocked up to tier {}", max_tier);
+}
+
+#[test]
+fn test_restock_gating() {
+    let catalog = vec![
+        (ItemType::Weapon, 1, 0),
+        (ItemType::Weapon, 2, 1),
+        (ItemType::Weapon, 3, 2),
+        (ItemType::Weapon, 4, 3),
+        (ItemType::Armor, 1, 1),
+        (ItemType::Armor, 2, 4),
+    ];
+    let mut stock = ShopStock::default();
+    let mut rng = rand::thread_rng();
+    assert!(stock.is_due());
+
+    stock.restock(&catalog, max_stock_tier(1, 0), &mut rng);
+    assert!(!stock.is_due());
+    assert_eq!(stock.offers.len(), 3);
+    assert!(stock.offer(&ItemType::Weapon, 3).is_none());
+    assert!(stock.offer(&ItemType::Armor, 2).is_none());
+    assert!(stock.offer(&ItemType::Armor, 1).is_some());
+
+    // a cleared boss opens the next tier without waiting on levels
+    stock.restock(&catalog, max_stock_tier(1, 1), &mut rng);
+    assert_eq!(stock.offers.len(), 4);
+    assert!(stock.offer(&ItemType::Weapon, 4).is_none());
+
+    stock.mark_sold(&ItemType::Armor, 1);
+    assert!(stock.offer(&ItemType::Armor, 1).is_none());
+
+    stock.battles = RESTOCK_BATTLES;
+    assert!(stock.is_due());
+
+    let start = ChangeMapEvent {
+        location: crate::game::map::preload::Location::new("Start"),
+        spawn_point: Entity::PLACEHOLDER,
+        run_start: true,
+    };
+    let trip = ChangeMapEvent {
+        location: crate::game::map::preload::Location::new("FromForest"),
+        spawn_point: Entity::PLACEHOLDER,
+        run_start: false,
+    };
+    assert!(!has_travelled([&start]));
+    assert!(has_travelled([&start, &trip]));
+}
@@ -1,16 +1,260 @@
-use bevy::{
-    app::Plugin,
-    ecs::{query::Changed, system::Query},
+use bevy::prelude::*;
+
+use crate::{model::player::PlayerData, preload::data::PlayerPreloadHandler, state::Screen};
+
+use super::{
+    character::{
+        component::{BaseStates, Hero, HeroClass},
+        hero::SelectedClass,
+    },
+    player::{EquipmentBelt, Money, Progress, Storage},
+    Player,
 };
 
-use super::EquipmentBelt;
+// under the user's data folder, never inside assets where the file watcher would reload it
+#[cfg(not(target_arch = "wasm32"))]
+const PROFILE_FILE: &str = "leveling-up/profile.json";
 
 pub struct SavingPlugin;
 
 impl Plugin for SavingPlugin {
-    fn build(&self, app: &mut bevy::prelude::App) {}
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, read_saved_profile)
+            .add_systems(
+                Update,
+                start_new_profile.run_if(not(resource_exists::<Profile>)),
+            )
+            // the player starts out empty, saving before the profile is applied would wipe it
+            .add_systems(
+                Update,
+                (save_hero_progress, save_boss_progress, save_inventory)
+                    .run_if(resource_exists::<ProfileApplied>),
+            )
+            .add_systems(OnExit(Screen::GameOver), clear_hero_save);
+    }
+}
+
+// The player's save, inserted once it has been read, systems pick it up with is_added
+#[derive(Resource, Debug, Clone, Deref, DerefMut)]
+pub struct Profile(pub PlayerData);
+
+// Inserted once the profile has been applied to the player
+#[derive(Resource, Debug, Default)]
+pub struct ProfileApplied;
+
+fn read_saved_profile(mut commands: Commands) {
+    if let Some(data) = read_profile() {
+        info!("Profile loaded");
+        commands.insert_resource(Profile(data));
+    }
+}
+
+// a first run starts from the bundled data/player.json, which is only ever read
+fn start_new_profile(
+    mut commands: Commands,
+    player_handle: Option<Res<PlayerPreloadHandler>>,
+    player_data: Res<Assets<PlayerData>>,
+) {
+    let Some(data) = player_handle.and_then(|handle| player_data.get(handle.id())) else {
+        return;
+    };
+
+    info!("New profile started");
+    commands.insert_resource(Profile(data.clone()));
+}
+
+// Class, level, unspent points and allocations go into the profile whenever they change
+fn save_hero_progress(
+    hero_query: Query<(&BaseStates, &HeroClass), (With<Hero>, Changed<BaseStates>)>,
+    profile: Option<ResMut<Profile>>,
+) {
+    let Ok((base, class)) = hero_query.get_single() else {
+        return;
+    };
+    let Some(mut profile) = profile else {
+        return;
+    };
+
+    profile.set_hero(base.to_save(*class));
+    write_profile(&profile);
+}
+
+// Cleared bosses and the ending survive restarts, the shop tiers depend on them
+fn save_boss_progress(
+    player_query: Query<&Progress, (With<Player>, Changed<Progress>)>,
+    profile: Option<ResMut<Profile>>,
+) {
+    let Ok(progress) = player_query.get_single() else {
+        return;
+    };
+    let Some(mut profile) = profile else {
+        return;
+    };
+    if profile.cleared_bosses == progress.cleared_bosses
+        && profile.is_completed == progress.is_completed
+    {
+        return;
+    }
+
+    profile.cleared_bosses = progress.cleared_bosses.clone();
+    profile.is_completed = progress.is_completed;
+    write_profile(&profile);
+}
+
+// Gold, owned items, their sockets and levels, and what is equipped
+fn save_inventory(
+    player_query: Query<
+        (&Storage, &EquipmentBelt, &Money),
+        (
+            With<Player>,
+            Or<(Changed<Storage>, Changed<EquipmentBelt>, Changed<Money>)>,
+        ),
+    >,
+    profile: Option<ResMut<Profile>>,
+) {
+    let Ok((storage, belt, money)) = player_query.get_single() else {
+        return;
+    };
+    let Some(mut profile) = profile else {
+        return;
+    };
+
+    profile.money = money.get();
+    profile.owned = storage.items.clone();
+    profile.consumables = storage.consumables.clone();
+    profile.gems = storage.gems.clone();
+    profile.materials = storage.materials;
+    profile.eq_weapon = belt.weapon.as_ref().map(|item| item.feature.clone());
+    profile.eq_armor = belt.armor.as_ref().map(|item| item.feature.clone());
+    profile.eq_helmet = belt.helmet.as_ref().map(|item| item.feature.clone());
+    profile.eq_shoes = belt.shoes.as_ref().map(|item| item.feature.clone());
+    write_profile(&profile);
+}
+
+// A fainted hero starts over from level 1, the other classes keep theirs
+fn clear_hero_save(profile: Option<ResMut<Profile>>, selected_class: Res<SelectedClass>) {
+    let Some(mut profile) = profile else {
+        return;
+    };
+
+    profile.remove_hero(&selected_class.0.to_string());
+    write_profile(&profile);
 }
 
-fn save_on_new_owned(player_belt: Query<&EquipmentBelt, Changed<EquipmentBelt>>) {}
+#[cfg(not(target_arch = "wasm32"))]
+fn profile_path() -> Option<std::path::PathBuf> {
+    use std::{env, path::PathBuf};
 
-// Todo: Player mainly for now.
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(data_dir.join(PROFILE_FILE))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_profile() -> Option<PlayerData> {
+    let path = profile_path()?;
+    let json = std::fs::read_to_string(&path).ok()?;
+
+    match serde_json::from_str(&json) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            warn!("Ignoring broken profile {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn write_profile(data: &PlayerData) {
+    let Some(path) = profile_path() else {
+        warn!("Failed to save profile: no user data folder");
+        return;
+    };
+
+    let result = serde_json::to_string_pretty(data)
+        .map_err(|err| err.to_string())
+        .and_then(|json| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+            }
+            std::fs::write(&path, json).map_err(|err| err.to_string())
+        });
+
+    if let Err(err) = result {
+        warn!("Failed to save profile: {}", err);
+    }
+}
+
+// no file system on the web, the profile only lives for the session
+#[cfg(target_arch = "wasm32")]
+fn read_profile() -> Option<PlayerData> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) fn write_profile(_data: &PlayerData) {}
+
+#[test]
+fn test_profile_survives_first_frames() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::model::{
+        armor::ArmorData, helmet::HelmetData, shoes::ShoesData, sub::DropEquipment,
+        weapon::WeaponData, ItemType,
+    };
+
+    // keep the test's saves away from the real profile
+    std::env::set_var(
+        "XDG_DATA_HOME",
+        std::env::temp_dir().join(format!("leveling-up-test-{}", std::process::id())),
+    );
+
+    let data = PlayerData {
+        money: 500,
+        materials: 7,
+        owned: std::collections::HashMap::from([(
+            ItemType::Weapon,
+            vec![DropEquipment::new(ItemType::Weapon, 2, 10)],
+        )]),
+        ..default()
+    };
+
+    let mut app = App::new();
+    app.init_resource::<Assets<PlayerData>>()
+        .init_resource::<Assets<WeaponData>>()
+        .init_resource::<Assets<ArmorData>>()
+        .init_resource::<Assets<HelmetData>>()
+        .init_resource::<Assets<ShoesData>>()
+        .insert_resource(Profile(data.clone()))
+        .add_plugins(SavingPlugin);
+    app.world_mut().spawn((
+        Player {},
+        EquipmentBelt::new(),
+        Storage::empty(),
+        Progress::default(),
+        Money::default(),
+    ));
+
+    // the empty player must not be saved over the profile while loading
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<Profile>().money, 500);
+    assert_eq!(app.world().resource::<Profile>().owned, data.owned);
+
+    app.world_mut()
+        .run_system_once(crate::game::player::init_player_w_save);
+    app.update();
+    app.update();
+
+    let profile = app.world().resource::<Profile>();
+    assert_eq!(profile.money, 500);
+    assert_eq!(profile.materials, 7);
+    assert_eq!(profile.owned, data.owned);
+
+    let mut player_query = app.world_mut().query::<(&Money, &Storage)>();
+    let (money, storage) = player_query.single(app.world());
+    assert_eq!(money.get(), 500);
+    assert_eq!(storage.items, data.owned);
+}
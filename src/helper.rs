@@ -77,7 +77,7 @@ pub trait Toggle {
 }
 
 impl Toggle for Visibility {
-    fn toggle(&mut self) -> () {
+    fn toggle(&mut self) {
         let _ = self.set(match *self {
             Visibility::Inherited => Box::new(Visibility::Hidden),
             Visibility::Hidden => Box::new(Visibility::Visible),
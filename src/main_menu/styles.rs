@@ -1,8 +1,8 @@
 use bevy::prelude::*;
 
-pub const NORMAL_BUTTON_COLOR: Color = Color::rgba(0.15, 0.15, 0.15, 0.7);
-pub const HOVERED_BUTTON_COLOR: Color = Color::rgba(0.50, 0.50, 0.50, 0.7);
-pub const PRESSED_BUTTON_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
+pub const NORMAL_BUTTON_COLOR: Color = Color::srgba(0.15, 0.15, 0.15, 0.7);
+pub const HOVERED_BUTTON_COLOR: Color = Color::srgba(0.50, 0.50, 0.50, 0.7);
+pub const PRESSED_BUTTON_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
 
 pub fn get_main_menu_style() -> Style {
     Style {
@@ -0,0 +1,71 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use serde::Deserialize;
+
+use super::player::AchievementSave;
+
+// Every achievement in the game, the file is a plain json array
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct AchievementBook(pub Vec<AchievementData>);
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AchievementData {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+}
+
+// Each condition is a threshold on a lifetime counter kept in the profile
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum AchievementCondition {
+    CriticalHits { count: u32 },
+    MonstersDefeated { count: u32 },
+    BossesDefeated { count: u32 },
+    DropsFound { count: u32 },
+    Travels { count: u32 },
+    ReachLevel { lv: u32 },
+    WeaponElements { count: u32 },
+    FlawlessRuns { count: u32 },
+}
+
+impl AchievementCondition {
+    // (current, target)
+    pub fn progress(&self, save: &AchievementSave) -> (u32, u32) {
+        match *self {
+            AchievementCondition::CriticalHits { count } => (save.critical_hits, count),
+            AchievementCondition::MonstersDefeated { count } => (save.monsters_defeated, count),
+            AchievementCondition::BossesDefeated { count } => (save.bosses_defeated, count),
+            AchievementCondition::DropsFound { count } => (save.drops_found, count),
+            AchievementCondition::Travels { count } => (save.travels, count),
+            AchievementCondition::ReachLevel { lv } => (save.best_lv, lv),
+            AchievementCondition::WeaponElements { count } => (save.weapon_elements, count),
+            AchievementCondition::FlawlessRuns { count } => (save.flawless_runs, count),
+        }
+    }
+
+    pub fn is_met(&self, save: &AchievementSave) -> bool {
+        let (current, target) = self.progress(save);
+        current >= target
+    }
+}
+
+#[test]
+fn test_achievement_conditions() {
+    let book: Vec<AchievementData> = serde_json::from_str(
+        r#"[
+            {"id": 1, "name": "Lucky", "description": "", "condition": {"kind": "CriticalHits", "count": 1}},
+            {"id": 2, "name": "Veteran", "description": "", "condition": {"kind": "ReachLevel", "lv": 50}}
+        ]"#,
+    )
+    .unwrap();
+
+    let mut save = AchievementSave::default();
+    assert!(book.iter().all(|data| !data.condition.is_met(&save)));
+
+    save.critical_hits = 1;
+    save.best_lv = 49;
+    assert!(book[0].condition.is_met(&save));
+    assert_eq!(book[1].condition.progress(&save), (49, 50));
+    assert!(!book[1].condition.is_met(&save));
+}
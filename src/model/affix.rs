@@ -0,0 +1,144 @@
+use bevy::{asset::Asset, reflect::Reflect, reflect::TypePath};
+use rand::{seq::IteratorRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use super::{sub::Rarity, ItemType};
+
+// Pool of affixes a dropped equipment can roll, the file is a plain json array
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct AffixBook(pub Vec<AffixData>);
+
+impl AffixBook {
+    pub fn get(&self, kind: &AffixKind) -> Option<&AffixData> {
+        self.0.iter().find(|data| data.kind == *kind)
+    }
+
+    // one affix per kind at most, as many as the rarity allows
+    pub fn roll(&self, item_type: &ItemType, rarity: &Rarity, rng: &mut impl Rng) -> Vec<Affix> {
+        self.0
+            .iter()
+            .filter(|data| data.item_types.contains(item_type))
+            .choose_multiple(rng, rarity.affix_count())
+            .into_iter()
+            .map(|data| Affix {
+                kind: data.kind.clone(),
+                value: rng.gen_range(data.min..=data.max.max(data.min)),
+            })
+            .collect()
+    }
+
+    // affix names to put in front of the item name, e.g. "Keen Charged"
+    pub fn prefix(&self, affixes: &[Affix]) -> String {
+        affixes
+            .iter()
+            .filter_map(|affix| self.get(&affix.kind))
+            .map(|data| data.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AffixData {
+    pub kind: AffixKind,
+    pub name: String,
+    pub min: u32,
+    pub max: u32,
+    // equipment types that can roll this affix
+    pub item_types: Vec<ItemType>,
+}
+
+#[derive(Debug, Default, Clone, Reflect, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AffixKind {
+    // crit rate in percent points
+    #[default]
+    CritRate,
+    // percent more damage when the weapon hits with an element
+    ElementalDamage,
+    // percent more exp from battles
+    ExpGain,
+    // flat attack on top of the weapon
+    Attack,
+    // flat defense on top of the armor
+    Defense,
+    // percent faster on the field
+    WalkSpeed,
+}
+
+// Values are whole percents so the equipment stays hashable
+#[derive(Debug, Default, Clone, Reflect, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Affix {
+    pub kind: AffixKind,
+    pub value: u32,
+}
+
+impl std::fmt::Display for Affix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self.kind {
+            AffixKind::CritRate => write!(f, "+{}% Crit", self.value),
+            AffixKind::ElementalDamage => write!(f, "+{}% Elemental Damage", self.value),
+            AffixKind::ExpGain => write!(f, "+{}% Exp", self.value),
+            AffixKind::Attack => write!(f, "+{} Attack", self.value),
+            AffixKind::Defense => write!(f, "+{} Defend", self.value),
+            AffixKind::WalkSpeed => write!(f, "+{}% Speed", self.value),
+        }
+    }
+}
+
+#[test]
+fn test_affix_roll() {
+    let book: Vec<AffixData> = serde_json::from_str(
+        r#"[
+            {"kind": "CritRate", "name": "Keen", "min": 1, "max": 5, "item_types": ["Weapon", "Helmet"]},
+            {"kind": "ElementalDamage", "name": "Charged", "min": 5, "max": 15, "item_types": ["Weapon"]},
+            {"kind": "ExpGain", "name": "Wise", "min": 3, "max": 3, "item_types": ["Helmet", "Shoes"]}
+        ]"#,
+    )
+    .unwrap();
+    let book = AffixBook(book);
+    let mut rng = rand::thread_rng();
+
+    assert!(book
+        .roll(&ItemType::Weapon, &Rarity::Common, &mut rng)
+        .is_empty());
+
+    // never more than the pool for that item type
+    let affixes = book.roll(&ItemType::Weapon, &Rarity::Legendary, &mut rng);
+    assert_eq!(affixes.len(), 2);
+    for affix in affixes.iter() {
+        let data = book.get(&affix.kind).unwrap();
+        assert!(affix.value >= data.min && affix.value <= data.max);
+    }
+
+    let affixes = book.roll(&ItemType::Shoes, &Rarity::Rare, &mut rng);
+    assert_eq!(
+        affixes,
+        vec![Affix {
+            kind: AffixKind::ExpGain,
+            value: 3
+        }]
+    );
+    assert_eq!(book.prefix(&affixes), "Wise");
+}
+
+#[test]
+fn test_every_rarity_fills_its_affixes() {
+    let json = std::fs::read_to_string("assets/data/1.affix.json").unwrap();
+    let book = AffixBook(serde_json::from_str(&json).unwrap());
+    let mut rng = rand::thread_rng();
+
+    for item_type in [
+        ItemType::Weapon,
+        ItemType::Armor,
+        ItemType::Helmet,
+        ItemType::Shoes,
+    ] {
+        let affixes = book.roll(&item_type, &Rarity::Legendary, &mut rng);
+        assert_eq!(
+            affixes.len(),
+            Rarity::Legendary.affix_count(),
+            "not enough affixes for {:?}",
+            item_type
+        );
+    }
+}
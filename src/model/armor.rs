@@ -6,6 +6,8 @@ use super::{
 };
 
 #[derive(serde::Deserialize, Asset, TypePath, Debug, Clone)]
+// mirrors every column of the csv, some are not read by the game yet
+#[allow(dead_code)]
 pub struct ArmorData {
     pub id: u32,
     pub name: String,
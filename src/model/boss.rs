@@ -0,0 +1,51 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use serde::Deserialize;
+
+use super::sub::DropEquipment;
+
+// Every boss script in the game, the file is a plain json array
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct BossBook(pub Vec<BossData>);
+
+impl BossBook {
+    pub fn get(&self, id: u32) -> Option<&BossData> {
+        self.0.iter().find(|boss| boss.id == id)
+    }
+}
+
+// id matches the Id field of the Boss entity in the map
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BossData {
+    pub id: u32,
+    #[serde(default)]
+    pub intro: Vec<String>,
+    #[serde(default)]
+    pub phases: Vec<BossPhase>,
+    #[serde(default)]
+    pub defeat: Vec<String>,
+    // always handed over on defeat, leveled to the boss
+    #[serde(default)]
+    pub drops: Vec<DropEquipment>,
+    // beating this one rolls the ending credits
+    #[serde(default)]
+    pub is_final: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BossPhase {
+    // phase starts once the boss hp falls to this fraction of its max
+    pub hp_threshold: f32,
+    #[serde(default = "default_multiplier")]
+    pub attack_multiplier: f32,
+    #[serde(default = "default_multiplier")]
+    pub damage_taken: f32,
+    // fraction of max hp recovered when the phase starts
+    #[serde(default)]
+    pub heal: f32,
+    #[serde(default)]
+    pub dialogue: Vec<String>,
+}
+
+fn default_multiplier() -> f32 {
+    1.
+}
@@ -0,0 +1,53 @@
+use bevy::{asset::Asset, reflect::TypePath};
+
+use crate::util::csv_helper::bool_from_str;
+
+use super::{sub::Tier, EquipmentData};
+
+#[derive(Debug, serde::Deserialize, Default, Clone, PartialEq)]
+pub enum ConsumableEffect {
+    // restore hp in battle
+    #[default]
+    Heal,
+    // give back turns
+    RestoreTurns,
+    // seconds without encounter
+    Repel,
+    // exp multiplier for the next few battles
+    ExpBoost,
+}
+
+// id	name	tier	effect	value	duration	price	drop_rate	battle	description
+#[derive(serde::Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct ConsumableData {
+    pub id: u32,
+    pub name: String,
+    pub tier: Tier,
+    pub effect: ConsumableEffect,
+    pub value: f32,
+    pub duration: u32,
+    pub price: u64,
+    pub drop_rate: f32,
+    #[serde(deserialize_with = "bool_from_str")]
+    pub battle: bool,
+    pub description: String,
+}
+
+impl EquipmentData for ConsumableData {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn item_type(&self) -> super::ItemType {
+        super::ItemType::Others
+    }
+    fn tier(&self) -> Tier {
+        self.tier.clone()
+    }
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use serde::Deserialize;
+
+use super::sub::Element;
+
+// Every gem in the game, the file is a plain json array
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct GemBook(pub Vec<GemData>);
+
+impl GemBook {
+    pub fn get(&self, id: u32) -> Option<&GemData> {
+        self.0.iter().find(|gem| gem.id == id)
+    }
+
+    // combined effect of the gems socketed into one weapon
+    pub fn bonus(&self, sockets: &[u32]) -> GemBonus {
+        let mut bonus = GemBonus::default();
+        for gem in sockets.iter().filter_map(|id| self.get(*id)) {
+            match &gem.effect {
+                // the last elemental gem decides
+                GemEffect::Element { element } => bonus.element = Some(element.clone()),
+                GemEffect::Crit { rate } => bonus.crit_rate += rate,
+                GemEffect::Attack { value } => bonus.attack += value,
+            }
+        }
+        bonus
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GemData {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub price: u64,
+    pub effect: GemEffect,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum GemEffect {
+    // weapon hits with this element instead of its own
+    Element { element: Element },
+    // crit rate in percent points
+    Crit { rate: f32 },
+    // flat damage like weapon level
+    Attack { value: f32 },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GemBonus {
+    pub element: Option<Element>,
+    pub crit_rate: f32,
+    pub attack: f32,
+}
+
+#[test]
+fn test_gem_bonus() {
+    let book: Vec<GemData> = serde_json::from_str(
+        r#"[
+            {"id": 1, "name": "Ruby", "description": "", "price": 1, "effect": {"kind": "Element", "element": "Fire"}},
+            {"id": 2, "name": "Topaz", "description": "", "price": 1, "effect": {"kind": "Crit", "rate": 5}},
+            {"id": 3, "name": "Onyx", "description": "", "price": 1, "effect": {"kind": "Attack", "value": 2}}
+        ]"#,
+    )
+    .unwrap();
+    let book = GemBook(book);
+
+    assert_eq!(book.bonus(&[]), GemBonus::default());
+
+    let bonus = book.bonus(&[1, 2, 2, 3, 99]);
+    assert_eq!(bonus.element, Some(Element::Fire));
+    assert_eq!(bonus.crit_rate, 10.);
+    assert_eq!(bonus.attack, 2.);
+}
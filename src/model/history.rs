@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::sub::DropEquipment;
+
+// older battles are dropped once the history is full
+pub const MAX_BATTLE_LOGS: usize = 20;
+
+// Last battles kept in the profile, newest at the back
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HistorySave {
+    pub next_id: u32,
+    pub logs: VecDeque<BattleLog>,
+}
+
+impl HistorySave {
+    pub fn push(&mut self, mut log: BattleLog) {
+        self.next_id += 1;
+        log.id = self.next_id;
+        self.logs.push_back(log);
+
+        while self.logs.len() > MAX_BATTLE_LOGS {
+            self.logs.pop_front();
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.logs)
+    }
+
+    // newest battle first, as shown in the status board
+    pub fn to_lines(&self) -> Vec<String> {
+        self.logs
+            .iter()
+            .rev()
+            .flat_map(BattleLog::to_lines)
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BattleLog {
+    pub id: u32,
+    pub monsters: Vec<String>,
+    pub is_victory: bool,
+    pub turns: Vec<TurnLog>,
+    pub drops: Vec<DropEquipment>,
+    pub consumables: Vec<String>,
+    pub exp: f64,
+}
+
+impl BattleLog {
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "#{} {} vs {}",
+            self.id,
+            if self.is_victory { "Won" } else { "Lost" },
+            self.monsters.join(", ")
+        )];
+
+        lines.extend(self.turns.iter().map(|turn| turn.text.clone()));
+
+        for drop in self.drops.iter() {
+            lines.push(format!("Drop: {}lv {}", drop.level, drop.name));
+        }
+        for consumable in self.consumables.iter() {
+            lines.push(format!("Item: {}", consumable));
+        }
+        if self.exp > 0. {
+            lines.push(format!("Exp: {:.1}", self.exp));
+        }
+
+        lines
+    }
+}
+
+// status effects are kept by name so the log reads the same after a restart
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TurnLog {
+    pub is_player_turn: bool,
+    pub attacker: String,
+    pub defender: String,
+    pub damage: f32,
+    pub is_critical: bool,
+    pub status_damage: f32,
+    pub is_skipped: bool,
+    pub expired: Vec<String>,
+    pub inflicted: Option<String>,
+    // exactly what the terminal showed for the turn
+    pub text: String,
+}
+
+#[test]
+fn test_battle_history_round_trip() {
+    let mut history = HistorySave::default();
+    history.push(BattleLog {
+        monsters: vec![String::from("Slime")],
+        is_victory: true,
+        turns: vec![TurnLog {
+            text: String::from("Hero smiles at Slime\ndealt 3.0 damage to Slime"),
+            ..Default::default()
+        }],
+        exp: 2.,
+        ..Default::default()
+    });
+
+    let json = serde_json::to_string(&history).unwrap();
+    let loaded: HistorySave = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.to_lines(), history.to_lines());
+
+    // the next battle keeps counting from the saved id
+    let mut loaded = loaded;
+    loaded.push(BattleLog::default());
+    assert_eq!(loaded.logs.back().unwrap().id, 2);
+}
@@ -6,13 +6,23 @@ use serde_with::serde_as;
 
 use self::sub::{DropEquipment, Tier};
 
+pub mod achievement;
+pub mod affix;
 pub mod armor;
+pub mod boss;
+pub mod consumable;
+pub mod gem;
 pub mod helmet;
+pub mod history;
 pub mod monster;
 pub mod player;
+pub mod progression;
+pub mod recipe;
+pub mod set;
 pub mod shoes;
 pub mod sub;
 pub mod weapon;
+pub mod world;
 
 #[serde_as]
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Reflect, Serialize, Deserialize)]
@@ -71,12 +81,9 @@ pub trait EquipmentData {
     fn description(&self) -> String;
     fn to_drop_equipment(&self, lv: u32) -> DropEquipment {
         DropEquipment {
-            id: self.id(),
-            level: lv,
             name: self.name(),
-            image: None,
-            item_type: self.item_type(),
             description: self.description(),
+            ..DropEquipment::new(self.item_type(), self.id(), lv)
         }
     }
 }
@@ -5,6 +5,8 @@ use super::sub::*;
 
 // id	name	tier	armor_force	weight	sapien	beast	automatan	inanimate	mythical	spirit	element
 #[derive(serde::Deserialize, Asset, TypePath, Debug, Clone, Component)]
+// mirrors every column of the csv, some are not read by the game yet
+#[allow(dead_code)]
 pub struct MonsterData {
     pub id: u32,
     pub name: String,
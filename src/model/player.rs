@@ -3,11 +3,11 @@ use std::collections::HashMap;
 use bevy::{asset::Asset, ecs::component::Component, reflect::TypePath};
 use serde::{Deserialize, Serialize};
 
-use super::{sub::DropEquipment, ItemType};
+use super::{history::HistorySave, sub::DropEquipment, ItemType};
 use serde_with::serde_as;
 
 #[serde_as]
-#[derive(Asset, TypePath, Debug, Component, Clone, Deserialize, Serialize)]
+#[derive(Asset, TypePath, Debug, Component, Clone, Default, Deserialize, Serialize)]
 pub struct PlayerData {
     #[serde_as(as = "HashMap<_, Vec<_>>")]
     pub owned: HashMap<ItemType, Vec<DropEquipment>>,
@@ -18,4 +18,119 @@ pub struct PlayerData {
     pub eq_helmet: Option<DropEquipment>,
 
     pub eq_shoes: Option<DropEquipment>,
+
+    // consumable id -> count
+    #[serde(default)]
+    pub consumables: HashMap<u32, u32>,
+
+    // gem id -> count, socketed gems live on the weapon instead
+    #[serde(default)]
+    pub gems: HashMap<u32, u32>,
+
+    // from salvaged equipment
+    #[serde(default)]
+    pub materials: u32,
+
+    #[serde(default)]
+    pub money: u64,
+
+    #[serde(default)]
+    pub cleared_bosses: Vec<u32>,
+
+    #[serde(default)]
+    pub is_completed: bool,
+
+    // one hero per class, picking another class keeps the others
+    #[serde(default)]
+    pub heroes: Vec<HeroSave>,
+
+    #[serde(default)]
+    pub meta: MetaSave,
+
+    #[serde(default)]
+    pub achievements: AchievementSave,
+
+    #[serde(default)]
+    pub shop: ShopSave,
+
+    #[serde(default)]
+    pub history: HistorySave,
+}
+
+impl PlayerData {
+    pub fn hero(&self, class: &str) -> Option<&HeroSave> {
+        self.heroes.iter().find(|save| save.class == class)
+    }
+
+    pub fn set_hero(&mut self, save: HeroSave) {
+        self.remove_hero(&save.class);
+        self.heroes.push(save);
+    }
+
+    pub fn remove_hero(&mut self, class: &str) {
+        self.heroes.retain(|save| save.class != class);
+    }
+}
+
+// Hero progress kept in the profile, the level is derived from exp on load
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct HeroSave {
+    #[serde(default)]
+    pub class: String,
+    pub exp: f64,
+    pub state_point: u32,
+    pub str: u32,
+    pub def: u32,
+    pub agi: u32,
+}
+
+// Survives game over, spent on permanent upgrades between runs
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MetaSave {
+    pub essence: u64,
+    pub extra_turns: u32,
+    pub drop_chance: u32,
+    pub starting_gear: u32,
+}
+
+// Unlocked achievements and the lifetime counters they are checked against
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AchievementSave {
+    pub unlocked: Vec<u32>,
+    pub critical_hits: u32,
+    pub monsters_defeated: u32,
+    pub bosses_defeated: u32,
+    pub drops_found: u32,
+    pub travels: u32,
+    pub best_lv: u32,
+    pub weapon_elements: u32,
+    pub flawless_runs: u32,
+}
+
+// What the shop has on its shelves until the next restock
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ShopSave {
+    pub offers: Vec<StockOffer>,
+    // victories since the last restock
+    pub battles: u32,
+    pub restocks: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StockOffer {
+    pub item_type: ItemType,
+    pub id: u32,
+    // percent off, a deal when above 0
+    #[serde(default)]
+    pub discount: u32,
+    #[serde(default)]
+    pub sold: bool,
+}
+
+impl StockOffer {
+    pub fn price(&self, base: u64) -> u64 {
+        base * (100 - self.discount.min(100)) as u64 / 100
+    }
 }
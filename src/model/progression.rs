@@ -0,0 +1,88 @@
+use bevy::{asset::Asset, ecs::system::Resource, reflect::TypePath};
+use serde::Deserialize;
+
+// Every exp, level and hp curve in one place, loaded from data/progression.json.
+// The asset is copied into a resource of the same type so stat math can borrow it directly
+#[derive(Deserialize, Asset, Resource, TypePath, Debug, Clone, PartialEq)]
+pub struct Progression {
+    // exp to go from lv to lv + 1 is exp_base * 2^(lv / exp_doubling_lv)
+    pub exp_base: f64,
+    pub exp_doubling_lv: f64,
+    // exp dropped by a monster of lv
+    pub monster_exp_base: f64,
+    pub monster_exp_doubling_lv: f64,
+    // hp gained from level on top of the class base hp
+    pub hp_base: f32,
+    pub hp_doubling_lv: f32,
+    pub level_cap: u32,
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Self {
+            exp_base: 100.,
+            exp_doubling_lv: 10.,
+            monster_exp_base: 577.1124142,
+            monster_exp_doubling_lv: 10.,
+            hp_base: 3.,
+            hp_doubling_lv: 20.,
+            level_cap: 99,
+        }
+    }
+}
+
+impl Progression {
+    pub fn exp_to_next_lv(&self, lv: u32) -> f64 {
+        self.exp_base * 2_f64.powf(lv as f64 / self.exp_doubling_lv)
+    }
+
+    // total exp a hero holds the moment it reaches lv
+    pub fn total_exp_for_lv(&self, lv: u32) -> f64 {
+        (1..lv.min(self.level_cap))
+            .map(|i| self.exp_to_next_lv(i))
+            .sum()
+    }
+
+    pub fn lv_for_exp(&self, exp: f64) -> u32 {
+        let mut lv = 1;
+        let mut total = 0.;
+        while lv < self.level_cap {
+            total += self.exp_to_next_lv(lv);
+            if total > exp {
+                break;
+            }
+            lv += 1;
+        }
+        lv
+    }
+
+    pub fn monster_exp(&self, lv: u32) -> f64 {
+        self.monster_exp_base * 2_f64.powf(lv as f64 / self.monster_exp_doubling_lv)
+    }
+
+    pub fn lv_hp(&self, lv: u32) -> f32 {
+        self.hp_base * 2_f32.powf(lv as f32 / self.hp_doubling_lv)
+    }
+}
+
+#[test]
+fn test_progression_curve() {
+    let progression = Progression::default();
+
+    let mut last_total = -1.;
+    for lv in 1..=progression.level_cap {
+        let total = progression.total_exp_for_lv(lv);
+        assert!(total > last_total, "curve is not monotonic at lv {}", lv);
+        last_total = total;
+
+        // level-up detection lands exactly on the curve
+        assert_eq!(progression.lv_for_exp(total), lv);
+        if lv > 1 {
+            assert_eq!(progression.lv_for_exp(total - 0.01), lv - 1);
+        }
+    }
+
+    assert_eq!(progression.lv_for_exp(f64::MAX), progression.level_cap);
+    assert_eq!(progression.exp_to_next_lv(1).floor(), 107.);
+    assert_eq!(progression.total_exp_for_lv(40).floor(), 20799.);
+}
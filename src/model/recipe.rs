@@ -0,0 +1,214 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use rand::{seq::IteratorRandom, Rng};
+use serde::Deserialize;
+
+use super::ItemType;
+
+// Every crafting recipe, the file is a plain json array
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct RecipeBook(pub Vec<RecipeData>);
+
+impl RecipeBook {
+    pub fn get(&self, id: u32) -> Option<&RecipeData> {
+        self.0.iter().find(|recipe| recipe.id == id)
+    }
+
+    // catalog is (type, id, tier) of every equipment
+    pub fn validate(&self, catalog: &[(ItemType, u32, u32)]) -> Result<(), (u32, RecipeError)> {
+        for (index, recipe) in self.0.iter().enumerate() {
+            if self.0[..index].iter().any(|other| other.id == recipe.id) {
+                return Err((recipe.id, RecipeError::DuplicateId));
+            }
+            recipe.validate(catalog).map_err(|err| (recipe.id, err))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecipeError {
+    DuplicateId,
+    NotEquipment,
+    NoIngredients,
+    // nothing of the next tier exists for that slot
+    NoOutcome,
+}
+
+// count items of one slot and tier, plus materials, make a random item one tier up
+#[derive(Deserialize, Debug, Clone)]
+pub struct RecipeData {
+    pub id: u32,
+    pub item_type: ItemType,
+    pub tier: u32,
+    pub count: u32,
+    #[serde(default)]
+    pub materials: u32,
+}
+
+impl RecipeData {
+    pub fn validate(&self, catalog: &[(ItemType, u32, u32)]) -> Result<(), RecipeError> {
+        if self.item_type == ItemType::Others {
+            return Err(RecipeError::NotEquipment);
+        }
+        let has_ingredient = catalog
+            .iter()
+            .any(|(item_type, _, tier)| *item_type == self.item_type && *tier == self.tier);
+        if self.count == 0 || !has_ingredient {
+            return Err(RecipeError::NoIngredients);
+        }
+        if self.outcomes(catalog).is_empty() {
+            return Err(RecipeError::NoOutcome);
+        }
+        Ok(())
+    }
+
+    // ids of everything the recipe can turn into
+    pub fn outcomes(&self, catalog: &[(ItemType, u32, u32)]) -> Vec<u32> {
+        catalog
+            .iter()
+            .filter(|(item_type, _, tier)| *item_type == self.item_type && *tier == self.tier + 1)
+            .map(|(_, id, _)| *id)
+            .collect()
+    }
+
+    // every outcome is equally likely
+    pub fn roll(&self, catalog: &[(ItemType, u32, u32)], rng: &mut impl Rng) -> Option<u32> {
+        self.outcomes(catalog).into_iter().choose(rng)
+    }
+}
+
+impl std::fmt::Display for RecipeData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}x T{} {}", self.count, self.tier, self.item_type)?;
+        if self.materials > 0 {
+            write!(f, " + {}m", self.materials)?;
+        }
+        write!(f, " -> T{}", self.tier + 1)
+    }
+}
+
+#[test]
+fn test_recipe_validation() {
+    let book: Vec<RecipeData> = serde_json::from_str(
+        r#"[
+            {"id": 1, "item_type": "Weapon", "tier": 0, "count": 3, "materials": 2},
+            {"id": 2, "item_type": "Armor", "tier": 1, "count": 3}
+        ]"#,
+    )
+    .unwrap();
+    let mut book = RecipeBook(book);
+    let catalog = vec![
+        (ItemType::Weapon, 1, 0),
+        (ItemType::Weapon, 2, 1),
+        (ItemType::Armor, 1, 1),
+        (ItemType::Armor, 2, 2),
+    ];
+    assert_eq!(book.validate(&catalog), Ok(()));
+
+    let recipe = |id: u32, item_type: ItemType, tier: u32, count: u32| RecipeData {
+        id,
+        item_type,
+        tier,
+        count,
+        materials: 0,
+    };
+    assert_eq!(
+        recipe(3, ItemType::Armor, 2, 3).validate(&catalog),
+        Err(RecipeError::NoOutcome)
+    );
+    assert_eq!(
+        recipe(3, ItemType::Helmet, 1, 3).validate(&catalog),
+        Err(RecipeError::NoIngredients)
+    );
+    assert_eq!(
+        recipe(3, ItemType::Armor, 1, 0).validate(&catalog),
+        Err(RecipeError::NoIngredients)
+    );
+    assert_eq!(
+        recipe(3, ItemType::Others, 1, 3).validate(&catalog),
+        Err(RecipeError::NotEquipment)
+    );
+
+    book.0.push(recipe(2, ItemType::Weapon, 0, 1));
+    assert_eq!(book.validate(&catalog), Err((2, RecipeError::DuplicateId)));
+}
+
+#[test]
+fn test_recipe_outcome_distribution() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let catalog = vec![
+        (ItemType::Weapon, 1, 0),
+        (ItemType::Weapon, 2, 1),
+        (ItemType::Weapon, 3, 1),
+        (ItemType::Weapon, 4, 1),
+        (ItemType::Armor, 2, 1),
+    ];
+    let recipe = RecipeData {
+        id: 1,
+        item_type: ItemType::Weapon,
+        tier: 0,
+        count: 3,
+        materials: 0,
+    };
+    let mut rng = StdRng::seed_from_u64(48);
+
+    let mut counts = std::collections::HashMap::new();
+    for _ in 0..3000 {
+        let id = recipe.roll(&catalog, &mut rng).unwrap();
+        *counts.entry(id).or_insert(0) += 1;
+    }
+
+    // only the next tier of the same slot, each about a third of the time
+    assert_eq!(counts.len(), 3);
+    for id in [2, 3, 4] {
+        let count = counts[&id];
+        assert!(
+            (800..1200).contains(&count),
+            "id {} rolled {} times",
+            id,
+            count
+        );
+    }
+}
+
+#[test]
+fn test_bundled_recipes_cover_every_tier() {
+    #[derive(Deserialize)]
+    struct Row {
+        id: u32,
+        tier: u32,
+    }
+
+    let mut catalog = Vec::new();
+    for (item_type, file) in [
+        (ItemType::Weapon, "weapon"),
+        (ItemType::Armor, "armor"),
+        (ItemType::Helmet, "helmet"),
+        (ItemType::Shoes, "shoes"),
+    ] {
+        let mut reader = csv::Reader::from_path(format!("assets/data/1.{}.csv", file)).unwrap();
+        for row in reader.deserialize::<Row>() {
+            let row = row.unwrap();
+            catalog.push((item_type.clone(), row.id, row.tier));
+        }
+    }
+
+    let json = std::fs::read_to_string("assets/data/1.recipe.json").unwrap();
+    let book = RecipeBook(serde_json::from_str(&json).unwrap());
+    assert_eq!(book.validate(&catalog), Ok(()));
+
+    // armor pieces go all the way up to the top tier
+    for item_type in [ItemType::Armor, ItemType::Helmet, ItemType::Shoes] {
+        for tier in 1..10 {
+            assert!(
+                book.0
+                    .iter()
+                    .any(|recipe| recipe.item_type == item_type && recipe.tier == tier),
+                "no {:?} recipe for tier {}",
+                item_type,
+                tier
+            );
+        }
+    }
+}
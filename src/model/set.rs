@@ -0,0 +1,150 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use serde::Deserialize;
+
+use super::ItemType;
+
+// Every equipment set in the game, the file is a plain json array
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct SetBook(pub Vec<SetData>);
+
+impl SetBook {
+    // sets with at least one bonus reached by the equipped (type, id) pairs
+    pub fn active(&self, equipped: &[(ItemType, u32)]) -> Vec<ActiveSet> {
+        self.0
+            .iter()
+            .filter_map(|set| {
+                let pieces = set
+                    .pieces
+                    .iter()
+                    .filter(|piece| equipped.contains(&(piece.item_type.clone(), piece.id)))
+                    .count() as u32;
+
+                // bonuses stack, a 4-piece set also keeps its 2-piece bonus
+                let reached = set.bonuses.iter().filter(|bonus| bonus.pieces <= pieces);
+                let mut active = ActiveSet {
+                    name: set.name.clone(),
+                    pieces,
+                    attack: 0.,
+                    defense: 0.,
+                };
+                let mut any = false;
+                for bonus in reached {
+                    active.attack += bonus.attack;
+                    active.defense += bonus.defense;
+                    any = true;
+                }
+
+                any.then_some(active)
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SetData {
+    pub name: String,
+    pub pieces: Vec<SetPiece>,
+    pub bonuses: Vec<SetBonus>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SetPiece {
+    pub item_type: ItemType,
+    pub id: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SetBonus {
+    // equipped pieces needed
+    pub pieces: u32,
+    #[serde(default)]
+    pub attack: f32,
+    #[serde(default)]
+    pub defense: f32,
+}
+
+// Bonus currently granted by one set, summed over every reached threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveSet {
+    pub name: String,
+    pub pieces: u32,
+    pub attack: f32,
+    pub defense: f32,
+}
+
+impl std::fmt::Display for ActiveSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{} ({})", self.name, self.pieces)?;
+        if self.attack > 0. {
+            write!(f, " +{} Atk", self.attack)?;
+        }
+        if self.defense > 0. {
+            write!(f, " +{} Def", self.defense)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_set_bonus_thresholds() {
+    let book: Vec<SetData> = serde_json::from_str(
+        r#"[{
+            "name": "Guard",
+            "pieces": [
+                {"item_type": "Weapon", "id": 1},
+                {"item_type": "Armor", "id": 5},
+                {"item_type": "Helmet", "id": 5},
+                {"item_type": "Shoes", "id": 5}
+            ],
+            "bonuses": [
+                {"pieces": 2, "defense": 2},
+                {"pieces": 4, "attack": 3, "defense": 3}
+            ]
+        }]"#,
+    )
+    .unwrap();
+    let book = SetBook(book);
+
+    assert!(book.active(&[(ItemType::Armor, 5)]).is_empty());
+    // same id in the wrong slot does not count
+    assert!(book
+        .active(&[(ItemType::Armor, 5), (ItemType::Weapon, 5)])
+        .is_empty());
+
+    let two = book.active(&[(ItemType::Armor, 5), (ItemType::Shoes, 5)]);
+    assert_eq!(two.len(), 1);
+    assert_eq!((two[0].attack, two[0].defense), (0., 2.));
+
+    let four = book.active(&[
+        (ItemType::Weapon, 1),
+        (ItemType::Armor, 5),
+        (ItemType::Helmet, 5),
+        (ItemType::Shoes, 5),
+    ]);
+    assert_eq!(
+        (four[0].pieces, four[0].attack, four[0].defense),
+        (4, 3., 5.)
+    );
+}
+
+#[test]
+fn test_every_set_has_four_pieces() {
+    let json = std::fs::read_to_string("assets/data/1.set.json").unwrap();
+    let book: Vec<SetData> = serde_json::from_str(&json).unwrap();
+
+    for set in book.iter() {
+        assert_eq!(set.pieces.len(), 4, "{} is not a full set", set.name);
+        assert!(
+            set.bonuses.iter().any(|bonus| bonus.pieces == 4),
+            "{} has no 4-piece bonus",
+            set.name
+        );
+        // one piece per slot
+        assert!(set.pieces.iter().all(|piece| set
+            .pieces
+            .iter()
+            .filter(|other| other.item_type == piece.item_type)
+            .count()
+            == 1));
+    }
+}
@@ -1,8 +1,11 @@
-use bevy::{ecs::component::Component, reflect::Reflect};
+use bevy::{color::Color, ecs::component::Component, reflect::Reflect};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use super::{EquipmentData, ItemType};
+use super::{
+    affix::{Affix, AffixKind},
+    ItemType,
+};
 
 #[serde_as]
 #[derive(
@@ -15,22 +18,162 @@ pub struct DropEquipment {
     pub image: Option<String>,
     pub description: String,
     pub item_type: ItemType,
+    // duplicates picked up after the first, spent on upgrades
+    #[serde(default)]
+    pub copies: u32,
+    // gem ids socketed into this weapon
+    #[serde(default)]
+    pub sockets: Vec<u32>,
+    #[serde(default)]
+    pub rarity: Rarity,
+    // rolled when dropped, see AffixBook
+    #[serde(default)]
+    pub affixes: Vec<Affix>,
 }
 
+#[derive(
+    Debug, Default, Clone, Reflect, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize,
+)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    // roll is in [0, 1)
+    pub fn from_roll(roll: f32) -> Self {
+        match roll {
+            r if r < 0.01 => Rarity::Legendary,
+            r if r < 0.05 => Rarity::Epic,
+            r if r < 0.15 => Rarity::Rare,
+            r if r < 0.40 => Rarity::Uncommon,
+            _ => Rarity::Common,
+        }
+    }
+
+    pub fn affix_count(&self) -> usize {
+        match self {
+            Rarity::Common => 0,
+            Rarity::Uncommon => 1,
+            Rarity::Rare => 2,
+            Rarity::Epic => 3,
+            Rarity::Legendary => 4,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Rarity::Common => Color::WHITE,
+            Rarity::Uncommon => Color::srgb(0.3, 0.9, 0.3),
+            Rarity::Rare => Color::srgb(0.3, 0.6, 1.),
+            Rarity::Epic => Color::srgb(0.7, 0.3, 0.9),
+            Rarity::Legendary => Color::srgb(1., 0.6, 0.1),
+        }
+    }
+}
+
+// upgrades past this level can fail
+const SAFE_UPGRADE_LV: u32 = 10;
+
 // all item ability is related to it assigned level
 impl DropEquipment {
+    // a plain common item with no name, data books fill in the rest
+    pub fn new(item_type: ItemType, id: u32, level: u32) -> Self {
+        Self {
+            id,
+            level,
+            item_type,
+            ..Default::default()
+        }
+    }
+
     pub fn set_level(&mut self, lv: u32) {
         self.level = self.level.max(lv);
     }
 
-    // for weapon true damage
-    pub fn weapon(&self) -> f32 {
-        self.level as f32
+    // a duplicate picked up, keeps the better roll, the summed affixes settle equal rarities
+    pub fn merge(&mut self, other: &DropEquipment) {
+        self.set_level(other.level);
+        self.copies += 1;
+        if (&other.rarity, other.affix_total()) > (&self.rarity, self.affix_total()) {
+            self.rarity = other.rarity.clone();
+            self.affixes = other.affixes.clone();
+        }
+    }
+
+    pub fn affix(&self, kind: &AffixKind) -> u32 {
+        self.affixes
+            .iter()
+            .filter(|affix| affix.kind == *kind)
+            .map(|affix| affix.value)
+            .sum()
+    }
+
+    pub fn affix_total(&self) -> u32 {
+        self.affixes.iter().map(|affix| affix.value).sum()
+    }
+
+    // a spare copy halves the money cost
+    pub fn upgrade_cost(&self, tier: u32) -> u64 {
+        let cost = 10 * (self.level as u64 + 1).pow(2) * (tier as u64 + 1);
+        if self.copies > 0 {
+            cost / 2
+        } else {
+            cost
+        }
+    }
+
+    // shop price of a fresh copy
+    pub fn price(&self, tier: u32) -> u64 {
+        10 * (tier as u64 + 1).pow(2) * self.level.max(1) as u64
+    }
+
+    // the whole stack goes, spare copies included
+    pub fn sell_value(&self, tier: u32) -> u64 {
+        self.price(tier) * (self.copies as u64 + 1) / 2
+    }
+
+    pub fn salvage_materials(&self, tier: u32) -> u32 {
+        (tier + 1) * (self.copies + 1)
+    }
+
+    // materials that stand in for a spare copy on upgrade
+    pub fn upgrade_materials(tier: u32) -> u32 {
+        3 * (tier + 1)
+    }
+
+    // 5% per level over the safe level, at most half of the tries fail
+    pub fn upgrade_fail_chance(&self) -> f32 {
+        (self.level.saturating_sub(SAFE_UPGRADE_LV) as f32 * 0.05).min(0.5)
     }
 
-    // for armor damage reduction
-    pub fn armor(&self) -> f32 {
-        self.level as f32
+    // roll is in [0, 1), returns whether the level went up
+    pub fn upgrade(&mut self, roll: f32) -> bool {
+        self.copies = self.copies.saturating_sub(1);
+        if roll < self.upgrade_fail_chance() {
+            return false;
+        }
+        self.level += 1;
+        true
+    }
+
+    // shoes, multiplier on walking speed
+    pub fn walk_speed(&self, tier: u32) -> f32 {
+        (1. + tier as f32 * 0.03 + self.level as f32 * 0.005).min(1.5)
+    }
+
+    // shoes, multiplier on how fast the encounter gauge fills
+    pub fn encounter_rate(&self, tier: u32) -> f32 {
+        (1. - tier as f32 * 0.03 - self.level as f32 * 0.005).max(0.6)
+    }
+
+    // helmet, percent points taken off the attacker's crit rate
+    pub fn crit_resist(&self, tier: u32) -> f32 {
+        (tier as f32 * 1.5 + self.level as f32 * 0.1).min(30.)
     }
 }
 
@@ -63,6 +206,13 @@ pub enum Element {
 pub struct Tier(String);
 
 impl Tier {
+    // numeric part of the tier, 0 when there is none
+    pub fn rank(&self) -> u32 {
+        self.split_alphanumberic()
+            .0
+            .map_or(0, |num| num.max(0) as u32)
+    }
+
     pub fn contain(&self, tier: &Tier) -> bool {
         let (container_num, container_letter) = self.split_alphanumberic();
         let (num, letter) = tier.split_alphanumberic();
@@ -85,3 +235,98 @@ impl Tier {
         (n.parse().ok(), s)
     }
 }
+
+#[test]
+fn test_equipment_upgrade() {
+    let mut eq = DropEquipment {
+        level: 1,
+        ..Default::default()
+    };
+    assert_eq!(eq.upgrade_cost(1), 80);
+    assert!(eq.upgrade(0.));
+    assert_eq!(eq.level, 2);
+
+    // a spare copy is used up and halves the price
+    eq.copies = 1;
+    assert_eq!(eq.upgrade_cost(1), 90);
+    assert!(eq.upgrade(0.99));
+    assert_eq!(eq.copies, 0);
+
+    eq.level = SAFE_UPGRADE_LV;
+    assert_eq!(eq.upgrade_fail_chance(), 0.);
+    eq.level = SAFE_UPGRADE_LV + 4;
+    assert!(!eq.upgrade(0.1));
+    assert_eq!(eq.level, SAFE_UPGRADE_LV + 4);
+    eq.level = 99;
+    assert_eq!(eq.upgrade_fail_chance(), 0.5);
+}
+
+#[test]
+fn test_shoes_and_helmet_effects() {
+    let mut eq = DropEquipment {
+        level: 1,
+        ..Default::default()
+    };
+    assert!(eq.walk_speed(2) > eq.walk_speed(1));
+    assert!(eq.encounter_rate(2) < eq.encounter_rate(1));
+    assert!(eq.crit_resist(2) > eq.crit_resist(1));
+
+    eq.level = 1000;
+    assert_eq!(eq.walk_speed(10), 1.5);
+    assert_eq!(eq.encounter_rate(10), 0.6);
+    assert_eq!(eq.crit_resist(10), 30.);
+}
+
+#[test]
+fn test_rarity_and_affixes_round_trip() {
+    let eq = DropEquipment {
+        id: 1,
+        level: 3,
+        rarity: Rarity::Epic,
+        affixes: vec![
+            Affix {
+                kind: AffixKind::CritRate,
+                value: 4,
+            },
+            Affix {
+                kind: AffixKind::ExpGain,
+                value: 10,
+            },
+        ],
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&eq).unwrap();
+    let back: DropEquipment = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, eq);
+    assert_eq!(back.affix(&AffixKind::ExpGain), 10);
+
+    // older saves have neither field
+    let old: DropEquipment = serde_json::from_str(
+        r#"{"id": 1, "level": 3, "name": "", "image": null, "description": "", "item_type": "Weapon"}"#,
+    )
+    .unwrap();
+    assert_eq!(old.rarity, Rarity::Common);
+    assert!(old.affixes.is_empty());
+
+    let mut merged = old.clone();
+    merged.merge(&eq);
+    assert_eq!(merged.copies, 1);
+    assert_eq!(merged.rarity, Rarity::Epic);
+
+    // an equal rarity with better rolls replaces the affixes, a weaker one only adds a copy
+    let better = DropEquipment {
+        affixes: vec![Affix {
+            kind: AffixKind::CritRate,
+            value: 20,
+        }],
+        ..eq.clone()
+    };
+    merged.merge(&better);
+    assert_eq!(merged.copies, 2);
+    assert_eq!(merged.affixes, better.affixes);
+
+    merged.merge(&eq);
+    assert_eq!(merged.copies, 3);
+    assert_eq!(merged.affixes, better.affixes);
+}
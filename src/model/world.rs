@@ -0,0 +1,256 @@
+use bevy::{asset::Asset, reflect::TypePath};
+use serde::Deserialize;
+
+// Every LDtk project and level the game can travel to, loaded from data/world.json
+#[derive(Deserialize, Asset, TypePath, Debug, Clone)]
+pub struct WorldManifest {
+    // name or iid of the level a run starts in
+    pub start: String,
+    // spawn point in the start level the hero lands on
+    pub start_location: String,
+    pub projects: Vec<ProjectData>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectData {
+    // relative to the assets folder
+    pub path: String,
+    pub levels: Vec<LevelData>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LevelData {
+    // stable name ports refer to, the iid works as well
+    pub name: String,
+    pub iid: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub recommended_lv: u32,
+    // names of the levels a port here may lead to
+    #[serde(default)]
+    pub connections: Vec<String>,
+}
+
+impl LevelData {
+    pub fn is(&self, key: &str) -> bool {
+        self.name == key || self.iid == key
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldError {
+    DuplicateLevel(String),
+    UnknownStart(String),
+    UnknownConnection { from: String, to: String },
+}
+
+impl WorldManifest {
+    // the level and the project it lives in, by name or iid
+    pub fn level(&self, key: &str) -> Option<(&ProjectData, &LevelData)> {
+        self.projects.iter().find_map(|project| {
+            project
+                .levels
+                .iter()
+                .find(|level| level.is(key))
+                .map(|level| (project, level))
+        })
+    }
+
+    pub fn start_level(&self) -> Option<(&ProjectData, &LevelData)> {
+        self.level(&self.start)
+    }
+
+    pub fn is_connected(&self, from: &str, to: &str) -> bool {
+        let (Some((_, from)), Some((_, to))) = (self.level(from), self.level(to)) else {
+            return false;
+        };
+        from.connections.iter().any(|key| to.is(key))
+    }
+
+    pub fn validate(&self) -> Result<(), WorldError> {
+        let levels: Vec<&LevelData> = self
+            .projects
+            .iter()
+            .flat_map(|project| project.levels.iter())
+            .collect();
+
+        for (index, level) in levels.iter().enumerate() {
+            if levels[..index]
+                .iter()
+                .any(|other| other.is(&level.name) || other.is(&level.iid))
+            {
+                return Err(WorldError::DuplicateLevel(level.name.clone()));
+            }
+        }
+        if self.start_level().is_none() {
+            return Err(WorldError::UnknownStart(self.start.clone()));
+        }
+        for level in levels.iter() {
+            for to in level.connections.iter() {
+                if self.level(to).is_none() {
+                    return Err(WorldError::UnknownConnection {
+                        from: level.name.clone(),
+                        to: to.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_world_manifest() {
+    let mut world: WorldManifest = serde_json::from_str(
+        r#"{
+            "start": "forest",
+            "start_location": "Gate",
+            "projects": [
+                {
+                    "path": "map/forest.ldtk",
+                    "levels": [
+                        {"name": "forest", "iid": "a-1", "display_name": "Forest", "connections": ["plains"]},
+                        {"name": "cave", "iid": "a-2", "display_name": "Cave", "recommended_lv": 30}
+                    ]
+                },
+                {
+                    "path": "map/plains.ldtk",
+                    "levels": [
+                        {"name": "plains", "iid": "b-1", "display_name": "Plains", "connections": ["a-1"]}
+                    ]
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(world.validate(), Ok(()));
+
+    assert_eq!(world.start_level().unwrap().0.path, "map/forest.ldtk");
+    assert_eq!(world.level("b-1").unwrap().1.name, "plains");
+    assert_eq!(world.level("cave").unwrap().1.recommended_lv, 30);
+    assert!(world.is_connected("forest", "b-1"));
+    assert!(world.is_connected("plains", "forest"));
+    assert!(!world.is_connected("forest", "cave"));
+
+    world.projects[0].levels[1]
+        .connections
+        .push(String::from("desert"));
+    assert_eq!(
+        world.validate(),
+        Err(WorldError::UnknownConnection {
+            from: String::from("cave"),
+            to: String::from("desert"),
+        })
+    );
+
+    world.projects[0].levels[1].connections.clear();
+    world.projects[1].levels[0].iid = String::from("a-2");
+    assert_eq!(
+        world.validate(),
+        Err(WorldError::DuplicateLevel(String::from("plains")))
+    );
+
+    world.start = String::from("nowhere");
+    world.projects[1].levels[0].iid = String::from("b-1");
+    assert_eq!(
+        world.validate(),
+        Err(WorldError::UnknownStart(String::from("nowhere")))
+    );
+}
+
+// every level in the bundled manifest carries the fields the LDtk bundles expect
+#[test]
+fn test_bundled_world_projects() {
+    use serde_json::Value;
+
+    fn entities<'a>(level: &'a Value, identifier: &'a str) -> impl Iterator<Item = &'a Value> {
+        level["layerInstances"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .flat_map(|layer| layer["entityInstances"].as_array().into_iter().flatten())
+            .filter(move |entity| entity["__identifier"] == identifier)
+    }
+
+    fn field<'a>(entity: &'a Value, identifier: &str) -> &'a Value {
+        entity["fieldInstances"]
+            .as_array()
+            .and_then(|fields| {
+                fields
+                    .iter()
+                    .find(|field| field["__identifier"] == identifier)
+            })
+            .map(|field| &field["__value"])
+            .unwrap_or(&Value::Null)
+    }
+
+    let world: WorldManifest =
+        serde_json::from_str(&std::fs::read_to_string("assets/data/world.json").unwrap()).unwrap();
+    assert_eq!(world.validate(), Ok(()));
+
+    let mut levels = Vec::new();
+    for project in world.projects.iter() {
+        let json = std::fs::read_to_string(format!("assets/{}", project.path)).unwrap();
+        let ldtk: Value = serde_json::from_str(&json).unwrap();
+        for data in project.levels.iter() {
+            let level = ldtk["levels"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|level| level["iid"] == data.iid.as_str())
+                .unwrap_or_else(|| panic!("{} is not in {}", data.name, project.path))
+                .clone();
+            levels.push((data.name.clone(), level));
+        }
+    }
+
+    let locations = |name: &str| -> Vec<String> {
+        let (_, data) = world.level(name).unwrap();
+        let (_, level) = levels.iter().find(|(key, _)| *key == data.name).unwrap();
+        entities(level, "Player")
+            .filter_map(|spawn| field(spawn, "Location").as_str())
+            .map(|location| location.trim().to_string())
+            .collect()
+    };
+
+    assert!(
+        locations(&world.start).contains(&world.start_location),
+        "no spawn point {:?} in the start level",
+        world.start_location
+    );
+
+    for (name, level) in levels.iter() {
+        for area in entities(level, "Field") {
+            assert!(
+                field(area, "MonsterID")
+                    .as_array()
+                    .is_some_and(|ids| !ids.is_empty()),
+                "{name}: field without monsters"
+            );
+            assert!(
+                field(area, "Level").is_u64(),
+                "{name}: field without a level"
+            );
+            assert!(
+                field(area, "ZoneId").is_u64(),
+                "{name}: field without a zone"
+            );
+        }
+
+        let mut ports = 0;
+        for port in entities(level, "Port") {
+            let to = field(port, "Level").as_str().unwrap_or_default();
+            let location = field(port, "Location").as_str().unwrap_or_default().trim();
+            assert!(
+                world.is_connected(name, to),
+                "{name}: port to {to:?} is not a connection"
+            );
+            assert!(
+                locations(to).iter().any(|spawn| spawn == location),
+                "{name}: no spawn point {location:?} in {to}"
+            );
+            ports += 1;
+        }
+        assert!(ports > 0, "{name} has no way out");
+    }
+}
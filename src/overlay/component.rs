@@ -1,6 +1,6 @@
 use bevy::{ecs::component::Component, reflect::Reflect, time::Timer};
 
-use crate::model::{sub::DropEquipment, EquipmentData};
+use crate::model::{sub::DropEquipment, ItemType};
 
 #[derive(Component)]
 pub struct ShopImage;
@@ -12,6 +12,22 @@ pub enum ShopTab {
     Armor,
     Helmet,
     Shoes,
+    Items,
+    Gems,
+    Craft,
+}
+
+impl ShopTab {
+    // equipment tabs only
+    pub fn item_type(&self) -> Option<ItemType> {
+        match self {
+            ShopTab::Weapon => Some(ItemType::Weapon),
+            ShopTab::Armor => Some(ItemType::Armor),
+            ShopTab::Helmet => Some(ItemType::Helmet),
+            ShopTab::Shoes => Some(ItemType::Shoes),
+            ShopTab::Items | ShopTab::Gems | ShopTab::Craft => None,
+        }
+    }
 }
 
 #[derive(Component)]
@@ -20,6 +36,36 @@ pub struct UnequipButton(pub DropEquipment);
 #[derive(Component)]
 pub struct UpgradeButton(pub DropEquipment);
 
+#[derive(Component)]
+pub struct SellButton(pub DropEquipment);
+
+#[derive(Component)]
+pub struct SalvageButton(pub DropEquipment);
+
+// Level under which the bulk sell button sells, kept on the shop
+#[derive(Component)]
+pub struct SellBelowLevel(pub u32);
+
+impl Default for SellBelowLevel {
+    fn default() -> Self {
+        Self(5)
+    }
+}
+
+// Moves SellBelowLevel up or down
+#[derive(Component)]
+pub struct SellLevelButton(pub i32);
+
+#[derive(Component)]
+pub struct BulkSellButton;
+
+// Confirm in the bulk sell popup
+#[derive(Component)]
+pub struct ConfirmBulkSellButton {
+    pub item_type: ItemType,
+    pub below: u32,
+}
+
 #[derive(Component)]
 pub struct SelectedEqPic;
 
@@ -62,7 +108,26 @@ pub struct ScrollingListContainer;
 pub struct EqipmentRow(pub DropEquipment);
 
 #[derive(Component)]
-pub struct BuyButton(pub DropEquipment);
+pub struct BuyButton(pub DropEquipment, pub u64);
+
+// Consumables are bought straight away, no confirm popup
+#[derive(Component)]
+pub struct BuyConsumableButton(pub u32);
+
+#[derive(Component)]
+pub struct BuyGemButton(pub u32);
+
+// Gem id to put into the equipped weapon
+#[derive(Component)]
+pub struct SocketGemButton(pub u32);
+
+// Socket index on the equipped weapon, the gem goes back to storage
+#[derive(Component)]
+pub struct UnsocketGemButton(pub usize);
+
+// Recipe id to craft from storage
+#[derive(Component)]
+pub struct CraftButton(pub u32);
 
 #[derive(Component, Default, PartialEq, Eq)]
 pub struct SelectedEquipment(pub Option<DropEquipment>);
@@ -82,8 +147,9 @@ pub struct ConfirmPopup;
 #[derive(Component)]
 pub struct CancelButton;
 
+// buys the item for the price
 #[derive(Component)]
-pub struct ConfirmButton(pub u32);
+pub struct ConfirmButton(pub DropEquipment, pub u64);
 
 #[derive(Component)]
 pub struct WarningTextLayout(pub Timer);
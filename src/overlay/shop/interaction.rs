@@ -2,20 +2,25 @@ use bevy::{
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
+use rand::random;
 
 use crate::{
-    game::player::{Armor, EquipmentBelt, Helmet, Money, Player, Shoes, Weapon},
+    game::player::{
+        Armor, EquipmentAssets, EquipmentBelt, Helmet, Ledger, Money, Player, Shoes, ShopStock,
+        Storage, Weapon,
+    },
     model::{
-        armor::ArmorData, helmet::HelmetData, player::PlayerData, shoes::ShoesData,
-        weapon::WeaponData,
+        armor::ArmorData, consumable::ConsumableData, gem::GemBook, helmet::HelmetData,
+        player::PlayerData, shoes::ShoesData, weapon::WeaponData, ItemType,
     },
     overlay::component::*,
-    preload::data::PlayerPreloadHandler,
+    preload::data::{GemPreloadHandler, PlayerPreloadHandler},
     state::OverlayShopState,
 };
 
 use super::{
-    ActiveShopTab, NotEnoughMoneyPopupEvent, SpawnConfirmPopupEvent, SpawnEquipPopupEvent,
+    ActiveShopTab, NotEnoughMoneyPopupEvent, ShopMessageEvent, SpawnBulkSellPopupEvent,
+    SpawnConfirmPopupEvent, SpawnEquipPopupEvent,
 };
 
 pub fn interact_with_eq_list(
@@ -30,13 +35,10 @@ pub fn interact_with_eq_list(
             continue;
         };
 
-        match *interaction {
-            // Interaction::Hovered => *border_color = Color::VIOLET.into(),
-            Interaction::Pressed => {
-                selected.set_if_neq(SelectedEquipment(Some(row.0.clone())));
-                info!("Selected: {:?}", row.0);
-            }
-            _ => (),
+        // Interaction::Hovered => *border_color = Color::VIOLET.into(),
+        if *interaction == Interaction::Pressed {
+            selected.set_if_neq(SelectedEquipment(Some(row.0.clone())));
+            info!("Selected: {:?}", row.0);
         }
     }
 }
@@ -50,16 +52,13 @@ pub fn interact_with_shop_tab(
             continue;
         };
 
-        match *interaction {
-            // Interaction::Hovered => *border_color = Color::VIOLET.into(),
-            Interaction::Pressed => {
-                if activated.set_if_neq(ActiveShopTab(tab.clone())) {
-                    selected.set_if_neq(SelectedEquipment(None));
-                }
-
-                info!("Selected: {:?}", tab);
+        // Interaction::Hovered => *border_color = Color::VIOLET.into(),
+        if *interaction == Interaction::Pressed {
+            if activated.set_if_neq(ActiveShopTab(tab.clone())) {
+                selected.set_if_neq(SelectedEquipment(None));
             }
-            _ => (),
+
+            info!("Selected: {:?}", tab);
         }
     }
 }
@@ -71,17 +70,13 @@ pub fn interact_with_back_button(
     >,
     mut next_shop_state: ResMut<NextState<OverlayShopState>>,
 ) {
-    for (interaction, background_color) in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                // *background_color = PRESSED_BUTTON_COLOR.into();
-
-                next_shop_state.set(OverlayShopState::Closed);
-                info!("Close Shop");
-            }
+    for (interaction, _background_color) in button_query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            // *background_color = PRESSED_BUTTON_COLOR.into();
 
+            next_shop_state.set(OverlayShopState::Closed);
+            info!("Close Shop");
             // Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
-            _ => (),
         }
     }
 }
@@ -91,8 +86,8 @@ pub fn interact_with_equire_button(
         (&Interaction, &mut BackgroundColor, &EquireButton),
         (Changed<Interaction>, With<EquireButton>),
     >,
-    player_assets: Res<Assets<PlayerData>>,
-    player_id: Res<PlayerPreloadHandler>,
+    _player_assets: Res<Assets<PlayerData>>,
+    _player_id: Res<PlayerPreloadHandler>,
     mut shop_query: Query<(&mut ActiveShopTab, &mut SelectedEquipment), With<Shop>>,
     mut next_shop_state: ResMut<NextState<OverlayShopState>>,
     mut player_query: Query<&mut EquipmentBelt, With<Player>>,
@@ -101,39 +96,36 @@ pub fn interact_with_equire_button(
     helmet_data: Res<Assets<HelmetData>>,
     shoes_data: Res<Assets<ShoesData>>,
 ) {
-    for (interaction, background_color, button) in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                let Ok(mut eq_belt) = player_query.get_single_mut() else {
-                    continue;
-                };
+    for (interaction, _background_color, button) in button_query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            let Ok(mut eq_belt) = player_query.get_single_mut() else {
+                continue;
+            };
 
-                // *background_color = PRESSED_BUTTON_COLOR.into();
+            // *background_color = PRESSED_BUTTON_COLOR.into();
 
-                match button.0.item_type {
-                    crate::model::ItemType::Weapon => {
-                        eq_belt.weapon = Weapon::from(&weapon_data, button.0.clone());
-                    }
-                    crate::model::ItemType::Armor => {
-                        info!("Wearing Armor: {:?}", button.0.id);
-                        eq_belt.armor = Armor::from(&armor_data, button.0.clone());
-                    }
-                    crate::model::ItemType::Helmet => {
-                        eq_belt.helmet = Helmet::from(&helmet_data, button.0.clone())
-                    }
-                    crate::model::ItemType::Shoes => {
-                        eq_belt.shoes = Shoes::from(&shoes_data, button.0.clone())
-                    }
-                    crate::model::ItemType::Others => (),
+            match button.0.item_type {
+                crate::model::ItemType::Weapon => {
+                    eq_belt.weapon = Weapon::from(&weapon_data, button.0.clone());
                 }
-                let (mut active, mut selected) =
-                    shop_query.get_single_mut().expect("No Shop been spawned");
-
-                selected.set_if_neq(SelectedEquipment(None));
-                active.set_changed();
-                next_shop_state.set(OverlayShopState::Opened);
+                crate::model::ItemType::Armor => {
+                    info!("Wearing Armor: {:?}", button.0.id);
+                    eq_belt.armor = Armor::from(&armor_data, button.0.clone());
+                }
+                crate::model::ItemType::Helmet => {
+                    eq_belt.helmet = Helmet::from(&helmet_data, button.0.clone())
+                }
+                crate::model::ItemType::Shoes => {
+                    eq_belt.shoes = Shoes::from(&shoes_data, button.0.clone())
+                }
+                crate::model::ItemType::Others => (),
             }
+            let (mut active, mut selected) =
+                shop_query.get_single_mut().expect("No Shop been spawned");
 
+            selected.set_if_neq(SelectedEquipment(None));
+            active.set_changed();
+            next_shop_state.set(OverlayShopState::Opened);
             //     match equipment_query
             //         .iter()
             //         .find(|(eq, _, _)| eq.get() == button.0)
@@ -147,89 +139,77 @@ pub fn interact_with_equire_button(
             //         _ => todo!(),
             //     }
             //     *background_color = PRESSED_BUTTON_COLOR.into();
-
             //     selected.set_if_neq(SelectedEquipment(0));
             //     active.set_changed();
             //     next_shop_state.set(OverlayShopState::Opened);
             //     info!("Equire Equipment{}", button.0);
             // }
             // Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
-            _ => (),
         }
     }
 }
 
+// Spends money, and a spare copy when there is one, to raise the equipped item by one level
 pub fn interact_with_upgrade_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor, &UpgradeButton),
-        (Changed<Interaction>, With<UpgradeButton>),
-    >,
-    player_assets: Res<Assets<PlayerData>>,
-    player_id: Res<PlayerPreloadHandler>,
+    button_query: Query<(&Interaction, &UpgradeButton), Changed<Interaction>>,
     mut shop_query: Query<(&mut ActiveShopTab, &mut SelectedEquipment), With<Shop>>,
-    mut next_shop_state: ResMut<NextState<OverlayShopState>>,
-    mut player_query: Query<&mut EquipmentBelt, With<Player>>,
-    weapon_data: Res<Assets<WeaponData>>,
-    armor_data: Res<Assets<ArmorData>>,
-    helmet_data: Res<Assets<HelmetData>>,
-    shoes_data: Res<Assets<ShoesData>>,
+    mut player_query: Query<(&mut EquipmentBelt, &mut Storage, &mut Money), With<Player>>,
+    assets: EquipmentAssets,
+    mut ledger: ResMut<Ledger>,
+    mut not_enough_event: EventWriter<NotEnoughMoneyPopupEvent>,
+    mut message_event: EventWriter<ShopMessageEvent>,
 ) {
-    for (interaction, background_color, button) in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                let Ok(mut eq_belt) = player_query.get_single_mut() else {
-                    continue;
-                };
-
-                info!("Wearing Armor: {:?}", button.0.id);
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
 
-                // *background_color = PRESSED_BUTTON_COLOR.into();
+        let Ok((mut eq_belt, mut storage, mut money)) = player_query.get_single_mut() else {
+            continue;
+        };
+        let Some((cost, materials)) = storage
+            .items
+            .get(&button.0.item_type)
+            .and_then(|list| list.iter().find(|item| item.id == button.0.id))
+            .map(|owned| storage.upgrade_cost(owned, assets.tier(&button.0)))
+        else {
+            continue;
+        };
 
-                match button.0.item_type {
-                    crate::model::ItemType::Weapon => {
-                        eq_belt.weapon = Weapon::from(&weapon_data, button.0.clone());
-                    }
-                    crate::model::ItemType::Armor => {
-                        eq_belt.armor = Armor::from(&armor_data, button.0.clone());
-                    }
-                    crate::model::ItemType::Helmet => {
-                        eq_belt.helmet = Helmet::from(&helmet_data, button.0.clone())
-                    }
-                    crate::model::ItemType::Shoes => {
-                        eq_belt.shoes = Shoes::from(&shoes_data, button.0.clone())
-                    }
-                    crate::model::ItemType::Others => (),
-                }
-                let (mut active, mut selected) =
-                    shop_query.get_single_mut().expect("No Shop been spawned");
+        if ledger
+            .spend(&mut money, format!("Upgrade {}", button.0.name), cost)
+            .is_err()
+        {
+            not_enough_event.send(NotEnoughMoneyPopupEvent);
+            continue;
+        }
+        storage.materials -= materials;
 
-                selected.set_if_neq(SelectedEquipment(None));
-                active.set_changed();
-                next_shop_state.set(OverlayShopState::Opened);
-            }
+        let Some(owned) = storage.owned_mut(&button.0) else {
+            continue;
+        };
 
-            //     match equipment_query
-            //         .iter()
-            //         .find(|(eq, _, _)| eq.get() == button.0)
-            //     {
-            //         Some((_, Some(_wp), None)) => {
-            //             eq_weapon.0 = button.0;
-            //         }
-            //         Some((_, _, Some(_ar))) => {
-            //             eq_armor.0 = button.0;
-            //         }
-            //         _ => todo!(),
-            //     }
-            //     *background_color = PRESSED_BUTTON_COLOR.into();
+        if owned.upgrade(random::<f32>()) {
+            info!("Upgraded {} to lv {}", owned.name, owned.level);
+            message_event.send(ShopMessageEvent(format!("Upgraded to Lv {}", owned.level)));
+        } else {
+            info!("Upgrade of {} failed", owned.name);
+            message_event.send(ShopMessageEvent("Upgrade Failed".to_string()));
+        }
 
-            //     selected.set_if_neq(SelectedEquipment(0));
-            //     active.set_changed();
-            //     next_shop_state.set(OverlayShopState::Opened);
-            //     info!("Equire Equipment{}", button.0);
-            // }
-            // Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
-            _ => (),
+        // the belt keeps its own copy of the item
+        let upgraded = owned.clone();
+        match upgraded.item_type {
+            ItemType::Weapon => eq_belt.weapon = Weapon::from(&assets.weapon, upgraded),
+            ItemType::Armor => eq_belt.armor = Armor::from(&assets.armor, upgraded),
+            ItemType::Helmet => eq_belt.helmet = Helmet::from(&assets.helmet, upgraded),
+            ItemType::Shoes => eq_belt.shoes = Shoes::from(&assets.shoes, upgraded),
+            ItemType::Others => (),
         }
+
+        let (mut active, mut selected) = shop_query.get_single_mut().expect("No Shop been spawned");
+        selected.set_if_neq(SelectedEquipment(None));
+        active.set_changed();
     }
 }
 
@@ -242,41 +222,37 @@ pub fn interact_with_unequip_button(
     mut next_shop_state: ResMut<NextState<OverlayShopState>>,
     mut player_query: Query<&mut EquipmentBelt, With<Player>>,
 ) {
-    for (interaction, background_color, button) in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                info!("Unequip button being pressed");
-                let Ok(mut eq_belt) = player_query.get_single_mut() else {
-                    continue;
-                };
+    for (interaction, _background_color, button) in button_query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            info!("Unequip button being pressed");
+            let Ok(mut eq_belt) = player_query.get_single_mut() else {
+                continue;
+            };
 
-                // *background_color = PRESSED_BUTTON_COLOR.into();
+            // *background_color = PRESSED_BUTTON_COLOR.into();
 
-                match button.0.item_type {
-                    crate::model::ItemType::Weapon => {
-                        eq_belt.weapon = None;
-                    }
-                    crate::model::ItemType::Armor => {
-                        eq_belt.armor = None;
-                    }
-                    crate::model::ItemType::Helmet => {
-                        eq_belt.helmet = None;
-                    }
-                    crate::model::ItemType::Shoes => {
-                        eq_belt.shoes = None;
-                    }
-                    crate::model::ItemType::Others => (),
+            match button.0.item_type {
+                crate::model::ItemType::Weapon => {
+                    eq_belt.weapon = None;
                 }
-                let (mut active, mut selected) =
-                    shop_query.get_single_mut().expect("No Shop been spawned");
-
-                selected.set_if_neq(SelectedEquipment(Some(button.0.clone())));
-                active.set_changed();
-                next_shop_state.set(OverlayShopState::Opened);
+                crate::model::ItemType::Armor => {
+                    eq_belt.armor = None;
+                }
+                crate::model::ItemType::Helmet => {
+                    eq_belt.helmet = None;
+                }
+                crate::model::ItemType::Shoes => {
+                    eq_belt.shoes = None;
+                }
+                crate::model::ItemType::Others => (),
             }
+            let (mut active, mut selected) =
+                shop_query.get_single_mut().expect("No Shop been spawned");
 
+            selected.set_if_neq(SelectedEquipment(Some(button.0.clone())));
+            active.set_changed();
+            next_shop_state.set(OverlayShopState::Opened);
             // Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
-            _ => (),
         }
     }
 }
@@ -287,35 +263,438 @@ pub fn interact_with_buy_button(
     mut pop_up_event: EventWriter<SpawnConfirmPopupEvent>,
 ) {
     for (interaction, button) in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                // *background_color = PRESSED_BUTTON_COLOR.into();
-                pop_up_event.send(SpawnConfirmPopupEvent(button.0.clone()));
-                next_shop_state.set(OverlayShopState::Popup);
-                info!("Confirm Window");
-            }
-
+        if *interaction == Interaction::Pressed {
+            // *background_color = PRESSED_BUTTON_COLOR.into();
+            pop_up_event.send(SpawnConfirmPopupEvent(button.0.clone(), button.1));
+            next_shop_state.set(OverlayShopState::Popup);
+            info!("Confirm Window");
             // Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
-            _ => (),
         }
     }
 }
 
-pub fn interact_with_cancel_buy_button(
-    mut button_query: Query<(&Interaction), (Changed<Interaction>, With<CancelButton>)>,
+pub fn interact_with_buy_consumable_button(
+    button_query: Query<(&Interaction, &BuyConsumableButton), Changed<Interaction>>,
+    mut player_query: Query<(&mut Money, &mut Storage), With<Player>>,
+    consumable_assets: Res<Assets<ConsumableData>>,
+    mut ledger: ResMut<Ledger>,
+    mut not_enough_event: EventWriter<NotEnoughMoneyPopupEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some((_, data)) = consumable_assets
+            .iter()
+            .find(|(_, data)| data.id == button.0)
+        else {
+            continue;
+        };
+
+        let Ok((mut money, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
+
+        if ledger
+            .spend(&mut money, data.name.clone(), data.price)
+            .is_ok()
+        {
+            storage.add_consumable(data.id, 1);
+            info!("Bought {}", data.name);
+        } else {
+            not_enough_event.send(NotEnoughMoneyPopupEvent);
+        }
+    }
+}
+
+pub fn interact_with_buy_gem_button(
+    button_query: Query<(&Interaction, &BuyGemButton), Changed<Interaction>>,
+    mut shop_query: Query<&mut ActiveShopTab, With<Shop>>,
+    mut player_query: Query<(&mut Money, &mut Storage), With<Player>>,
+    gem_handle: Res<GemPreloadHandler>,
+    gem_books: Res<Assets<GemBook>>,
+    mut ledger: ResMut<Ledger>,
+    mut not_enough_event: EventWriter<NotEnoughMoneyPopupEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(gem) = gem_books
+            .get(gem_handle.id())
+            .and_then(|book| book.get(button.0))
+        else {
+            continue;
+        };
+
+        let Ok((mut money, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
+
+        if ledger
+            .spend(&mut money, gem.name.clone(), gem.price)
+            .is_err()
+        {
+            not_enough_event.send(NotEnoughMoneyPopupEvent);
+            continue;
+        }
+
+        storage.add_gem(gem.id);
+        info!("Bought {}", gem.name);
+
+        if let Ok(mut active) = shop_query.get_single_mut() {
+            active.set_changed();
+        }
+    }
+}
+
+pub fn interact_with_socket_gem_button(
+    button_query: Query<(&Interaction, &SocketGemButton), Changed<Interaction>>,
+    mut shop_query: Query<&mut ActiveShopTab, With<Shop>>,
+    mut player_query: Query<(&mut EquipmentBelt, &mut Storage), With<Player>>,
+    weapon_data: Res<Assets<WeaponData>>,
+    mut message_event: EventWriter<ShopMessageEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Ok((mut eq_belt, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
+        let Some(weapon) = &eq_belt.weapon else {
+            continue;
+        };
+        let (item, slot) = (weapon.feature.clone(), weapon.slot());
+
+        if item.sockets.len() as u32 >= slot {
+            message_event.send(ShopMessageEvent("No Free Socket".to_string()));
+            continue;
+        }
+        if !storage.take_gem(button.0) {
+            continue;
+        }
+        let Some(owned) = storage.owned_mut(&item) else {
+            // not in storage anymore, give the gem back
+            storage.add_gem(button.0);
+            continue;
+        };
+
+        owned.sockets.push(button.0);
+        info!("Socketed gem {} into {}", button.0, owned.name);
+
+        // the belt keeps its own copy of the item
+        eq_belt.weapon = Weapon::from(&weapon_data, owned.clone());
+
+        if let Ok(mut active) = shop_query.get_single_mut() {
+            active.set_changed();
+        }
+    }
+}
+
+pub fn interact_with_unsocket_gem_button(
+    button_query: Query<(&Interaction, &UnsocketGemButton), Changed<Interaction>>,
+    mut shop_query: Query<&mut ActiveShopTab, With<Shop>>,
+    mut player_query: Query<(&mut EquipmentBelt, &mut Storage), With<Player>>,
+    weapon_data: Res<Assets<WeaponData>>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Ok((mut eq_belt, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
+        let Some(item) = eq_belt.weapon.as_ref().map(|weapon| weapon.feature.clone()) else {
+            continue;
+        };
+        let Some(owned) = storage.owned_mut(&item) else {
+            continue;
+        };
+        if button.0 >= owned.sockets.len() {
+            continue;
+        }
+
+        let gem_id = owned.sockets.remove(button.0);
+        let updated = owned.clone();
+        storage.add_gem(gem_id);
+        info!("Removed gem {} from {}", gem_id, updated.name);
+
+        eq_belt.weapon = Weapon::from(&weapon_data, updated);
+
+        if let Ok(mut active) = shop_query.get_single_mut() {
+            active.set_changed();
+        }
+    }
+}
+
+// Pays for the item in the confirm popup, then offers to equip it
+pub fn interact_with_confirm_buy_button(
+    button_query: Query<(&Interaction, &ConfirmButton), Changed<Interaction>>,
+    mut shop_query: Query<&mut ActiveShopTab, With<Shop>>,
+    mut player_query: Query<(&mut Money, &mut Storage), With<Player>>,
+    mut ledger: ResMut<Ledger>,
+    mut stock: ResMut<ShopStock>,
     mut next_shop_state: ResMut<NextState<OverlayShopState>>,
+    mut not_enough_event: EventWriter<NotEnoughMoneyPopupEvent>,
+    mut message_event: EventWriter<ShopMessageEvent>,
+    mut equip_pop_up_event: EventWriter<SpawnEquipPopupEvent>,
 ) {
-    for (interaction) in button_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                // *background_color = PRESSED_BUTTON_COLOR.into();
-                next_shop_state.set(OverlayShopState::Opened);
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((mut money, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
 
-                info!("Close Window");
+        let ConfirmButton(item, price) = button;
+        if stock.offer(&item.item_type, item.id).is_none() {
+            message_event.send(ShopMessageEvent("Sold out".to_string()));
+            next_shop_state.set(OverlayShopState::Opened);
+            continue;
+        }
+        if ledger.spend(&mut money, item.name.clone(), *price).is_err() {
+            not_enough_event.send(NotEnoughMoneyPopupEvent);
+            next_shop_state.set(OverlayShopState::Opened);
+            continue;
+        }
+
+        storage.add_item(item);
+        stock.mark_sold(&item.item_type, item.id);
+        info!("Bought {} for {}", item.name, price);
+        equip_pop_up_event.send(SpawnEquipPopupEvent(item.clone()));
+
+        if let Ok(mut active) = shop_query.get_single_mut() {
+            active.set_changed();
+        }
+    }
+}
+
+pub fn interact_with_sell_button(
+    button_query: Query<(&Interaction, &SellButton), Changed<Interaction>>,
+    mut shop_query: Query<(&mut ActiveShopTab, &mut SelectedEquipment), With<Shop>>,
+    mut player_query: Query<(&EquipmentBelt, &mut Storage, &mut Money), With<Player>>,
+    assets: EquipmentAssets,
+    mut ledger: ResMut<Ledger>,
+    mut message_event: EventWriter<ShopMessageEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((belt, mut storage, mut money)) = player_query.get_single_mut() else {
+            continue;
+        };
+        if belt
+            .equipped()
+            .contains(&(button.0.item_type.clone(), button.0.id))
+        {
+            message_event.send(ShopMessageEvent("Unequip it first".to_string()));
+            continue;
+        }
+        let Some(sold) = storage.remove_item(&button.0) else {
+            continue;
+        };
+
+        let value = sold.sell_value(assets.tier(&sold));
+        ledger.earn(&mut money, format!("Sold {}", sold.name), value);
+        info!("Sold {} for {}", sold.name, value);
+        message_event.send(ShopMessageEvent(format!("Sold for ${}", value)));
+
+        let (mut active, mut selected) = shop_query.get_single_mut().expect("No Shop been spawned");
+        selected.set_if_neq(SelectedEquipment(None));
+        active.set_changed();
+    }
+}
+
+pub fn interact_with_salvage_button(
+    button_query: Query<(&Interaction, &SalvageButton), Changed<Interaction>>,
+    mut shop_query: Query<(&mut ActiveShopTab, &mut SelectedEquipment), With<Shop>>,
+    mut player_query: Query<(&EquipmentBelt, &mut Storage), With<Player>>,
+    assets: EquipmentAssets,
+    mut message_event: EventWriter<ShopMessageEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((belt, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
+        if belt
+            .equipped()
+            .contains(&(button.0.item_type.clone(), button.0.id))
+        {
+            message_event.send(ShopMessageEvent("Unequip it first".to_string()));
+            continue;
+        }
+        let Some(salvaged) = storage.remove_item(&button.0) else {
+            continue;
+        };
+
+        let materials = salvaged.salvage_materials(assets.tier(&salvaged));
+        storage.materials += materials;
+        info!("Salvaged {} into {} materials", salvaged.name, materials);
+        message_event.send(ShopMessageEvent(format!("Got {} materials", materials)));
+
+        let (mut active, mut selected) = shop_query.get_single_mut().expect("No Shop been spawned");
+        selected.set_if_neq(SelectedEquipment(None));
+        active.set_changed();
+    }
+}
+
+pub fn interact_with_sell_level_button(
+    button_query: Query<(&Interaction, &SellLevelButton), Changed<Interaction>>,
+    mut shop_query: Query<(&mut ActiveShopTab, &mut SellBelowLevel), With<Shop>>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((mut active, mut sell_below)) = shop_query.get_single_mut() else {
+            continue;
+        };
+
+        sell_below.0 = sell_below.0.saturating_add_signed(button.0).max(1);
+        active.set_changed();
+    }
+}
+
+pub fn interact_with_bulk_sell_button(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<BulkSellButton>)>,
+    shop_query: Query<(&ActiveShopTab, &SellBelowLevel), With<Shop>>,
+    player_query: Query<(&EquipmentBelt, &Storage), With<Player>>,
+    assets: EquipmentAssets,
+    mut next_shop_state: ResMut<NextState<OverlayShopState>>,
+    mut message_event: EventWriter<ShopMessageEvent>,
+    mut bulk_sell_event: EventWriter<SpawnBulkSellPopupEvent>,
+) {
+    for interaction in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let (Ok((active, sell_below)), Ok((belt, storage))) =
+            (shop_query.get_single(), player_query.get_single())
+        else {
+            continue;
+        };
+        let Some(item_type) = active.0.item_type() else {
+            continue;
+        };
+
+        let items = storage.sellable_below(&item_type, sell_below.0, belt);
+        if items.is_empty() {
+            message_event.send(ShopMessageEvent("Nothing to sell".to_string()));
+            continue;
+        }
+
+        let total = items
+            .iter()
+            .map(|item| item.sell_value(assets.tier(item)))
+            .sum();
+        bulk_sell_event.send(SpawnBulkSellPopupEvent {
+            item_type,
+            below: sell_below.0,
+            count: items.len(),
+            total,
+        });
+        next_shop_state.set(OverlayShopState::Popup);
+    }
+}
+
+// the list is worked out again so nothing equipped in the meantime gets sold
+pub fn interact_with_confirm_bulk_sell_button(
+    button_query: Query<(&Interaction, &ConfirmBulkSellButton), Changed<Interaction>>,
+    mut shop_query: Query<(&mut ActiveShopTab, &mut SelectedEquipment), With<Shop>>,
+    mut player_query: Query<(&EquipmentBelt, &mut Storage, &mut Money), With<Player>>,
+    assets: EquipmentAssets,
+    mut ledger: ResMut<Ledger>,
+    mut next_shop_state: ResMut<NextState<OverlayShopState>>,
+    mut message_event: EventWriter<ShopMessageEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((belt, mut storage, mut money)) = player_query.get_single_mut() else {
+            continue;
+        };
+
+        let mut total = 0;
+        for item in storage.sellable_below(&button.item_type, button.below, belt) {
+            if let Some(sold) = storage.remove_item(&item) {
+                let value = sold.sell_value(assets.tier(&sold));
+                ledger.earn(&mut money, format!("Sold {}", sold.name), value);
+                total += value;
             }
+        }
+        info!("Bulk sold for {}", total);
+        message_event.send(ShopMessageEvent(format!("Sold for ${}", total)));
+        next_shop_state.set(OverlayShopState::Opened);
+
+        if let Ok((mut active, mut selected)) = shop_query.get_single_mut() {
+            selected.set_if_neq(SelectedEquipment(None));
+            active.set_changed();
+        }
+    }
+}
+
+pub fn interact_with_craft_button(
+    button_query: Query<(&Interaction, &CraftButton), Changed<Interaction>>,
+    mut shop_query: Query<&mut ActiveShopTab, With<Shop>>,
+    mut player_query: Query<(&EquipmentBelt, &mut Storage), With<Player>>,
+    assets: EquipmentAssets,
+    mut message_event: EventWriter<ShopMessageEvent>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok((belt, mut storage)) = player_query.get_single_mut() else {
+            continue;
+        };
+        let Some(recipe) = assets.recipes().and_then(|book| book.get(button.0)) else {
+            continue;
+        };
+
+        let catalog = assets.catalog();
+        let Some(outcome) = recipe
+            .roll(&catalog, &mut rand::thread_rng())
+            .and_then(|id| assets.drop_equipment(&recipe.item_type, id))
+        else {
+            continue;
+        };
+        if storage.craft(recipe, &catalog, belt).is_err() {
+            message_event.send(ShopMessageEvent("Not enough to craft".to_string()));
+            continue;
+        }
+
+        storage.add_item(&outcome);
+        info!("Crafted {}", outcome.name);
+        message_event.send(ShopMessageEvent(format!("Crafted {}", outcome.name)));
+
+        if let Ok(mut active) = shop_query.get_single_mut() {
+            active.set_changed();
+        }
+    }
+}
+
+pub fn interact_with_cancel_buy_button(
+    mut button_query: Query<&Interaction, (Changed<Interaction>, With<CancelButton>)>,
+    mut next_shop_state: ResMut<NextState<OverlayShopState>>,
+) {
+    for interaction in button_query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            // *background_color = PRESSED_BUTTON_COLOR.into();
+            next_shop_state.set(OverlayShopState::Opened);
 
+            info!("Close Window");
             // Interaction::None => *background_color = NORMAL_BUTTON_COLOR.into(),
-            _ => (),
         }
     }
 }
@@ -14,7 +14,7 @@ pub fn spawn_shop(
     equipment_handles: Res<EquipmentHandles>,
     ui_image_asset: Res<UiImageHandles>,
     asset_server: Res<AssetServer>,
-    player_query: Query<&Money, With<Player>>,
+    _player_query: Query<&Money, With<Player>>,
 ) {
     // let money = player_query.get_single().expect("No Player is spawned");
     let layout = commands
@@ -35,6 +35,7 @@ pub fn spawn_shop(
             Shop {},
             SelectedEquipment::default(),
             ActiveShopTab::default(),
+            SellBelowLevel::default(),
         ))
         .id();
 
@@ -127,7 +128,7 @@ pub fn spawn_shop(
                 border_color: Color::NONE.into(),
                 image: UiImage {
                     texture: equipment_handles[&ItemType::Armor]
-                        .get(0)
+                        .first()
                         .expect("armor01 png failed to load")
                         .clone(),
                     ..default()
@@ -177,8 +178,8 @@ pub fn spawn_shop(
                 border_color: Color::NONE.into(),
                 image: UiImage {
                     texture: equipment_handles[&ItemType::Shoes]
-                        .get(0)
-                        .unwrap_or(equipment_handles[&ItemType::Weapon].get(0).unwrap())
+                        .first()
+                        .unwrap_or(equipment_handles[&ItemType::Weapon].first().unwrap())
                         .clone(),
                     ..default()
                 },
@@ -195,6 +196,84 @@ pub fn spawn_shop(
         ))
         .id();
 
+    let items_tab = commands
+        .spawn((
+            Name::new("Items Tab"),
+            ButtonBundle {
+                border_color: Color::NONE.into(),
+                image: UiImage {
+                    texture: equipment_handles[&ItemType::Others]
+                        .first()
+                        .unwrap_or(equipment_handles[&ItemType::Weapon].first().unwrap())
+                        .clone(),
+                    ..default()
+                },
+                style: Style {
+                    border: UiRect::all(Val::Px(5.)),
+                    width: Val::Px(64.),
+                    height: Val::Px(64.),
+                    ..default()
+                },
+                ..default()
+            },
+            ButtonClickEffect,
+            ShopTab::Items,
+        ))
+        .id();
+
+    let gems_tab = commands
+        .spawn((
+            Name::new("Gems Tab"),
+            ButtonBundle {
+                border_color: Color::NONE.into(),
+                image: UiImage {
+                    texture: equipment_handles[&ItemType::Others]
+                        .first()
+                        .unwrap_or(equipment_handles[&ItemType::Weapon].first().unwrap())
+                        .clone(),
+                    // same icon as items, tinted so the tabs tell apart
+                    color: Color::srgb(0.5, 0.8, 1.),
+                    ..default()
+                },
+                style: Style {
+                    border: UiRect::all(Val::Px(5.)),
+                    width: Val::Px(64.),
+                    height: Val::Px(64.),
+                    ..default()
+                },
+                ..default()
+            },
+            ButtonClickEffect,
+            ShopTab::Gems,
+        ))
+        .id();
+
+    let craft_tab = commands
+        .spawn((
+            Name::new("Craft Tab"),
+            ButtonBundle {
+                border_color: Color::NONE.into(),
+                image: UiImage {
+                    texture: equipment_handles[&ItemType::Others]
+                        .first()
+                        .unwrap_or(equipment_handles[&ItemType::Weapon].first().unwrap())
+                        .clone(),
+                    color: Color::srgb(1., 0.7, 0.4),
+                    ..default()
+                },
+                style: Style {
+                    border: UiRect::all(Val::Px(5.)),
+                    width: Val::Px(64.),
+                    height: Val::Px(64.),
+                    ..default()
+                },
+                ..default()
+            },
+            ButtonClickEffect,
+            ShopTab::Craft,
+        ))
+        .id();
+
     let content_row = commands
         .spawn((
             Name::new("Shop Row"),
@@ -784,9 +863,9 @@ pub fn spawn_shop(
 
     commands.entity(shop_tabs_row).push_children(&[shop_tabs]);
 
-    commands
-        .entity(shop_tabs)
-        .push_children(&[weapon_tab, armor_tab, helmet_tab, shoes_tab]);
+    commands.entity(shop_tabs).push_children(&[
+        weapon_tab, armor_tab, helmet_tab, shoes_tab, items_tab, gems_tab, craft_tab,
+    ]);
 
     commands
         .entity(content_row)
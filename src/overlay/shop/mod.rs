@@ -9,12 +9,25 @@ use layout::*;
 use popup::*;
 use update::*;
 
-use crate::{model::sub::DropEquipment, state::OverlayShopState};
+use crate::{
+    model::{sub::DropEquipment, ItemType},
+    state::OverlayShopState,
+};
 
 use super::component::ActiveShopTab;
 
+// the item and the price it is sold for
 #[derive(Event)]
-pub struct SpawnConfirmPopupEvent(DropEquipment);
+pub struct SpawnConfirmPopupEvent(DropEquipment, u64);
+
+// Every unequipped item of a type below the level, with what selling them all pays
+#[derive(Event)]
+pub struct SpawnBulkSellPopupEvent {
+    item_type: ItemType,
+    below: u32,
+    count: usize,
+    total: u64,
+}
 
 #[derive(Event)]
 pub struct SpawnEquipPopupEvent(DropEquipment);
@@ -22,13 +35,19 @@ pub struct SpawnEquipPopupEvent(DropEquipment);
 #[derive(Event)]
 pub struct NotEnoughMoneyPopupEvent;
 
+// Short floating message, same look as the not enough money warning
+#[derive(Event)]
+pub struct ShopMessageEvent(pub String);
+
 pub struct ShopUIPlugin;
 
 impl Plugin for ShopUIPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnConfirmPopupEvent>()
             .add_event::<NotEnoughMoneyPopupEvent>()
+            .add_event::<ShopMessageEvent>()
             .add_event::<SpawnEquipPopupEvent>()
+            .add_event::<SpawnBulkSellPopupEvent>()
             .add_systems(OnExit(OverlayShopState::Closed), spawn_shop)
             .add_systems(OnEnter(OverlayShopState::Closed), despawn_shop)
             .add_systems(
@@ -52,12 +71,32 @@ impl Plugin for ShopUIPlugin {
                     interact_with_buy_button,
                     interact_with_unequip_button,
                     interact_with_upgrade_button,
+                    interact_with_buy_consumable_button,
+                )
+                    .run_if(in_state(OverlayShopState::Opened)),
+            )
+            .add_systems(
+                Update,
+                (
+                    interact_with_buy_gem_button,
+                    interact_with_socket_gem_button,
+                    interact_with_unsocket_gem_button,
+                    interact_with_sell_button,
+                    interact_with_salvage_button,
+                    interact_with_sell_level_button,
+                    interact_with_bulk_sell_button,
+                    interact_with_craft_button,
                 )
                     .run_if(in_state(OverlayShopState::Opened)),
             )
             .add_systems(
                 Update,
-                (spawn_buy_pop_up, update_warning_text, spawn_warning_text),
+                (
+                    spawn_buy_pop_up,
+                    spawn_bulk_sell_pop_up,
+                    update_warning_text,
+                    spawn_warning_text,
+                ),
             )
             .add_systems(
                 Update,
@@ -65,7 +104,12 @@ impl Plugin for ShopUIPlugin {
             )
             .add_systems(
                 Update,
-                (interact_with_cancel_buy_button, spawn_equip_pop_up)
+                (
+                    interact_with_cancel_buy_button,
+                    interact_with_confirm_buy_button,
+                    interact_with_confirm_bulk_sell_button,
+                    spawn_equip_pop_up,
+                )
                     .run_if(in_state(OverlayShopState::Popup)),
             )
             .add_systems(OnExit(OverlayShopState::Popup), despawn_pop_up)
@@ -2,10 +2,16 @@ use bevy::prelude::*;
 
 use crate::{
     audio_effects::ButtonClickEffect,
-    overlay::component::{CancelButton, ConfirmPopup, WarningText, WarningTextLayout},
+    overlay::component::{
+        CancelButton, ConfirmBulkSellButton, ConfirmButton, ConfirmPopup, EquireButton,
+        WarningText, WarningTextLayout,
+    },
 };
 
-use super::{NotEnoughMoneyPopupEvent, SpawnConfirmPopupEvent, SpawnEquipPopupEvent};
+use super::{
+    NotEnoughMoneyPopupEvent, ShopMessageEvent, SpawnBulkSellPopupEvent, SpawnConfirmPopupEvent,
+    SpawnEquipPopupEvent,
+};
 
 pub fn spawn_buy_pop_up(
     mut commands: Commands,
@@ -13,183 +19,219 @@ pub fn spawn_buy_pop_up(
     mut spawn_event: EventReader<SpawnConfirmPopupEvent>,
 ) {
     for ev in spawn_event.read() {
-        let popup_layout = commands
-            .spawn((
-                Name::new("Confirm Popup"),
-                NodeBundle {
-                    background_color: Color::srgba(0.0, 0.0, 0.0, 0.3).into(),
+        spawn_confirm_window(
+            &mut commands,
+            &asset_server,
+            format!("Buy {} for ${}?", ev.0.name, ev.1),
+            ConfirmButton(ev.0.clone(), ev.1),
+        );
+    }
+}
 
-                    style: Style {
-                        width: Val::Percent(100.),
-                        height: Val::Percent(100.),
-                        justify_items: JustifyItems::Center,
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
-                        ..default()
-                    },
+pub fn spawn_bulk_sell_pop_up(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut spawn_event: EventReader<SpawnBulkSellPopupEvent>,
+) {
+    for ev in spawn_event.read() {
+        spawn_confirm_window(
+            &mut commands,
+            &asset_server,
+            format!(
+                "Sell {} items below Lv {} for ${}?",
+                ev.count, ev.below, ev.total
+            ),
+            ConfirmBulkSellButton {
+                item_type: ev.item_type.clone(),
+                below: ev.below,
+            },
+        );
+    }
+}
 
-                    z_index: ZIndex::Global(99),
+// Cancel closes the popup, whatever reads the confirm marker does the rest
+fn spawn_confirm_window(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    message: String,
+    confirm_marker: impl Bundle,
+) {
+    let popup_layout = commands
+        .spawn((
+            Name::new("Confirm Popup"),
+            NodeBundle {
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.3).into(),
+
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    justify_items: JustifyItems::Center,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
                     ..default()
                 },
-                ConfirmPopup,
-            ))
-            .id();
 
-        let window = commands
-            .spawn((
-                Name::new("Confirm window"),
-                NodeBundle {
-                    background_color: Color::srgb(0.3, 0.3, 0.3).into(),
+                z_index: ZIndex::Global(99),
+                ..default()
+            },
+            ConfirmPopup,
+        ))
+        .id();
 
-                    style: Style {
-                        flex_direction: FlexDirection::Column,
-                        width: Val::Px(400.),
-                        height: Val::Px(300.),
-                        margin: UiRect::horizontal(Val::Px(20.)),
-                        justify_content: JustifyContent::SpaceBetween,
-                        ..default()
-                    },
+    let window = commands
+        .spawn((
+            Name::new("Confirm window"),
+            NodeBundle {
+                background_color: Color::srgb(0.3, 0.3, 0.3).into(),
+
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    width: Val::Px(400.),
+                    height: Val::Px(300.),
+                    margin: UiRect::horizontal(Val::Px(20.)),
+                    justify_content: JustifyContent::SpaceBetween,
                     ..default()
                 },
-            ))
-            .id();
+                ..default()
+            },
+        ))
+        .id();
 
-        let warning_text = commands
-            .spawn((
-                Name::new("Confirm text"),
-                TextBundle {
-                    text: Text {
-                        sections: vec![TextSection {
-                            value: format!("Are you sure you want to buy?"),
+    let warning_text = commands
+        .spawn((
+            Name::new("Confirm text"),
+            TextBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: message,
+                        style: TextStyle {
+                            font_size: 40.,
+                            font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                            color: Color::WHITE,
+                        },
+                    }],
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    let button_row = commands
+        .spawn((
+            Name::new("Confirm Row"),
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    height: Val::Px(100.),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    let cancel = commands
+        .spawn((
+            Name::new("Cancel"),
+            ButtonBundle {
+                // background_color: NORMAL_BUTTON_COLOR.into(),
+                border_color: Color::BLACK.into(),
+                style: Style {
+                    width: Val::Percent(50.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(2.)),
+                    ..default()
+                },
+                ..default()
+            },
+            CancelButton,
+            ButtonClickEffect,
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect {
+                        left: Val::Px(10.),
+                        right: Val::Px(10.),
+                        top: Val::ZERO,
+                        bottom: Val::ZERO,
+                    },
+                    ..default()
+                },
+                text: Text {
+                    sections: {
+                        vec![TextSection {
+                            value: String::from("Cancel"),
                             style: TextStyle {
-                                font_size: 40.,
                                 font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                                font_size: 20.0,
                                 color: Color::WHITE,
                             },
-                        }],
-                        ..default()
+                        }]
                     },
                     ..default()
                 },
-            ))
-            .id();
+                ..Default::default()
+            });
+        })
+        .id();
 
-        let button_row = commands
-            .spawn((
-                Name::new("Confirm Row"),
-                NodeBundle {
-                    style: Style {
-                        flex_direction: FlexDirection::Row,
-                        height: Val::Px(100.),
-                        ..default()
-                    },
+    let confirm = commands
+        .spawn((
+            Name::new("Confirm"),
+            ButtonBundle {
+                // background_color: NORMAL_BUTTON_COLOR.into(),
+                border_color: Color::BLACK.into(),
+                style: Style {
+                    width: Val::Percent(50.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(2.)),
                     ..default()
                 },
-            ))
-            .id();
-
-        let cancel = commands
-            .spawn((
-                Name::new("Cancel"),
-                ButtonBundle {
-                    // background_color: NORMAL_BUTTON_COLOR.into(),
-                    border_color: Color::BLACK.into(),
-                    style: Style {
-                        width: Val::Percent(50.),
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
-                        border: UiRect::all(Val::Px(2.)),
-                        ..default()
+                ..default()
+            },
+            ButtonClickEffect,
+            confirm_marker,
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect {
+                        left: Val::Px(10.),
+                        right: Val::Px(10.),
+                        top: Val::ZERO,
+                        bottom: Val::ZERO,
                     },
                     ..default()
                 },
-                CancelButton,
-                ButtonClickEffect,
-            ))
-            .with_children(|builder| {
-                builder.spawn(TextBundle {
-                    style: Style {
-                        margin: UiRect {
-                            left: Val::Px(10.),
-                            right: Val::Px(10.),
-                            top: Val::ZERO,
-                            bottom: Val::ZERO,
-                        },
-                        ..default()
-                    },
-                    text: Text {
-                        sections: {
-                            vec![TextSection {
-                                value: String::from("Cancel"),
-                                style: TextStyle {
-                                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
-                                    font_size: 20.0,
-                                    color: Color::WHITE,
-                                },
-                            }]
-                        },
-                        ..default()
-                    },
-                    ..Default::default()
-                });
-            })
-            .id();
-
-        let confirm = commands
-            .spawn((
-                Name::new("Confirm"),
-                ButtonBundle {
-                    // background_color: NORMAL_BUTTON_COLOR.into(),
-                    border_color: Color::BLACK.into(),
-                    style: Style {
-                        width: Val::Percent(50.),
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
-                        border: UiRect::all(Val::Px(2.)),
-                        ..default()
+                text: Text {
+                    sections: {
+                        vec![TextSection {
+                            value: String::from("Confirm"),
+                            style: TextStyle {
+                                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        }]
                     },
                     ..default()
                 },
-                ButtonClickEffect,
-                // ConfirmButton(ev.0),
-            ))
-            .with_children(|builder| {
-                builder.spawn(TextBundle {
-                    style: Style {
-                        margin: UiRect {
-                            left: Val::Px(10.),
-                            right: Val::Px(10.),
-                            top: Val::ZERO,
-                            bottom: Val::ZERO,
-                        },
-                        ..default()
-                    },
-                    text: Text {
-                        sections: {
-                            vec![TextSection {
-                                value: String::from("Confirm"),
-                                style: TextStyle {
-                                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
-                                    font_size: 20.0,
-                                    color: Color::WHITE,
-                                },
-                            }]
-                        },
-                        ..default()
-                    },
-                    ..Default::default()
-                });
-            })
-            .id();
+                ..Default::default()
+            });
+        })
+        .id();
 
-        commands.entity(popup_layout).add_child(window);
-        commands
-            .entity(window)
-            .push_children(&[warning_text, button_row]);
-        commands
-            .entity(button_row)
-            .push_children(&[confirm, cancel]);
-    }
+    commands.entity(popup_layout).add_child(window);
+    commands
+        .entity(window)
+        .push_children(&[warning_text, button_row]);
+    commands
+        .entity(button_row)
+        .push_children(&[confirm, cancel]);
 }
 
 pub fn despawn_pop_up(mut commands: Commands, pop_up_query: Query<Entity, With<ConfirmPopup>>) {
@@ -234,7 +276,7 @@ pub fn spawn_equip_pop_up(
                     TextBundle {
                         text: Text {
                             sections: vec![TextSection {
-                                value: format!("Do you want to equip it?"),
+                                value: "Do you want to equip it?".to_string(),
                                 style: TextStyle {
                                     font_size: 40.,
                                     font: asset_server.load("fonts/FiraMono-Medium.ttf"),
@@ -325,7 +367,7 @@ pub fn spawn_equip_pop_up(
                         ..default()
                     },
                     ButtonClickEffect,
-                    // EquireButton(ev.0),
+                    EquireButton(ev.0.clone()),
                 ))
                 .with_children(|builder| {
                     builder.spawn(TextBundle {
@@ -370,6 +412,7 @@ pub fn spawn_equip_pop_up(
 pub fn spawn_warning_text(
     mut commands: Commands,
     mut not_enough_event: EventReader<NotEnoughMoneyPopupEvent>,
+    mut message_event: EventReader<ShopMessageEvent>,
     asset_server: Res<AssetServer>,
 ) {
     // let center = camera_query
@@ -377,7 +420,13 @@ pub fn spawn_warning_text(
     //     .expect("No Camera been found")
     //     .translation;
 
-    for _ in not_enough_event.read() {
+    let messages = not_enough_event
+        .read()
+        .map(|_| String::from("Not Enough Money"))
+        .chain(message_event.read().map(|ev| ev.0.clone()))
+        .collect::<Vec<_>>();
+
+    for message in messages {
         let layout = commands
             .spawn((
                 Name::new("Confirm Popup"),
@@ -409,7 +458,7 @@ pub fn spawn_warning_text(
                     text: Text {
                         sections: {
                             vec![TextSection {
-                                value: String::from("Not Enough Money"),
+                                value: message,
                                 style: TextStyle {
                                     font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                                     font_size: 40.0,
@@ -2,8 +2,10 @@ use bevy::prelude::*;
 
 use crate::{
     model::{
-        armor::ArmorData, helmet::HelmetData, monster::MonsterData, player::PlayerData,
-        shoes::ShoesData, weapon::WeaponData,
+        achievement::AchievementBook, affix::AffixBook, armor::ArmorData, boss::BossBook,
+        consumable::ConsumableData, gem::GemBook, helmet::HelmetData, monster::MonsterData,
+        player::PlayerData, progression::Progression, recipe::RecipeBook, set::SetBook,
+        shoes::ShoesData, weapon::WeaponData, world::WorldManifest,
     },
     state::Screen,
     util::{
@@ -19,8 +21,19 @@ pub(super) fn plugin(app: &mut App) {
         CsvAssetPlugin::<HelmetData>::new(&["helmet.csv"]),
         CsvAssetPlugin::<ShoesData>::new(&["shoes.csv"]),
         CsvAssetPlugin::<MonsterData>::new(&["monster.csv"]),
+        CsvAssetPlugin::<ConsumableData>::new(&["consumable.csv"]),
         JsonAssetPlugin::<PlayerData>::new(&["json"]),
+        JsonAssetPlugin::<BossBook>::new(&["boss.json"]),
+        JsonAssetPlugin::<Progression>::new(&["progression.json"]),
+        JsonAssetPlugin::<AchievementBook>::new(&["achievement.json"]),
+        JsonAssetPlugin::<SetBook>::new(&["set.json"]),
+        JsonAssetPlugin::<GemBook>::new(&["gem.json"]),
+        JsonAssetPlugin::<AffixBook>::new(&["affix.json"]),
+        JsonAssetPlugin::<RecipeBook>::new(&["recipe.json"]),
+        JsonAssetPlugin::<WorldManifest>::new(&["world.json"]),
     ))
+    .init_resource::<Progression>()
+    .add_systems(Update, sync_progression)
     .add_systems(
         OnEnter(Screen::Loading),
         (
@@ -30,6 +43,15 @@ pub(super) fn plugin(app: &mut App) {
             load_helmet,
             load_shoes,
             load_monster,
+            load_consumable,
+            load_boss,
+            load_progression,
+            load_achievement,
+            load_set,
+            load_gem,
+            load_affix,
+            load_recipe,
+            load_world,
         ),
     );
 }
@@ -81,3 +103,96 @@ fn load_monster(mut commands: Commands, asset_server: Res<AssetServer>) {
     let monster = MonsterPreloadHandler(asset_server.load("data/1.monster.csv"));
     commands.insert_resource(monster);
 }
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct ConsumablePreloadHandler(pub Handle<LoadedCsv<ConsumableData>>);
+
+fn load_consumable(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let consumable = ConsumablePreloadHandler(asset_server.load("data/1.consumable.csv"));
+    commands.insert_resource(consumable);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct BossPreloadHandler(pub Handle<BossBook>);
+
+fn load_boss(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let boss = BossPreloadHandler(asset_server.load("data/1.boss.json"));
+    commands.insert_resource(boss);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct ProgressionPreloadHandler(pub Handle<Progression>);
+
+fn load_progression(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let progression = ProgressionPreloadHandler(asset_server.load("data/progression.json"));
+    commands.insert_resource(progression);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct AchievementPreloadHandler(pub Handle<AchievementBook>);
+
+fn load_achievement(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let achievement = AchievementPreloadHandler(asset_server.load("data/1.achievement.json"));
+    commands.insert_resource(achievement);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct SetPreloadHandler(pub Handle<SetBook>);
+
+fn load_set(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let set = SetPreloadHandler(asset_server.load("data/1.set.json"));
+    commands.insert_resource(set);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct GemPreloadHandler(pub Handle<GemBook>);
+
+fn load_gem(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let gem = GemPreloadHandler(asset_server.load("data/1.gem.json"));
+    commands.insert_resource(gem);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct AffixPreloadHandler(pub Handle<AffixBook>);
+
+fn load_affix(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let affix = AffixPreloadHandler(asset_server.load("data/1.affix.json"));
+    commands.insert_resource(affix);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct RecipePreloadHandler(pub Handle<RecipeBook>);
+
+fn load_recipe(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let recipe = RecipePreloadHandler(asset_server.load("data/1.recipe.json"));
+    commands.insert_resource(recipe);
+}
+
+#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
+pub struct WorldPreloadHandler(pub Handle<WorldManifest>);
+
+fn load_world(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let world = WorldPreloadHandler(asset_server.load("data/world.json"));
+    commands.insert_resource(world);
+}
+
+// copies the curves into the resource on load and again whenever the file is hot reloaded
+fn sync_progression(
+    mut events: EventReader<AssetEvent<Progression>>,
+    handle: Option<Res<ProgressionPreloadHandler>>,
+    assets: Res<Assets<Progression>>,
+    mut progression: ResMut<Progression>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.is_loaded_with_dependencies(handle.id()) || event.is_modified(handle.id()) {
+            if let Some(loaded) = assets.get(handle.id()) {
+                info!("Progression loaded: {:?}", loaded);
+                *progression = loaded.clone();
+            }
+        }
+    }
+}
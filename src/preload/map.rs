@@ -1,7 +1,7 @@
 use bevy::{prelude::*, utils::hashbrown::HashMap};
 use bevy_ecs_ldtk::{assets::LdtkProject, LdtkPlugin};
 
-use super::MyAssetPath;
+use crate::{model::world::WorldManifest, preload::data::WorldPreloadHandler};
 
 // pub struct MapPreloadPlugin;
 
@@ -16,31 +16,44 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(LdtkPlugin);
     app.register_type::<MapHandles>();
     app.init_resource::<MapHandles>();
+    app.add_systems(Update, load_world_maps);
 }
 
-#[derive(PartialEq, Eq, Clone, Hash, Debug, Reflect)]
-pub enum MapAsset {
-    Forbidden,
-}
-
-impl MyAssetPath for MapAsset {
-    fn path_name(&self) -> String {
-        match self {
-            Self::Forbidden => "maps/forbidden.ldtk".to_string(),
+// LDtk projects keyed by their path in the world manifest
+#[derive(Resource, Debug, Default, Deref, DerefMut, Reflect)]
+#[reflect(Resource)]
+pub struct MapHandles(pub HashMap<String, Handle<LdtkProject>>);
+
+// the projects are only known once the manifest is read, a new region needs no code
+fn load_world_maps(
+    mut events: EventReader<AssetEvent<WorldManifest>>,
+    world_handle: Option<Res<WorldPreloadHandler>>,
+    worlds: Res<Assets<WorldManifest>>,
+    asset_server: Res<AssetServer>,
+    mut map_handles: ResMut<MapHandles>,
+) {
+    let Some(handle) = world_handle else {
+        return;
+    };
+
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(handle.id()) {
+            continue;
+        }
+        let Some(world) = worlds.get(handle.id()) else {
+            continue;
+        };
+        if let Err(err) = world.validate() {
+            error!("World manifest is invalid: {:?}", err);
         }
-    }
-}
 
-#[derive(Resource, Debug, Deref, DerefMut, Reflect)]
-#[reflect(Resource)]
-pub struct MapHandles(pub HashMap<MapAsset, Handle<LdtkProject>>);
-
-impl FromWorld for MapHandles {
-    fn from_world(world: &mut World) -> Self {
-        let asset_server = world.resource::<AssetServer>();
-        MapHandles(HashMap::from([(
-            MapAsset::Forbidden,
-            asset_server.load("map/forbidden.ldtk"),
-        )]))
+        for project in world.projects.iter() {
+            if !map_handles.contains_key(&project.path) {
+                map_handles.insert(
+                    project.path.clone(),
+                    asset_server.load(project.path.clone()),
+                );
+            }
+        }
     }
 }
@@ -164,7 +164,7 @@ impl FromWorld for HeroActionTextureAtLasHandles {
     fn from_world(world: &mut World) -> Self {
         let mut asset_server = world.resource_mut::<Assets<TextureAtlasLayout>>();
 
-        let pixel_art_settings = |settings: &mut ImageLoaderSettings| {
+        let _pixel_art_settings = |settings: &mut ImageLoaderSettings| {
             // Use `nearest` image sampling to preserve the pixel art style.
             settings.sampler = ImageSampler::nearest();
         };
@@ -232,7 +232,7 @@ impl FromWorld for MonsterTextureAtLasHandles {
     fn from_world(world: &mut World) -> Self {
         let mut asset_server = world.resource_mut::<Assets<TextureAtlasLayout>>();
 
-        let pixel_art_settings = |settings: &mut ImageLoaderSettings| {
+        let _pixel_art_settings = |settings: &mut ImageLoaderSettings| {
             // Use `nearest` image sampling to preserve the pixel art style.
             settings.sampler = ImageSampler::nearest();
         };
@@ -0,0 +1,57 @@
+//! The achievement gallery, every achievement with its progress.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::achievement::AchievementTracker, model::achievement::AchievementBook,
+    preload::data::AchievementPreloadHandler, state::Screen, theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Achievements), show_achievements_screen);
+}
+
+fn show_achievements_screen(
+    mut commands: Commands,
+    tracker: Res<AchievementTracker>,
+    handle: Res<AchievementPreloadHandler>,
+    books: Res<Assets<AchievementBook>>,
+) {
+    let achievements = books
+        .get(handle.id())
+        .map(|book| book.0.clone())
+        .unwrap_or_default();
+
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Achievements))
+        .with_children(|children| {
+            children.header(format!(
+                "Achievements {}/{}",
+                tracker.unlocked.len(),
+                achievements.len()
+            ));
+
+            for data in achievements.iter() {
+                let text = if tracker.unlocked.contains(&data.id) {
+                    format!("[Unlocked] {} - {}", data.name, data.description)
+                } else {
+                    let (current, target) = data.condition.progress(&tracker);
+                    format!(
+                        "{} - {} ({}/{})",
+                        data.name,
+                        data.description,
+                        current.min(target),
+                        target
+                    )
+                };
+                children.label(text);
+            }
+
+            children.button("Back").observe(enter_title);
+        });
+}
+
+fn enter_title(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
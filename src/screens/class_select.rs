@@ -0,0 +1,113 @@
+//! The class selection screen shown before a run starts.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        character::{
+            component::{BaseStates, HeroClass},
+            hero::SelectedClass,
+        },
+        saving::Profile,
+    },
+    model::progression::Progression,
+    state::{Screen, SimulationState},
+    theme::prelude::*,
+};
+
+#[derive(Component)]
+struct ClassChoice(HeroClass);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::ClassSelect), show_class_select_screen);
+}
+
+fn show_class_select_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profile: Option<Res<Profile>>,
+    progression: Res<Progression>,
+) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::ClassSelect))
+        .with_children(|children| {
+            children.header("Choose your class");
+
+            for class in HeroClass::iterator() {
+                // every class keeps its own hero, show where each one left off
+                let label = match profile
+                    .as_ref()
+                    .and_then(|profile| profile.hero(&class.to_string()))
+                {
+                    Some(save) => format!(
+                        "{} Lv.{}",
+                        class,
+                        BaseStates::from_save(*class, save, &progression).lv()
+                    ),
+                    None => class.to_string(),
+                };
+
+                children
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(10.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        if let Some(portrait) = class.portrait() {
+                            row.spawn(ImageBundle {
+                                style: Style {
+                                    width: Val::Px(80.),
+                                    height: Val::Px(80.),
+                                    ..default()
+                                },
+                                image: UiImage::new(asset_server.load(portrait)),
+                                ..default()
+                            });
+                        }
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(5.),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column
+                                .button(label)
+                                .insert(ClassChoice(*class))
+                                .observe(select_class);
+                            column.label(class.description());
+                        });
+                    });
+            }
+
+            children.button("Back").observe(enter_title);
+        });
+}
+
+fn select_class(
+    trigger: Trigger<OnPress>,
+    choice_query: Query<&ClassChoice>,
+    mut selected_class: ResMut<SelectedClass>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut next_simulation_state: ResMut<NextState<SimulationState>>,
+) {
+    let Ok(choice) = choice_query.get(trigger.entity()) else {
+        return;
+    };
+
+    *selected_class = SelectedClass(choice.0);
+    next_simulation_state.set(SimulationState::Pause);
+    next_screen.set(Screen::Playing);
+}
+
+fn enter_title(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -5,11 +5,15 @@ use bevy::{prelude::*, utils::HashMap};
 
 use crate::{
     assets::{BgmHandles, ImageHandles, SfxHandles},
+    game::saving::Profile,
     preload::{
         audio::{AudioEffectHandles, MusicHandles},
         data::{
-            ArmorPreloadHandler, HelmetPreloadHandler, MonsterPreloadHandler, PlayerPreloadHandler,
-            ShoesPreloadHandler, WeaponPreloadHandler,
+            AchievementPreloadHandler, AffixPreloadHandler, ArmorPreloadHandler,
+            BossPreloadHandler, ConsumablePreloadHandler, GemPreloadHandler, HelmetPreloadHandler,
+            MonsterPreloadHandler, PlayerPreloadHandler, ProgressionPreloadHandler,
+            RecipePreloadHandler, SetPreloadHandler, ShoesPreloadHandler, WeaponPreloadHandler,
+            WorldPreloadHandler,
         },
         fonts::FontHandles,
         images::{EquipmentHandles, UiImageHandles},
@@ -28,7 +32,8 @@ pub(super) fn plugin(app: &mut App) {
         continue_to_title.run_if(
             in_state(Screen::Loading)
                 .and_then(all_assets_loaded)
-                .and_then(all_data_loaded),
+                .and_then(all_data_loaded)
+                .and_then(resource_exists::<Profile>),
         ),
     );
 }
@@ -50,6 +55,15 @@ fn all_data_loaded(
     armor_handles: Res<ArmorPreloadHandler>,
     shoes_handles: Res<ShoesPreloadHandler>,
     monster_data_handles: Res<MonsterPreloadHandler>,
+    consumable_handles: Res<ConsumablePreloadHandler>,
+    boss_handles: Res<BossPreloadHandler>,
+    progression_handles: Res<ProgressionPreloadHandler>,
+    achievement_handles: Res<AchievementPreloadHandler>,
+    set_handles: Res<SetPreloadHandler>,
+    gem_handles: Res<GemPreloadHandler>,
+    affix_handles: Res<AffixPreloadHandler>,
+    recipe_handles: Res<RecipePreloadHandler>,
+    world_handles: Res<WorldPreloadHandler>,
 ) -> bool {
     player_data_handles.is_loaded(&asset_server)
         && weapon_handles.is_loaded(&asset_server)
@@ -57,6 +71,15 @@ fn all_data_loaded(
         && armor_handles.is_loaded(&asset_server)
         && shoes_handles.is_loaded(&asset_server)
         && monster_data_handles.is_loaded(&asset_server)
+        && consumable_handles.is_loaded(&asset_server)
+        && boss_handles.is_loaded(&asset_server)
+        && progression_handles.is_loaded(&asset_server)
+        && achievement_handles.is_loaded(&asset_server)
+        && set_handles.is_loaded(&asset_server)
+        && gem_handles.is_loaded(&asset_server)
+        && affix_handles.is_loaded(&asset_server)
+        && recipe_handles.is_loaded(&asset_server)
+        && world_handles.is_loaded(&asset_server)
 }
 
 fn all_assets_loaded(
@@ -76,6 +99,8 @@ fn all_assets_loaded(
     image_handles.is_loaded(&asset_server)
         && sfx_handles.is_loaded(&asset_server)
         && bgm_handles.is_loaded(&asset_server)
+        // filled in once the world manifest is read
+        && !map_handles.is_empty()
         && map_handles.is_loaded(&asset_server)
         && hero_action_handles.is_loaded(&asset_server)
         && monster_handles.is_loaded(&asset_server)
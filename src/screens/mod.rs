@@ -1,10 +1,13 @@
 //! The game's main screen states and transitions between them.
 
+mod achievements;
+mod class_select;
 mod credits;
 mod loading;
 mod playing;
 mod splash;
 mod title;
+mod upgrades;
 
 use bevy::prelude::*;
 
@@ -13,7 +16,10 @@ pub(super) fn plugin(app: &mut App) {
         splash::plugin,
         loading::plugin,
         title::plugin,
+        achievements::plugin,
+        class_select::plugin,
         credits::plugin,
         playing::plugin,
+        upgrades::plugin,
     ));
 }
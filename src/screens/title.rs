@@ -3,11 +3,8 @@
 use bevy::prelude::*;
 
 use crate::{
-    preload::{
-        images::{UiImageAsset, UiImageHandles},
-        sprites::{HeroActionHandles, HeroActionTextureAtLasHandles},
-    },
-    state::{FirstTime, OverlayShopState, Screen, SimulationState},
+    preload::images::{UiImageAsset, UiImageHandles},
+    state::{FirstTime, OverlayShopState, Screen},
     theme::prelude::*,
 };
 
@@ -24,7 +21,7 @@ fn show_title_screen(
     // sprite_set: Res<HeroActionHandles>,
     // texture_atlas_set: Res<HeroActionTextureAtLasHandles>,
 ) {
-    let row = commands
+    let _row = commands
         .spawn(NodeBundle {
             style: Style {
                 width: Val::Percent(100.),
@@ -110,8 +107,10 @@ fn show_title_screen(
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
             children.title("Leveling Up");
-            children.button("Play").observe(enter_playing);
+            children.button("Play").observe(enter_class_select);
+            children.button("Upgrades").observe(enter_upgrades);
             children.button("Shop").observe(enter_shop);
+            children.button("Achievements").observe(enter_achievements);
             children.button("Credits").observe(enter_credits);
             #[cfg(not(target_family = "wasm"))]
             children.button("Exit").observe(exit_app);
@@ -123,20 +122,26 @@ fn enter_shop(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Over
     next_screen.set(OverlayShopState::Opened);
 }
 
-fn enter_playing(
+fn enter_class_select(
     _trigger: Trigger<OnPress>,
     mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
-    mut next_simulation_state: ResMut<NextState<SimulationState>>,
     first_time_query: Query<Entity, (With<FirstTime>, Without<PlayButton>)>,
 ) {
-    next_simulation_state.set(SimulationState::Pause);
-    next_screen.set(Screen::Playing);
+    next_screen.set(Screen::ClassSelect);
     if let Ok(entity) = first_time_query.get_single() {
         commands.entity(entity).despawn();
     }
 }
 
+fn enter_upgrades(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Upgrades);
+}
+
+fn enter_achievements(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Achievements);
+}
+
 fn enter_credits(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Credits);
 }
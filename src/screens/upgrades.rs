@@ -0,0 +1,143 @@
+//! The upgrade screen between runs, where essence is spent on permanent upgrades.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::meta::{MetaProgress, MetaUpgrade},
+    state::Screen,
+    theme::prelude::*,
+};
+
+#[derive(Component)]
+struct UpgradeChoice(MetaUpgrade);
+
+#[derive(Component)]
+struct EssenceText;
+
+#[derive(Component)]
+struct UpgradeRankText(MetaUpgrade);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Upgrades), show_upgrades_screen)
+        .add_systems(
+            Update,
+            update_upgrade_texts.run_if(in_state(Screen::Upgrades)),
+        );
+}
+
+fn show_upgrades_screen(mut commands: Commands, meta: Res<MetaProgress>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Upgrades))
+        .with_children(|children| {
+            children.header("Upgrades");
+
+            if meta.last_award > 0 {
+                children.label(format!("Last run earned {} essence", meta.last_award));
+            }
+            children.label(essence_text(&meta)).insert(EssenceText);
+
+            for upgrade in MetaUpgrade::iterator() {
+                children
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(10.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.button(upgrade.name())
+                            .insert(UpgradeChoice(*upgrade))
+                            .observe(buy_upgrade);
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(5.),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column
+                                .label(rank_text(&meta, *upgrade))
+                                .insert(UpgradeRankText(*upgrade));
+                            column.label(upgrade.description());
+                        });
+                    });
+            }
+
+            children.button("Next Run").observe(enter_class_select);
+            children.button("Title").observe(enter_title);
+        });
+}
+
+fn essence_text(meta: &MetaProgress) -> String {
+    format!("Essence: {}", meta.essence)
+}
+
+fn rank_text(meta: &MetaProgress, upgrade: MetaUpgrade) -> String {
+    let rank = meta.rank(upgrade);
+    if rank >= upgrade.max_rank() {
+        format!("Rank {}/{} (max)", rank, upgrade.max_rank())
+    } else {
+        format!(
+            "Rank {}/{} - next costs {}",
+            rank,
+            upgrade.max_rank(),
+            upgrade.cost(rank)
+        )
+    }
+}
+
+fn buy_upgrade(
+    trigger: Trigger<OnPress>,
+    choice_query: Query<&UpgradeChoice>,
+    mut meta: ResMut<MetaProgress>,
+) {
+    let Ok(choice) = choice_query.get(trigger.entity()) else {
+        return;
+    };
+
+    match meta.buy(choice.0) {
+        Ok(cost) => info!("Bought {} for {} essence", choice.0.name(), cost),
+        Err(_) => info!("Cannot buy {}", choice.0.name()),
+    }
+}
+
+// label() keeps its text on a child entity
+fn update_upgrade_texts(
+    meta: Res<MetaProgress>,
+    essence_query: Query<&Children, With<EssenceText>>,
+    rank_query: Query<(&Children, &UpgradeRankText)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !meta.is_changed() {
+        return;
+    }
+
+    let mut set_text = |children: &Children, value: String| {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = value.clone();
+            }
+        }
+    };
+
+    for children in essence_query.iter() {
+        set_text(children, essence_text(&meta));
+    }
+    for (children, rank) in rank_query.iter() {
+        set_text(children, rank_text(&meta, rank.0));
+    }
+}
+
+fn enter_class_select(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::ClassSelect);
+}
+
+fn enter_title(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
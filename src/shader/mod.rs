@@ -1,3 +1,6 @@
+// The ShaderType derive emits per-field size checks that are never called.
+#![allow(dead_code)]
+
 use bevy::{
     prelude::*,
     render::render_resource::{AsBindGroup, ShaderRef, ShaderType},
@@ -1,6 +1,10 @@
 //!
 //! Most of the boilerplate to make a custom shader work lives here.
 //!
+
+// Shaders kept around for later may trigger this lint undesirably.
+#![allow(dead_code)]
+
 use std::path::PathBuf;
 pub mod component;
 
@@ -27,9 +27,12 @@ pub enum Screen {
     Splash,
     Loading,
     Title,
+    ClassSelect,
     Credits,
     Playing,
     GameOver,
+    Upgrades,
+    Achievements,
 }
 
 // This state is mainly controlling Player ButtonInput during gameplay
@@ -43,7 +46,6 @@ pub enum SimulationState {
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 
 pub enum OverlayDroppingPickingState {
-    Pause,
     Opened,
     #[default]
     Closed,
@@ -52,7 +54,6 @@ pub enum OverlayDroppingPickingState {
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 
 pub enum OverlayEndingCreditState {
-    Pause,
     Opened,
     #[default]
     Closed,
@@ -65,7 +66,6 @@ pub enum OverlayCombatState {
     Opened,
     #[default]
     Closed,
-    Pause,
 }
 
 // This state controls different stage of
@@ -92,7 +92,6 @@ pub enum OverlayScoreBoardState {
     Opened,
     #[default]
     Closed,
-    Popup,
 }
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]